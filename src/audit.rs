@@ -0,0 +1,61 @@
+// ── Registro CSV dos candidatos derivados (--audit-csv) ──────────────────────
+//
+// Uma linha por candidato checksum-valido que passou pelo PBKDF2: indice de
+// permutacao, fingerprint da chave mestra, caminho e endereco derivado. A
+// frase nao vai no arquivo: o indice, com as mesmas palavras e opcoes, ja a
+// identifica. As linhas de cada janela saem em ordem de indice.
+
+use anyhow::{Context, Result};
+use bitcoin::bip32::{DerivationPath, Fingerprint};
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+const HEADER: &str = "index,fingerprint,path,address\n";
+
+/// One derived candidate.
+#[derive(Debug, Clone)]
+pub struct AuditRow {
+    pub index:       u64,
+    pub fingerprint: Fingerprint,
+    pub path:        DerivationPath,
+    pub address:     String,
+}
+
+/// Append-only CSV of every candidate the search derived.
+pub struct AuditLog {
+    path:   PathBuf,
+    writer: BufWriter<File>,
+}
+
+impl AuditLog {
+    /// Opens `path` for appending (a resumed search continues the same file);
+    /// writes the header when the file is new or empty.
+    pub fn create(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)
+            .with_context(|| format!("Failed to open audit CSV {}", path.display()))?;
+        let empty = file.metadata().map(|m| m.len() == 0).unwrap_or(true);
+        let mut log = Self { path: path.to_path_buf(), writer: BufWriter::new(file) };
+        if empty {
+            log.writer.write_all(HEADER.as_bytes()).with_context(|| log.context())?;
+        }
+        Ok(log)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends `rows` and flushes, so an interrupted search leaves complete lines.
+    pub fn write(&mut self, rows: &[AuditRow]) -> Result<()> {
+        for row in rows {
+            writeln!(self.writer, "{},{},m/{},{}", row.index, row.fingerprint, row.path, row.address)
+                .with_context(|| self.context())?;
+        }
+        self.writer.flush().with_context(|| self.context())
+    }
+
+    fn context(&self) -> String {
+        format!("Failed to write audit CSV {}", self.path.display())
+    }
+}
@@ -0,0 +1,109 @@
+// ── Benchmark por etapa ──────────────────────────────────────────────────────
+//
+// Mede a vazao de cada etapa do pipeline nesta maquina (todas as threads do
+// Rayon) e estima a velocidade de uma busca: cada permutacao paga o filtro de
+// checksum; so ~1/2^(n/3) (1/16 com 12 palavras, 1/256 com 24) pagam PBKDF2 +
+// BIP32 + endereco.
+
+use crate::coin::Coin;
+use crate::derivation::{derive_pubkey, script_from_pubkey, AddressType};
+use crate::gpu::SeedBackend;
+use crate::pbkdf2;
+use crate::mnemonic::checksum_valid;
+use crate::permutation::{candidate_at_index, candidate_count, permutation_at_index, Slot};
+use anyhow::Result;
+use bitcoin::bip32::DerivationPath;
+use rayon::prelude::*;
+use std::hint::black_box;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+pub const ADDRESS_TYPES: [AddressType; 4] = AddressType::ALL;
+
+/// Measured throughput of each pipeline stage, in operations per second.
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    /// Permutation + BIP-39 checksum filter (12 words)
+    pub checksum: f64,
+    /// PBKDF2 seed stretching on the CPU
+    pub pbkdf2: f64,
+    /// PBKDF2 on the GPU backend, if one was given
+    pub pbkdf2_gpu: Option<f64>,
+    /// BIP32 derivation from the seed down to the address key (5 levels)
+    pub bip32: f64,
+    /// scriptPubKey + address encoding, per address type
+    pub address: Vec<(AddressType, f64)>,
+}
+
+impl BenchReport {
+    /// Estimated permutations/sec of a search with `n` words (12 to 24) for `address_type`.
+    pub fn estimate(&self, n: usize, address_type: AddressType) -> f64 {
+        let pass = 1.0 / (1u64 << (n / 3)) as f64;
+        let pbkdf2 = self.pbkdf2_gpu.unwrap_or(self.pbkdf2);
+        let address = self.address.iter()
+            .find(|(t, _)| *t == address_type)
+            .map_or(f64::INFINITY, |&(_, r)| r);
+        1.0 / (1.0 / self.checksum + pass * (1.0 / pbkdf2 + 1.0 / self.bip32 + 1.0 / address))
+    }
+}
+
+// Roda `f` em paralelo, em lotes que dobram de tamanho, ate passar `duration`
+fn measure(duration: Duration, f: impl Fn(u64) + Sync) -> f64 {
+    let start = Instant::now();
+    let mut done  = 0u64;
+    let mut batch = rayon::current_num_threads() as u64;
+    while start.elapsed() < duration {
+        (done..done + batch).into_par_iter().for_each(&f);
+        done  += batch;
+        batch *= 2;
+    }
+    done as f64 / start.elapsed().as_secs_f64()
+}
+
+/// Benchmark every stage for about `duration` each.
+pub fn run(duration: Duration, backend: Option<&mut dyn SeedBackend>) -> Result<BenchReport> {
+    let free: Vec<u16> = (0..12).map(|i| i * 170).collect();
+    let slots = vec![Slot::Free; 12];
+    let total = candidate_count(12, 0);
+    let checksum = measure(duration, |i| {
+        black_box(checksum_valid(&candidate_at_index(&free, &slots, i as u128 % total, permutation_at_index)));
+    });
+
+    // Em lotes, como na busca: com AVX2 saem LANES seeds por chamada
+    let pbkdf2 = measure(duration, |_| {
+        black_box(pbkdf2::seeds(&[(PHRASE, ""); pbkdf2::LANES]));
+    }) * pbkdf2::LANES as f64;
+
+    let pbkdf2_gpu = match backend {
+        Some(backend) => {
+            let phrases = vec![PHRASE.to_string(); backend.batch_size()];
+            let salts   = vec![b"mnemonic".as_slice(); phrases.len()];
+            backend.seeds(&phrases, &salts)?; // aquecimento (compilacao de kernels, buffers)
+            let start = Instant::now();
+            let mut done = 0u64;
+            while start.elapsed() < duration {
+                black_box(backend.seeds(&phrases, &salts)?);
+                done += phrases.len() as u64;
+            }
+            Some(done as f64 / start.elapsed().as_secs_f64())
+        }
+        None => None,
+    };
+
+    let seed = pbkdf2::seed(PHRASE, "");
+    let path = DerivationPath::from_str("m/84'/0'/0'/0/0")?;
+    let bip32 = measure(duration, |_| {
+        black_box(derive_pubkey(&seed, &path));
+    });
+
+    let pubkey = derive_pubkey(&seed, &path).expect("valid test path");
+    let address = ADDRESS_TYPES.iter()
+        .map(|&t| (t, measure(duration, |_| {
+            black_box(Coin::Bitcoin.encode_address(&script_from_pubkey(pubkey, t)));
+        })))
+        .collect();
+
+    Ok(BenchReport { checksum, pbkdf2, pbkdf2_gpu, bip32, address })
+}
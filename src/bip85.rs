@@ -0,0 +1,109 @@
+// ── BIP85: mnemonicos filhos de uma seed mestra ──────────────────────────────
+//
+// Uma carteira pode ter saido de um filho BIP85 da frase mestra: a chave em
+// m/83696968'/39'/{lingua}'/{palavras}'/{indice}' vira entropia por
+// HMAC-SHA512("bip-entropy-from-k", chave), truncada para 16, 24 ou 32 bytes
+// (12, 18 ou 24 palavras), e a entropia vira o mnemonico filho. O filho tem a
+// propria seed (PBKDF2, sem passphrase): com --bip85-index-range, cada seed
+// candidata custa um PBKDF2 a mais por filho, mas o filho so e derivado
+// quando a seed mestra nao casou.
+
+use crate::derivation::{hmac_sha512, Node};
+use bip39::{Language, Mnemonic};
+use bitcoin::bip32::ChildNumber;
+use std::ops::Range;
+
+// m/83696968'/39': aplicacao BIP39 do BIP85
+const PURPOSE: u32 = 83_696_968;
+const APPLICATION: u32 = 39;
+const ENTROPY_KEY: &[u8] = b"bip-entropy-from-k";
+
+/// Word counts BIP85 defines for BIP39 children.
+pub const WORD_COUNTS: [usize; 3] = [12, 18, 24];
+
+/// A BIP85 child mnemonic of a master seed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Child {
+    pub index:  u32,
+    pub words:  usize,
+    pub phrase: String,
+}
+
+impl Child {
+    /// Derivation path of the child's entropy from the master key, e.g. `m/83696968'/39'/0'/12'/0'`.
+    pub fn path(&self, language: Language) -> String {
+        format!("m/{}'/{}'/{}'/{}'/{}'", PURPOSE, APPLICATION, language_code(language).unwrap_or_default(), self.words, self.index)
+    }
+}
+
+/// Children tried for every candidate seed: each index with each word count.
+#[derive(Debug, Clone)]
+pub struct Children {
+    pub indices:     Range<u32>,
+    pub word_counts: Vec<usize>,
+    pub language:    Language,
+}
+
+impl Children {
+    /// Number of children per seed.
+    pub fn len(&self) -> usize {
+        self.indices.len() * self.word_counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Every child of `seed`, by index and then word count; `None` for a seed BIP32 rejects.
+    pub fn of_seed(&self, seed: &[u8]) -> Option<Vec<Child>> {
+        let master = Node::master(seed)?;
+        self.indices.clone()
+            .flat_map(|index| self.word_counts.iter().map(move |&words| (index, words)))
+            .map(|(index, words)| child_mnemonic(&master, self.language, words, index))
+            .collect()
+    }
+}
+
+/// BIP85 code of `language`; `None` for the wordlists BIP85 leaves out (Portuguese).
+pub fn language_code(language: Language) -> Option<u32> {
+    match language {
+        Language::English            => Some(0),
+        Language::Japanese           => Some(1),
+        Language::Korean             => Some(2),
+        Language::Spanish            => Some(3),
+        Language::SimplifiedChinese  => Some(4),
+        Language::TraditionalChinese => Some(5),
+        Language::French             => Some(6),
+        Language::Italian            => Some(7),
+        Language::Czech              => Some(8),
+        _                            => None,
+    }
+}
+
+/// Child mnemonic number `index` with `words` words (12, 18 or 24) of the master key `master`.
+///
+/// ```
+/// use brute_force_mnemonics::bip85::child_mnemonic;
+/// use brute_force_mnemonics::derivation::Node;
+/// use bip39::Language;
+/// use bitcoin::bip32::Xpriv;
+/// use std::str::FromStr;
+///
+/// // Test vector of BIP85
+/// let xprv = Xpriv::from_str("xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb")?;
+/// let child = child_mnemonic(&Node::from_xpriv(&xprv), Language::English, 12, 0).unwrap();
+/// assert_eq!(child.phrase, "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose");
+/// assert_eq!(child.path(Language::English), "m/83696968'/39'/0'/12'/0'");
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn child_mnemonic(master: &Node, language: Language, words: usize, index: u32) -> Option<Child> {
+    if !WORD_COUNTS.contains(&words) {
+        return None;
+    }
+    let path = [PURPOSE, APPLICATION, language_code(language)?, words as u32, index]
+        .map(|i| ChildNumber::Hardened { index: i });
+    let key = master.derive(&path)?.key.secret_bytes();
+    let entropy = hmac_sha512(ENTROPY_KEY, &[&key]);
+    let phrase = Mnemonic::from_entropy_in(language, &entropy[..words * 4 / 3]).ok()?.to_string();
+    Some(Child { index, words, phrase })
+}
@@ -0,0 +1,257 @@
+// ── Alvos num filtro de Bloom (--bloom-fp) ───────────────────────────────────
+//
+// Com milhoes de enderecos (o dump de enderecos de deposito de uma exchange) o
+// conjunto de hash do Matcher custa dezenas de bytes por alvo; o filtro custa
+// ~1.44·log2(1/p) bits, p a taxa de falso positivo pedida. Um "talvez" do
+// filtro e conferido relendo o arquivo: com p = 1e-6, uma releitura a cada
+// milhao de candidatos, e nunca um acerto falso.
+//
+// Os bits guardam o conteudo do script (ver `Payload`), o mesmo que o Matcher
+// compara: scripts que nao saem de uma chave so (P2WSH) ficam de fora.
+//
+// Com --funded-index o filtro sai do indice de enderecos com saldo (ver
+// funded.rs), e o "talvez" e conferido por busca binaria nele, sem releitura.
+
+use crate::coin::Coin;
+use crate::derivation::{AddressType, Payload};
+use crate::funded::{self, FundedIndex};
+use crate::target::script_type;
+use anyhow::{Context, Result};
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Bit array with `k` probes per item (double hashing).
+pub struct Bloom {
+    bits: Vec<u64>,
+    m:    u64,
+    k:    u32,
+}
+
+impl Bloom {
+    /// Sized for `items` at a false-positive rate `rate` (0 < rate < 1).
+    ///
+    /// ```
+    /// use brute_force_mnemonics::bloom::Bloom;
+    ///
+    /// let mut bloom = Bloom::with_rate(1000, 1e-6);
+    /// for i in 0..1000u32 {
+    ///     bloom.insert(&i);
+    /// }
+    /// assert!((0..1000u32).all(|i| bloom.contains(&i)));
+    /// assert!((1000..101_000u32).filter(|i| bloom.contains(i)).count() < 5);
+    /// assert!(bloom.bytes() < 1000 * 4);
+    /// ```
+    pub fn with_rate(items: u64, rate: f64) -> Self {
+        let ln2 = std::f64::consts::LN_2;
+        let m = ((items.max(1) as f64) * -rate.ln() / (ln2 * ln2)).ceil().max(64.0) as u64;
+        let k = ((m as f64 / items.max(1) as f64) * ln2).round().clamp(1.0, 32.0) as u32;
+        Self { bits: vec![0; m.div_ceil(64) as usize], m, k }
+    }
+
+    pub fn insert<T: Hash>(&mut self, item: &T) {
+        for bit in self.probes(item) {
+            self.bits[(bit / 64) as usize] |= 1 << (bit % 64);
+        }
+    }
+
+    /// `false`: certainly not inserted; `true`: probably inserted.
+    pub fn contains<T: Hash>(&self, item: &T) -> bool {
+        self.probes(item).all(|bit| self.bits[(bit / 64) as usize] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Size of the bit array.
+    pub fn bytes(&self) -> usize {
+        self.bits.len() * 8
+    }
+
+    // h1 + i·h2 (Kirsch-Mitzenmacher): dois hashes bastam para os k
+    fn probes<T: Hash>(&self, item: &T) -> impl Iterator<Item = u64> {
+        let hash = |salt: u64| {
+            let mut h = DefaultHasher::new();
+            salt.hash(&mut h);
+            item.hash(&mut h);
+            h.finish()
+        };
+        let (h1, h2, m) = (hash(0), hash(1) | 1, self.m);
+        (0..u64::from(self.k)).map(move |i| h1.wrapping_add(i.wrapping_mul(h2)) % m)
+    }
+}
+
+/// Targets of a file held in a [`Bloom`] filter; probable hits are re-checked against
+/// the file itself, or against the [`FundedIndex`] the filter was built from.
+pub struct BloomTargets {
+    filter:     Bloom,
+    exact:      Exact,
+    count:      u64,
+    skipped:    u64,
+    types:      Vec<AddressType>,
+    false_hits: AtomicU64,
+}
+
+// Onde um provavel acerto do filtro e conferido
+enum Exact {
+    File { path: PathBuf, coin: Coin },
+    Index(FundedIndex),
+}
+
+impl fmt::Debug for BloomTargets {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BloomTargets({}, {} addresses)", self.path().display(), self.count)
+    }
+}
+
+impl BloomTargets {
+    /// Reads the addresses of `path` (one per line, blank lines and `#` comments skipped)
+    /// twice: to count them, then into a filter sized for `rate`.
+    pub fn load(path: &Path, coin: Coin, rate: f64) -> Result<Self> {
+        if !(rate > 0.0 && rate < 1.0) {
+            anyhow::bail!("The false-positive rate must be between 0 and 1, got {}", rate);
+        }
+        let mut lines = 0;
+        for_each_address(path, |_, _| {
+            lines += 1;
+            Ok(())
+        })?;
+        if lines == 0 {
+            anyhow::bail!("Targets file {} has no addresses", path.display());
+        }
+        let mut filter  = Bloom::with_rate(lines, rate);
+        let mut count   = 0;
+        let mut skipped = 0;
+        let mut types   = Vec::new();
+        for_each_address(path, |line, address| {
+            let script = coin.parse_address(address).with_context(|| format!("Targets file, line {}", line))?;
+            match Payload::from_script(&script) {
+                Some(payload) => {
+                    filter.insert(&payload);
+                    count += 1;
+                }
+                None => skipped += 1,
+            }
+            if let Some(t) = script_type(&script).filter(|t| !types.contains(t)) {
+                types.push(t);
+            }
+            Ok(())
+        })?;
+        let exact = Exact::File { path: path.to_path_buf(), coin };
+        Ok(Self { filter, exact, count, skipped, types, false_hits: AtomicU64::new(0) })
+    }
+
+    /// Every record of `index` in a filter sized for `rate`; probable hits are looked up
+    /// in the index.
+    pub fn from_index(index: FundedIndex, rate: f64) -> Result<Self> {
+        if !(rate > 0.0 && rate < 1.0) {
+            anyhow::bail!("The false-positive rate must be between 0 and 1, got {}", rate);
+        }
+        let mut filter = Bloom::with_rate(index.len(), rate);
+        let mut types  = Vec::new();
+        for entry in index.entries()? {
+            let (payload, _) = entry?;
+            filter.insert(&payload);
+            let t = funded::payload_type(&payload);
+            if !types.contains(&t) {
+                types.push(t);
+            }
+        }
+        let count = index.len();
+        Ok(Self { filter, exact: Exact::Index(index), count, skipped: 0, types, false_hits: AtomicU64::new(0) })
+    }
+
+    /// Addresses in the filter.
+    pub fn len(&self) -> u64 {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Addresses left out: no single key derives them (P2WSH).
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+
+    /// Address types found in the file, in order of first appearance.
+    pub fn types(&self) -> &[AddressType] {
+        &self.types
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.filter.bytes()
+    }
+
+    /// The targets file, or the index.
+    pub fn path(&self) -> &Path {
+        match &self.exact {
+            Exact::File { path, .. } => path,
+            Exact::Index(index)      => index.path(),
+        }
+    }
+
+    /// The funded-address index behind the filter, if it was built from one.
+    pub fn index(&self) -> Option<&FundedIndex> {
+        match &self.exact {
+            Exact::Index(index) => Some(index),
+            Exact::File { .. }  => None,
+        }
+    }
+
+    /// Probable hits of the filter the file did not confirm.
+    pub fn false_hits(&self) -> u64 {
+        self.false_hits.load(Ordering::Relaxed)
+    }
+
+    /// Is `payload` one of the targets? The filter answers most candidates; the rare
+    /// probable hit is confirmed by reading the file again (or by a lookup in the index).
+    pub fn contains(&self, payload: &Payload) -> Result<bool> {
+        if !self.filter.contains(payload) {
+            return Ok(false);
+        }
+        let found = match &self.exact {
+            Exact::Index(index) => index.balance(payload)?.is_some(),
+            Exact::File { path, coin } => {
+                let mut found = false;
+                for_each_address(path, |_, address| {
+                    let script = coin.parse_address(address)?;
+                    found = found || Payload::from_script(&script).as_ref() == Some(payload);
+                    Ok(())
+                })?;
+                found
+            }
+        };
+        if !found {
+            self.false_hits.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(found)
+    }
+}
+
+/// First address of a targets file (the coin of the search when `--coin` is not given).
+pub fn first_address(path: &Path) -> Result<Option<String>> {
+    addresses(path)?.next().transpose().map(|first| first.map(|(_, address)| address))
+}
+
+// (numero da linha, endereco) de cada linha util, lendo aos poucos
+fn addresses(path: &Path) -> Result<impl Iterator<Item = Result<(usize, String)>> + '_> {
+    let file = File::open(path).with_context(|| format!("Failed to read targets file {}", path.display()))?;
+    Ok(BufReader::new(file).lines().enumerate().filter_map(move |(i, line)| match line {
+        Ok(line) => {
+            let line = line.trim();
+            (!line.is_empty() && !line.starts_with('#')).then(|| Ok((i + 1, line.to_string())))
+        }
+        Err(e) => Some(Err(anyhow::Error::new(e).context(format!("Failed to read targets file {}", path.display())))),
+    }))
+}
+
+fn for_each_address(path: &Path, mut f: impl FnMut(usize, &str) -> Result<()>) -> Result<()> {
+    for entry in addresses(path)? {
+        let (line, address) = entry?;
+        f(line, &address)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,268 @@
+// ── Cardano (CIP-1852): chaves Icarus e BIP32-Ed25519 ────────────────────────
+//
+// Chave mestre Icarus: PBKDF2-HMAC-SHA512(passphrase, entropia BIP-39, 4096)
+// → 96 bytes kL | kR | chain code, com kL ajustado (bits 0-2 e 255 zerados,
+// 254 setado). Derivacao BIP32-Ed25519: kL' = kL + 8·zL (28 bytes) e
+// kR' = kR + zR; passos nao-hardened usam a chave publica A = kL·B.
+// Caminho m/1852'/1815'/0'/cadeia/i.
+//
+// Enderecos Shelley (base addr1q..., enterprise addr1v...) trazem o
+// blake2b-224 da chave de pagamento logo apos o cabecalho: a busca compara so
+// esse hash, sem derivar a chave de stake.
+
+use crate::coin::Coin;
+use crate::ed25519;
+use crate::mnemonic::{build_phrase, checksum_valid, entropy};
+use crate::permutation::{order_at_index, order_count};
+use anyhow::{Context, Result};
+use bitcoin::bech32;
+use bitcoin::hashes::hmac::{Hmac, HmacEngine};
+use bitcoin::hashes::{sha512, Hash, HashEngine};
+use rayon::prelude::*;
+use std::ops::Range;
+
+const PURPOSE: u32 = 1852;
+const ICARUS_ITERATIONS: u32 = 4096;
+const HARDENED: u32 = 1 << 31;
+
+/// Length of a payment key hash (blake2b-224).
+pub const KEY_HASH_LEN: usize = 28;
+
+// Cabecalho: tipo (4 bits altos) | rede (4 bits baixos, 1 = mainnet)
+const NETWORK_MAINNET: u8 = 1;
+
+pub fn is_address(s: &str) -> bool {
+    s.to_lowercase().starts_with("addr1")
+}
+
+/// Payment key hash of a Shelley mainnet address whose payment part is a key
+/// (base, pointer or enterprise address).
+pub fn parse_address(s: &str) -> Result<[u8; KEY_HASH_LEN]> {
+    let (hrp, data) = bech32::decode(s).with_context(|| format!("Invalid Cardano address: {}", s))?;
+    if hrp.as_str() != "addr" || data.first().is_none_or(|h| h & 0x0f != NETWORK_MAINNET) {
+        anyhow::bail!("{} is not a mainnet Cardano address", s);
+    }
+    // Tipos pares (0, 2, 4, 6) tem chave de pagamento; impares sao scripts, 8+ nao sao de pagamento
+    if data[0] >> 4 > 7 || (data[0] >> 4) % 2 == 1 || data.len() < 1 + KEY_HASH_LEN {
+        anyhow::bail!("{} has no payment key (script, Byron or reward address)", s);
+    }
+    Ok(data[1..1 + KEY_HASH_LEN].try_into().expect("28-byte hash"))
+}
+
+// ── BLAKE2b-224 ──────────────────────────────────────────────────────────────
+const BLAKE2B_IV: [u64; 8] = [
+    0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+    0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+];
+
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+fn blake2b_compress(h: &mut [u64; 8], block: &[u8; 128], counter: u128, last: bool) {
+    let m: [u64; 16] = std::array::from_fn(|i| u64::from_le_bytes(block[8 * i..8 * i + 8].try_into().expect("8 bytes")));
+    let mut v = [0u64; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&BLAKE2B_IV);
+    v[12] ^= counter as u64;
+    v[13] ^= (counter >> 64) as u64;
+    if last {
+        v[14] = !v[14];
+    }
+    let mut g = |a: usize, b: usize, c: usize, d: usize, x: u64, y: u64| {
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+        v[d] = (v[d] ^ v[a]).rotate_right(32);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(24);
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+        v[d] = (v[d] ^ v[a]).rotate_right(16);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(63);
+    };
+    for round in 0..12 {
+        let s = &SIGMA[round % 10];
+        g(0, 4, 8, 12, m[s[0]], m[s[1]]);
+        g(1, 5, 9, 13, m[s[2]], m[s[3]]);
+        g(2, 6, 10, 14, m[s[4]], m[s[5]]);
+        g(3, 7, 11, 15, m[s[6]], m[s[7]]);
+        g(0, 5, 10, 15, m[s[8]], m[s[9]]);
+        g(1, 6, 11, 12, m[s[10]], m[s[11]]);
+        g(2, 7, 8, 13, m[s[12]], m[s[13]]);
+        g(3, 4, 9, 14, m[s[14]], m[s[15]]);
+    }
+    for i in 0..8 {
+        h[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+fn blake2b_224(data: &[u8]) -> [u8; KEY_HASH_LEN] {
+    let mut h = BLAKE2B_IV;
+    h[0] ^= 0x0101_0000 ^ KEY_HASH_LEN as u64; // sem chave, saida de 28 bytes
+    let mut counter = 0u128;
+    let mut chunks = data.chunks(128).peekable();
+    loop {
+        let chunk = chunks.next().unwrap_or_default();
+        let last  = chunks.peek().is_none();
+        let mut block = [0u8; 128];
+        block[..chunk.len()].copy_from_slice(chunk);
+        counter += chunk.len() as u128;
+        blake2b_compress(&mut h, &block, counter, last);
+        if last {
+            break;
+        }
+    }
+    let mut out = [0u8; KEY_HASH_LEN];
+    for (chunk, word) in out.chunks_mut(8).zip(h) {
+        chunk.copy_from_slice(&word.to_le_bytes()[..chunk.len()]);
+    }
+    out
+}
+
+// ── Chaves BIP32-Ed25519 ─────────────────────────────────────────────────────
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    let mut engine = HmacEngine::<sha512::Hash>::new(key);
+    for d in data {
+        engine.input(d);
+    }
+    Hmac::<sha512::Hash>::from_engine(engine).to_byte_array()
+}
+
+// a + b mod 2^256, little-endian
+fn add_le(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let mut out   = [0u8; 32];
+    let mut carry = 0u16;
+    for i in 0..32 {
+        let s = a[i] as u16 + b[i] as u16 + carry;
+        out[i] = s as u8;
+        carry  = s >> 8;
+    }
+    out
+}
+
+#[derive(Clone)]
+struct ExtendedKey {
+    kl:    [u8; 32],
+    kr:    [u8; 32],
+    chain: [u8; 32],
+}
+
+impl ExtendedKey {
+    // Icarus: 2 blocos de PBKDF2-HMAC-SHA512 (96 bytes)
+    fn master(entropy: &[u8], passphrase: &str) -> ExtendedKey {
+        let mut out = [0u8; 128];
+        for (block, chunk) in out.chunks_mut(64).enumerate() {
+            let mut u = hmac_sha512(passphrase.as_bytes(), &[entropy, &(block as u32 + 1).to_be_bytes()]);
+            chunk.copy_from_slice(&u);
+            for _ in 1..ICARUS_ITERATIONS {
+                u = hmac_sha512(passphrase.as_bytes(), &[&u]);
+                chunk.iter_mut().zip(&u).for_each(|(o, b)| *o ^= b);
+            }
+        }
+        out[0]  &= 0xf8;
+        out[31] &= 0x1f;
+        out[31] |= 0x40;
+        ExtendedKey {
+            kl:    out[..32].try_into().expect("32 bytes"),
+            kr:    out[32..64].try_into().expect("32 bytes"),
+            chain: out[64..96].try_into().expect("32 bytes"),
+        }
+    }
+
+    fn public(&self) -> [u8; 32] {
+        ed25519::public_key(&ed25519::reduce32(&self.kl))
+    }
+
+    fn child(&self, index: u32) -> ExtendedKey {
+        let i = index.to_le_bytes();
+        let (z, c) = if index >= HARDENED {
+            (hmac_sha512(&self.chain, &[&[0x00], &self.kl, &self.kr, &i]),
+             hmac_sha512(&self.chain, &[&[0x01], &self.kl, &self.kr, &i]))
+        } else {
+            let a = self.public();
+            (hmac_sha512(&self.chain, &[&[0x02], &a, &i]),
+             hmac_sha512(&self.chain, &[&[0x03], &a, &i]))
+        };
+
+        // 8·zL: os 28 primeiros bytes de Z deslocados 3 bits
+        let mut zl8 = [0u8; 32];
+        let mut carry = 0u8;
+        for (o, &b) in zl8.iter_mut().zip(&z[..28]) {
+            *o    = (b << 3) | carry;
+            carry = b >> 5;
+        }
+        zl8[28] = carry;
+        ExtendedKey {
+            kl:    add_le(&self.kl, &zl8),
+            kr:    add_le(&self.kr, z[32..].try_into().expect("32 bytes")),
+            chain: c[32..].try_into().expect("32 bytes"),
+        }
+    }
+}
+
+// ── Busca ────────────────────────────────────────────────────────────────────
+
+/// Parameters of a Cardano (CIP-1852) search.
+#[derive(Debug, Clone)]
+pub struct CardanoConfig {
+    /// Payment key hashes of the target addresses
+    pub targets: Vec<[u8; KEY_HASH_LEN]>,
+    /// Word indices in the BIP-39 wordlist, in the given order
+    pub words: Vec<u16>,
+    pub wordlist: &'static [&'static str],
+    pub passphrase: String,
+    /// Chains checked for every seed: 0 = external, 1 = internal
+    pub chains: Vec<u32>,
+    /// Address indices checked on every chain
+    pub derivation_indices: Range<u32>,
+    pub max_swaps: Option<usize>,
+    pub max_permutations: Option<u64>,
+}
+
+/// A mnemonic whose payment key matches one of the targets.
+#[derive(Debug, Clone)]
+pub struct CardanoMatch {
+    pub phrase: String,
+    pub index:  u64,
+    pub path:   String,
+}
+
+impl CardanoConfig {
+    /// Permutations visited by `search`.
+    pub fn total_permutations(&self) -> u64 {
+        let total = order_count(self.words.len(), self.max_swaps).min(u64::MAX as u128) as u64;
+        self.max_permutations.unwrap_or(u64::MAX).min(total)
+    }
+
+    /// First order of the words (given order first) that derives to a target.
+    pub fn search(&self) -> Option<CardanoMatch> {
+        (0..self.total_permutations()).into_par_iter().find_map_first(|i| {
+            let perm = order_at_index(&self.words, self.max_swaps, i as u128);
+            if !checksum_valid(&perm) {
+                return None;
+            }
+            let account = [PURPOSE | HARDENED, Coin::Cardano.coin_type() | HARDENED, HARDENED].iter()
+                .fold(ExtendedKey::master(&entropy(&perm), &self.passphrase), |k, &c| k.child(c));
+            self.chains.iter().find_map(|&chain| {
+                let chain_key = account.child(chain);
+                self.derivation_indices.clone().find(|&a| {
+                    let hash = blake2b_224(&chain_key.child(a).public());
+                    self.targets.contains(&hash)
+                })
+                .map(|a| CardanoMatch {
+                    phrase: build_phrase(&perm, self.wordlist),
+                    index:  i,
+                    path:   format!("m/{}'/{}'/0'/{}/{}", PURPOSE, Coin::Cardano.coin_type(), chain, a),
+                })
+            })
+        })
+    }
+}
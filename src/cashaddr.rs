@@ -0,0 +1,99 @@
+// ── CashAddr (Bitcoin Cash) ──────────────────────────────────────────────────
+//
+// prefix:payload, payload em base32 (mesmo alfabeto do bech32) com checksum
+// BCH de 40 bits. O primeiro byte do payload e a versao: tipo << 3 | tamanho
+// (tipo 0 = P2PKH, 1 = P2SH; tamanho 0 = hash de 160 bits).
+
+use anyhow::{Context, Result};
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+pub const TYPE_P2PKH: u8 = 0;
+pub const TYPE_P2SH: u8 = 1;
+
+fn polymod(values: impl Iterator<Item = u8>) -> u64 {
+    const GEN: [u64; 5] = [0x98f2bc8e61, 0x79b76d99e2, 0xf33e5fb3c4, 0xae2eabe2a8, 0x1e4f43e470];
+    let mut c: u64 = 1;
+    for d in values {
+        let c0 = c >> 35;
+        c = ((c & 0x07_ffff_ffff) << 5) ^ d as u64;
+        for (i, g) in GEN.iter().enumerate() {
+            if c0 & (1 << i) != 0 {
+                c ^= g;
+            }
+        }
+    }
+    c ^ 1
+}
+
+// Prefixo entra no checksum com os 5 bits baixos de cada caractere, seguido de 0
+fn prefix_values(prefix: &str) -> impl Iterator<Item = u8> + '_ {
+    prefix.bytes().map(|b| b & 0x1f).chain(std::iter::once(0))
+}
+
+// Reagrupa bits (8 → 5 com padding, 5 → 8 sem)
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+    let mut acc: u32 = 0;
+    let mut bits = 0;
+    let mut out  = Vec::new();
+    let max = (1u32 << to) - 1;
+    for &v in data {
+        acc   = (acc << from) | v as u32;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            out.push(((acc >> bits) & max) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            out.push(((acc << (to - bits)) & max) as u8);
+        }
+    } else if bits >= from || (acc << (to - bits)) & max != 0 {
+        return None;
+    }
+    Some(out)
+}
+
+/// Codifica `hash` (160 bits) como `prefix:payload`.
+pub fn encode(prefix: &str, kind: u8, hash: &[u8; 20]) -> String {
+    let mut payload = vec![kind << 3];
+    payload.extend_from_slice(hash);
+    let mut data = convert_bits(&payload, 8, 5, true).expect("padding never fails");
+
+    let checksum = polymod(prefix_values(prefix).chain(data.iter().copied()).chain([0; 8]));
+    data.extend((0..8).map(|i| ((checksum >> (5 * (7 - i))) & 0x1f) as u8));
+
+    let body: String = data.iter().map(|&d| CHARSET[d as usize] as char).collect();
+    format!("{}:{}", prefix, body)
+}
+
+/// Decodifica um endereco CashAddr (o prefixo e opcional); devolve (tipo, hash).
+pub fn decode(prefix: &str, s: &str) -> Result<(u8, [u8; 20])> {
+    if s.chars().any(|c| c.is_ascii_uppercase()) && s.chars().any(|c| c.is_ascii_lowercase()) {
+        anyhow::bail!("Mixed-case CashAddr address: {}", s);
+    }
+    let lower = s.to_lowercase();
+    let body = match lower.split_once(':') {
+        Some((p, body)) if p == prefix => body,
+        Some((p, _)) => anyhow::bail!("Unexpected CashAddr prefix '{}' (expected '{}')", p, prefix),
+        None => lower.as_str(),
+    };
+
+    let data = body.bytes()
+        .map(|b| CHARSET.iter().position(|&c| c == b).map(|v| v as u8))
+        .collect::<Option<Vec<u8>>>()
+        .with_context(|| format!("Invalid character in CashAddr address: {}", s))?;
+    if data.len() < 8 || polymod(prefix_values(prefix).chain(data.iter().copied())) != 0 {
+        anyhow::bail!("Invalid CashAddr checksum: {}", s);
+    }
+
+    let payload = convert_bits(&data[..data.len() - 8], 5, 8, false)
+        .with_context(|| format!("Invalid CashAddr padding: {}", s))?;
+    if payload.len() != 21 || payload[0] & 0x07 != 0 {
+        anyhow::bail!("Unsupported CashAddr hash size: {}", s);
+    }
+    let mut hash = [0u8; 20];
+    hash.copy_from_slice(&payload[1..]);
+    Ok((payload[0] >> 3, hash))
+}
@@ -0,0 +1,125 @@
+// ── Checkpoint / resume ──────────────────────────────────────────────────────
+//
+// Formato texto (chave=valor), uma entrada por linha:
+//   config=<sha256 hex da configuracao de busca>
+//   next_index=<primeiro indice de permutacao ainda NAO testado>
+//
+// A busca avanca em janelas sequenciais (paralelas por dentro), entao todo
+// indice < next_index ja foi testado — retomar a partir dele nao pula nada.
+// O arquivo e gravado num .tmp e renomeado: um kill no meio da gravacao deixa
+// o checkpoint anterior inteiro, nunca um arquivo pela metade.
+
+use anyhow::{Context, Result};
+use bitcoin::hashes::{sha256, Hash};
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How often the search position is saved: after a time or a number of permutations.
+///
+/// ```
+/// use brute_force_mnemonics::checkpoint::CheckpointInterval;
+/// use std::time::Duration;
+///
+/// assert_eq!("90s".parse::<CheckpointInterval>()?, CheckpointInterval::Time(Duration::from_secs(90)));
+/// assert_eq!("2m".parse::<CheckpointInterval>()?, CheckpointInterval::Time(Duration::from_secs(120)));
+/// assert_eq!("5000000".parse::<CheckpointInterval>()?, CheckpointInterval::Permutations(5_000_000));
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckpointInterval {
+    Time(Duration),
+    Permutations(u64),
+}
+
+impl FromStr for CheckpointInterval {
+    type Err = anyhow::Error;
+
+    // "30s", "5m", "1h": tempo; numero puro: permutacoes
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let (digits, unit) = match s.find(|c: char| !c.is_ascii_digit()) {
+            Some(i) => s.split_at(i),
+            None    => (s, ""),
+        };
+        let n: u64 = digits.parse()
+            .with_context(|| format!("Invalid checkpoint interval '{}' (expected e.g. 30s, 5m, 1h or a permutation count)", s))?;
+        if n == 0 {
+            anyhow::bail!("The checkpoint interval must be greater than zero");
+        }
+        let secs = match unit {
+            ""  => return Ok(CheckpointInterval::Permutations(n)),
+            "s" => n,
+            "m" => n.saturating_mul(60),
+            "h" => n.saturating_mul(3600),
+            _   => anyhow::bail!("Unknown unit '{}' in checkpoint interval '{}' (expected s, m or h)", unit, s),
+        };
+        Ok(CheckpointInterval::Time(Duration::from_secs(secs)))
+    }
+}
+
+pub struct Checkpoint {
+    path:   PathBuf,
+    digest: String,
+}
+
+/// SHA-256 (hex) dos parametros que definem o espaco de busca.
+pub fn config_digest(parts: &[&str]) -> String {
+    let joined = parts.join("\n");
+    sha256::Hash::hash(joined.as_bytes()).to_string()
+}
+
+impl Checkpoint {
+    pub fn new(path: impl Into<PathBuf>, digest: String) -> Self {
+        Self { path: path.into(), digest }
+    }
+
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    /// Indice salvo, ou `None` se o arquivo nao existe.
+    /// Falha se o checkpoint pertence a outra configuracao.
+    pub fn load(&self) -> Result<Option<u64>> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(t) => t,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read checkpoint {}", self.path.display()))
+            }
+        };
+
+        let mut config = None;
+        let mut next   = None;
+        for line in text.lines() {
+            match line.split_once('=') {
+                Some(("config", v))     => config = Some(v.trim().to_string()),
+                Some(("next_index", v)) => next = Some(v.trim().parse::<u64>()
+                    .with_context(|| format!("Invalid next_index in {}", self.path.display()))?),
+                _ => {}
+            }
+        }
+
+        let config = config.with_context(|| format!("Missing config digest in {}", self.path.display()))?;
+        if config != self.digest {
+            anyhow::bail!(
+                "Checkpoint {} was created for a different search configuration (words, target, language or path changed)",
+                self.path.display());
+        }
+        next.with_context(|| format!("Missing next_index in {}", self.path.display())).map(Some)
+    }
+
+    pub fn save(&self, next_index: u64) -> Result<()> {
+        let text = format!(
+            "# brute_force_mnemonics checkpoint\nconfig={}\nnext_index={}\n",
+            self.digest, next_index);
+        let mut tmp = self.path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        fs::write(&tmp, text)
+            .with_context(|| format!("Failed to write checkpoint {}", tmp.display()))?;
+        fs::rename(&tmp, &self.path)
+            .with_context(|| format!("Failed to write checkpoint {}", self.path.display()))
+    }
+}
@@ -0,0 +1,617 @@
+// ── Modo cluster: coordenador (serve) e workers (worker) ─────────────────────
+//
+// O coordenador nao testa nada: guarda a busca (os argumentos, como para uma
+// maquina so) e entrega faixas de indices aos workers. Cada pedido e uma
+// conexao TCP com uma linha de texto; a resposta vem em linhas e a conexao
+// fecha:
+//
+//   HELLO nome               → ARGS n, seguido de n linhas (um argumento cada)
+//   LEASE nome digest fim    → RANGE id inicio fim batida | WAIT s | STOP | ERROR msg
+//   BEAT nome id             → OK | STOP
+//   FOUND nome id indice     → OK | STALE | ERROR msg
+//   DONE nome id proximo     → OK | STALE | ERROR msg
+//
+// Com --token, cada pedido comeca por "TOKEN segredo"; sem ele (ou com outro)
+// a resposta e ERROR, inclusive ao HELLO, que revela os argumentos. LEASE so
+// vale para um nome que ja fez HELLO, e BEAT, FOUND e DONE so para uma faixa
+// ativa entregue a esse nome: uma faixa ja reentregue da STALE, a de outro
+// worker (ou um acerto fora dela) da ERROR, e nada disso muda o estado.
+//
+// O coordenador e cada worker sondam a busca (digest do checkpoint e fim do
+// espaco): um worker cujos arquivos (--words-file, ...) dao outra busca e
+// recusado. Uma faixa sem BEAT por --lease-timeout volta para a fila, como o
+// resto de uma faixa interrompida (DONE com proximo < fim). So o indice do
+// acerto chega ao coordenador; a frase fica na saida do worker que a achou.
+
+use crate::status::bind_address;
+use anyhow::{Context, Result};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+// Laco do coordenador: prazos vencidos e fim da busca
+const TICK: Duration = Duration::from_millis(250);
+// Worker: tentativas de conexao antes de desistir do coordenador
+const CONNECT_ATTEMPTS: u32 = 5;
+const CONNECT_RETRY: Duration = Duration::from_secs(2);
+
+// ── Coordenador ──────────────────────────────────────────────────────────────
+
+struct Active {
+    worker:   String,
+    range:    Range<u64>,
+    deadline: Instant,
+}
+
+#[derive(Default)]
+struct State {
+    // Primeiro indice nunca entregue
+    next:    u64,
+    // Faixas perdidas ou interrompidas, entregues antes das novas (a mais baixa primeiro)
+    retry:   Vec<Range<u64>>,
+    active:  HashMap<u64, Active>,
+    // Nomes que fizeram HELLO
+    joined:  HashSet<String>,
+    covered: Vec<Range<u64>>,
+    found:   Vec<(u64, String)>,
+    last_id: u64,
+}
+
+/// What the coordinator knows when it stops.
+pub struct Outcome {
+    /// Ranges every index of which was tested, merged and sorted
+    pub covered: Vec<Range<u64>>,
+    /// Exclusive end of the search space
+    pub end:     u64,
+    /// Match indices and the worker that found each
+    pub found:   Vec<(u64, String)>,
+}
+
+/// Hands out ranges of one search to the workers and tracks what they covered.
+pub struct Coordinator {
+    args:          Vec<String>,
+    digest:        String,
+    end:           u64,
+    find_all:      bool,
+    range_size:    u64,
+    lease_timeout: Duration,
+    token:         Option<String>,
+    state:         Mutex<State>,
+}
+
+impl Coordinator {
+    /// `args` are the options of the search, as for a single machine; `digest` and `end`
+    /// describe its space (see `Lease::probe`), and each worker's must match them.
+    pub fn new(args: Vec<String>, (digest, end): (String, u64), find_all: bool, range_size: u64,
+               lease_timeout: Duration) -> Self {
+        Self { args, digest, end, find_all, range_size, lease_timeout, token: None, state: Mutex::new(State::default()) }
+    }
+
+    /// Requires every request to start with `TOKEN token`.
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
+    }
+
+    // Intervalo entre BEATs: varios por prazo, para um atraso nao custar a faixa
+    fn beat(&self) -> Duration {
+        (self.lease_timeout / 4).max(Duration::from_secs(1))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("coordinator lock")
+    }
+
+    // Acerto (sem --find-all) ou espaco inteiro coberto
+    fn finished(&self, state: &State) -> bool {
+        let exhausted = state.next >= self.end && state.retry.is_empty() && state.active.is_empty();
+        exhausted || (!self.find_all && !state.found.is_empty())
+    }
+
+    fn handle(&self, line: &str) -> String {
+        let line = match &self.token {
+            Some(token) => match line.strip_prefix("TOKEN ").and_then(|l| l.split_once(' ')) {
+                Some((given, rest)) if same_secret(given, token) => rest,
+                _ => return "ERROR missing or wrong --token\n".to_string(),
+            },
+            None => line,
+        };
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        match parts[..] {
+            ["HELLO", name] => {
+                println!("Worker joined     : {}", name);
+                self.lock().joined.insert(name.to_string());
+                let mut reply = format!("ARGS {}\n", self.args.len());
+                for arg in &self.args {
+                    reply += arg;
+                    reply.push('\n');
+                }
+                reply
+            }
+            ["LEASE", name, digest, end] => match end.parse() {
+                Ok(end) => self.lease(name, digest, end),
+                Err(_)  => "ERROR bad end index\n".to_string(),
+            },
+            ["BEAT", name, id] => {
+                let mut state = self.lock();
+                let stop = !self.find_all && !state.found.is_empty();
+                let deadline = Instant::now() + self.lease_timeout;
+                match id.parse().ok().and_then(|id| state.active.get_mut(&id)) {
+                    Some(lease) if lease.worker == name && !stop => { lease.deadline = deadline; "OK\n".to_string() }
+                    // Faixa ja reentregue (prazo vencido), de outro worker ou busca encerrada
+                    _ => "STOP\n".to_string(),
+                }
+            }
+            ["FOUND", name, id, index] => {
+                let (Ok(id), Ok(index)) = (id.parse::<u64>(), index.parse::<u64>()) else {
+                    return "ERROR bad FOUND\n".to_string();
+                };
+                let mut state = self.lock();
+                match state.active.get(&id) {
+                    None => "STALE\n".to_string(),
+                    Some(lease) if lease.worker != name => {
+                        println!("⚠ Atencao: {} mandou um acerto da faixa {}, que e de {}; ignorado", name, id, lease.worker);
+                        format!("ERROR range {} is not leased to {}\n", id, name)
+                    }
+                    Some(lease) if !lease.range.contains(&index) => {
+                        format!("ERROR index {} is outside range {}\n", index, id)
+                    }
+                    Some(_) => {
+                        println!("✓ Match           : index {} (worker {})", index, name);
+                        state.found.push((index, name.to_string()));
+                        "OK\n".to_string()
+                    }
+                }
+            }
+            ["DONE", name, id, next] => {
+                let (Ok(id), Ok(next)) = (id.parse::<u64>(), next.parse::<u64>()) else {
+                    return "ERROR bad DONE\n".to_string();
+                };
+                self.done(name, id, next)
+            }
+            _ => "ERROR unknown request\n".to_string(),
+        }
+    }
+
+    fn lease(&self, name: &str, digest: &str, end: u64) -> String {
+        if digest != self.digest || end != self.end {
+            println!("⚠ Atencao: worker {} tem outra busca (arquivos diferentes?) e foi recusado", name);
+            return "ERROR this worker's search differs from the coordinator's (same files and flags?)\n".to_string();
+        }
+        let mut state = self.lock();
+        if !state.joined.contains(name) {
+            return format!("ERROR worker {} has not sent HELLO\n", name);
+        }
+        if self.finished(&state) {
+            return "STOP\n".to_string();
+        }
+        let lowest = state.retry.iter().enumerate().min_by_key(|(_, r)| r.start).map(|(i, _)| i);
+        let range = match lowest {
+            Some(i) => state.retry.swap_remove(i),
+            None if state.next < self.end => {
+                let range = state.next..state.next.saturating_add(self.range_size).min(self.end);
+                state.next = range.end;
+                range
+            }
+            // Tudo entregue: espera uma faixa voltar ou a busca acabar
+            None => return format!("WAIT {}\n", self.beat().as_secs()),
+        };
+        state.last_id += 1;
+        let id = state.last_id;
+        println!("Range {:<12}: {}..{} → {}", id, range.start, range.end, name);
+        let reply = format!("RANGE {} {} {} {}\n", id, range.start, range.end, self.beat().as_secs());
+        let deadline = Instant::now() + self.lease_timeout;
+        state.active.insert(id, Active { worker: name.to_string(), range, deadline });
+        reply
+    }
+
+    fn done(&self, name: &str, id: u64, next: u64) -> String {
+        let mut state = self.lock();
+        let lease = match state.active.entry(id) {
+            Entry::Occupied(lease) if lease.get().worker == name => lease.remove(),
+            Entry::Occupied(_) => return format!("ERROR range {} is not leased to {}\n", id, name),
+            Entry::Vacant(_)   => return "STALE\n".to_string(),
+        };
+        let next = next.clamp(lease.range.start, lease.range.end);
+        if next > lease.range.start {
+            state.covered.push(lease.range.start..next);
+        }
+        if next < lease.range.end {
+            state.retry.push(next..lease.range.end);
+        }
+        let covered: u64 = state.covered.iter().map(|r| r.end - r.start).sum();
+        let share = format!(" ({:.1}% covered)", 100.0 * covered as f64 / self.end.max(1) as f64);
+        if next == lease.range.end {
+            println!("Range {:<12}: done by {}{}", id, lease.worker, share);
+        } else if self.finished(&state) {
+            println!("Range {:<12}: stopped at {} by {}{}", id, next, lease.worker, share);
+        } else {
+            println!("Range {:<12}: stopped at {} by {}, rest requeued{}", id, next, lease.worker, share);
+        }
+        "OK\n".to_string()
+    }
+
+    // Faixas com prazo vencido voltam para a fila
+    fn expire(&self) {
+        let mut state = self.lock();
+        let now = Instant::now();
+        let lost: Vec<u64> = state.active.iter().filter(|(_, l)| l.deadline < now).map(|(&id, _)| id).collect();
+        for id in lost {
+            if let Some(lease) = state.active.remove(&id) {
+                println!("Range {:<12}: {} went silent, {}..{} requeued", id, lease.worker, lease.range.start, lease.range.end);
+                state.retry.push(lease.range);
+            }
+        }
+    }
+
+    fn outcome(&self) -> Outcome {
+        let state = self.lock();
+        Outcome {
+            covered: merge(&state.covered),
+            end:     self.end,
+            found:   state.found.clone(),
+        }
+    }
+}
+
+// Comparacao sem atalho, para o tempo de resposta nao revelar o prefixo certo
+fn same_secret(given: &str, token: &str) -> bool {
+    given.len() == token.len() && given.bytes().zip(token.bytes()).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+/// Sorted, with adjacent or overlapping ranges joined.
+pub fn merge(ranges: &[Range<u64>]) -> Vec<Range<u64>> {
+    let mut sorted = ranges.to_vec();
+    sorted.sort_by_key(|r| r.start);
+    let mut merged: Vec<Range<u64>> = Vec::new();
+    for r in sorted {
+        match merged.last_mut() {
+            Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+            _ => merged.push(r),
+        }
+    }
+    merged
+}
+
+/// Binds now (so a busy port fails early); `serve` answers on it.
+pub fn listen(spec: &str) -> Result<(TcpListener, String)> {
+    let addr = bind_address(spec);
+    let listener = TcpListener::bind(&addr)
+        .with_context(|| format!("Failed to listen on {} for the workers", addr))?;
+    Ok((listener, addr))
+}
+
+/// Serves the workers until the search is over or `stop` is set. After the end it keeps
+/// answering for a while, so that waiting workers hear STOP and running ones hand back.
+pub fn serve(listener: TcpListener, coordinator: Arc<Coordinator>, stop: &AtomicBool) -> Result<Outcome> {
+    let server = Arc::clone(&coordinator);
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let server = Arc::clone(&server);
+            // Worker lento ou quebrado nao derruba o coordenador
+            thread::spawn(move || { let _ = respond(stream, &server); });
+        }
+    });
+
+    let mut ended: Option<Instant> = None;
+    while !stop.load(Ordering::Relaxed) {
+        thread::sleep(TICK);
+        coordinator.expire();
+        let state = coordinator.lock();
+        if ended.is_none() && coordinator.finished(&state) {
+            ended = Some(Instant::now());
+        }
+        // Duas batidas para quem espera (WAIT); ate o prazo para quem ainda testa
+        if let Some(at) = ended {
+            let linger = if state.active.is_empty() { coordinator.beat() * 2 } else { coordinator.lease_timeout };
+            if at.elapsed() >= linger {
+                break;
+            }
+        }
+    }
+    Ok(coordinator.outcome())
+}
+
+fn respond(mut stream: TcpStream, coordinator: &Coordinator) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut line = String::new();
+    BufReader::new(stream.try_clone()?).read_line(&mut line)?;
+    stream.write_all(coordinator.handle(line.trim()).as_bytes())
+}
+
+// ── Worker ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    /// Digest and exclusive end of the search space (filled by a probe)
+    pub space: Option<(String, u64)>,
+    /// First index of the range not tested
+    pub next:  u64,
+    pub found: Vec<u64>,
+}
+
+/// A range handed to this worker; its search fills the report.
+#[derive(Debug, Clone, Default)]
+pub struct Lease {
+    pub id:    u64,
+    pub start: u64,
+    pub end:   u64,
+    pub beat:  Duration,
+    /// Sonda: a busca so descreve o espaco (digest e fim) e volta, sem testar
+    pub probe: bool,
+    /// Stop flag of the search on this range, set when the coordinator answers STOP
+    pub stop:  Arc<AtomicBool>,
+    report:    Arc<Mutex<Report>>,
+}
+
+impl Lease {
+    pub fn probe() -> Self {
+        Self { probe: true, ..Self::default() }
+    }
+
+    pub fn describe(&self, digest: String, end: u64) {
+        self.lock().space = Some((digest, end));
+    }
+
+    pub fn finish(&self, next: u64, found: impl IntoIterator<Item = u64>) {
+        let mut report = self.lock();
+        report.next = next;
+        report.found.extend(found);
+    }
+
+    pub fn report(&self) -> Report {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Report> {
+        self.report.lock().expect("lease lock")
+    }
+}
+
+pub enum Reply {
+    Range(Lease),
+    Wait(Duration),
+    Stop,
+}
+
+/// The worker's side of the protocol.
+#[derive(Clone)]
+pub struct Client {
+    addr:  String,
+    name:  String,
+    token: Option<String>,
+}
+
+impl Client {
+    pub fn new(addr: &str, name: &str, token: Option<String>) -> Self {
+        Self { addr: addr.to_string(), name: name.split_whitespace().collect::<Vec<_>>().join("-"), token }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // Uma conexao por pedido; algumas tentativas antes de dar o coordenador por perdido
+    fn request(&self, line: &str) -> Result<Vec<String>> {
+        let mut attempt = 1;
+        let mut stream = loop {
+            match TcpStream::connect(&self.addr) {
+                Ok(stream) => break stream,
+                Err(e) if attempt >= CONNECT_ATTEMPTS => {
+                    return Err(e).with_context(|| format!("Coordinator {} unreachable", self.addr));
+                }
+                Err(_) => {
+                    attempt += 1;
+                    thread::sleep(CONNECT_RETRY);
+                }
+            }
+        };
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        match &self.token {
+            Some(token) => writeln!(stream, "TOKEN {} {}", token, line)?,
+            None        => writeln!(stream, "{}", line)?,
+        }
+        let lines = BufReader::new(stream).lines().collect::<std::io::Result<Vec<String>>>()?;
+        match lines.first().and_then(|l| l.strip_prefix("ERROR ")) {
+            Some(msg) => anyhow::bail!("Coordinator: {}", msg),
+            None      => Ok(lines),
+        }
+    }
+
+    /// The options of the search, as given to `serve`.
+    pub fn hello(&self) -> Result<Vec<String>> {
+        let lines = self.request(&format!("HELLO {}", self.name))?;
+        let count: usize = lines.first()
+            .and_then(|l| l.strip_prefix("ARGS "))
+            .and_then(|n| n.parse().ok())
+            .context("Unexpected reply from the coordinator")?;
+        if lines.len() != count + 1 {
+            anyhow::bail!("Truncated reply from the coordinator");
+        }
+        Ok(lines[1..].to_vec())
+    }
+
+    pub fn lease(&self, digest: &str, end: u64) -> Result<Reply> {
+        let lines = self.request(&format!("LEASE {} {} {}", self.name, digest, end))?;
+        let parts: Vec<&str> = lines.first().map_or(Vec::new(), |l| l.split_whitespace().collect());
+        let num = |s: &str| s.parse::<u64>().context("Unexpected reply from the coordinator");
+        match parts[..] {
+            ["RANGE", id, start, end, beat] => Ok(Reply::Range(Lease {
+                id:    num(id)?,
+                start: num(start)?,
+                end:   num(end)?,
+                beat:  Duration::from_secs(num(beat)?),
+                ..Lease::default()
+            })),
+            ["WAIT", secs] => Ok(Reply::Wait(Duration::from_secs(num(secs)?))),
+            ["STOP"]       => Ok(Reply::Stop),
+            _              => anyhow::bail!("Unexpected reply from the coordinator: {:?}", lines.first()),
+        }
+    }
+
+    /// False when the range is no longer this worker's (it went to another after the lease timeout).
+    pub fn found(&self, id: u64, index: u64) -> Result<bool> {
+        let lines = self.request(&format!("FOUND {} {} {}", self.name, id, index))?;
+        Ok(lines.first().is_none_or(|l| l != "STALE"))
+    }
+
+    /// False when the range is no longer this worker's, as for `found`.
+    pub fn done(&self, id: u64, next: u64) -> Result<bool> {
+        let lines = self.request(&format!("DONE {} {} {}", self.name, id, next))?;
+        Ok(lines.first().is_none_or(|l| l != "STALE"))
+    }
+
+    /// BEATs for `lease` in the background; a STOP reply (the range went to another
+    /// worker, or the search is over) sets the lease's stop flag.
+    pub fn heartbeat(&self, lease: &Lease) -> Heartbeat {
+        let client  = self.clone();
+        let done    = Arc::new(AtomicBool::new(false));
+        let stopped = Arc::new(AtomicBool::new(false));
+        let (d2, s2, stop) = (Arc::clone(&done), Arc::clone(&stopped), Arc::clone(&lease.stop));
+        let (id, every) = (lease.id, lease.beat);
+        let handle = thread::spawn(move || {
+            let mut last = Instant::now();
+            while !d2.load(Ordering::Relaxed) {
+                thread::sleep(TICK);
+                if last.elapsed() < every {
+                    continue;
+                }
+                last = Instant::now();
+                // Falha de rede: a proxima batida tenta de novo, dentro do prazo
+                if let Ok(reply) = client.request(&format!("BEAT {} {}", client.name, id)) {
+                    if reply.first().is_some_and(|l| l == "STOP") {
+                        s2.store(true, Ordering::Relaxed);
+                        stop.store(true, Ordering::Relaxed);
+                    }
+                }
+            }
+        });
+        Heartbeat { done, stopped, handle }
+    }
+}
+
+pub struct Heartbeat {
+    done:    Arc<AtomicBool>,
+    stopped: Arc<AtomicBool>,
+    handle:  JoinHandle<()>,
+}
+
+impl Heartbeat {
+    /// Ends the BEATs; true when the coordinator asked this range to stop.
+    pub fn finish(self) -> bool {
+        self.done.store(true, Ordering::Relaxed);
+        let _ = self.handle.join();
+        self.stopped.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Espaco de 100 indices em faixas de 10
+    fn coordinator(lease_timeout: Duration) -> Coordinator {
+        let args = vec!["bc1q...".to_string(), "--words-file".to_string(), "words.txt".to_string()];
+        Coordinator::new(args, ("d".to_string(), 100), false, 10, lease_timeout)
+    }
+
+    fn joined(lease_timeout: Duration, names: &[&str]) -> Coordinator {
+        let c = coordinator(lease_timeout);
+        for name in names {
+            assert!(c.handle(&format!("HELLO {}", name)).starts_with("ARGS 3\n"));
+        }
+        c
+    }
+
+    const LONG: Duration = Duration::from_secs(600);
+
+    #[test]
+    fn leases_ranges_in_order_and_covers_done_ones() {
+        let c = joined(LONG, &["w1", "w2"]);
+        assert_eq!(c.handle("LEASE w1 d 100"), "RANGE 1 0 10 150\n");
+        assert_eq!(c.handle("LEASE w2 d 100"), "RANGE 2 10 20 150\n");
+        assert_eq!(c.handle("DONE w2 2 20"), "OK\n");
+        assert_eq!(c.handle("DONE w1 1 10"), "OK\n");
+        assert_eq!(c.outcome().covered, vec![0..20]);
+    }
+
+    #[test]
+    fn refuses_a_lease_without_hello_or_for_another_search() {
+        let c = joined(LONG, &["w1"]);
+        assert!(c.handle("LEASE w2 d 100").starts_with("ERROR "));
+        assert!(c.handle("LEASE w1 other 100").starts_with("ERROR "));
+        assert!(c.handle("LEASE w1 d 99").starts_with("ERROR "));
+        assert_eq!(c.handle("LEASE w1 d 100"), "RANGE 1 0 10 150\n");
+    }
+
+    #[test]
+    fn partial_done_requeues_the_rest_before_new_ranges() {
+        let c = joined(LONG, &["w1", "w2"]);
+        c.handle("LEASE w1 d 100");
+        assert_eq!(c.handle("DONE w1 1 4"), "OK\n");
+        assert_eq!(c.outcome().covered, vec![0..4]);
+        assert_eq!(c.handle("LEASE w2 d 100"), "RANGE 2 4 10 150\n");
+        assert_eq!(c.handle("LEASE w2 d 100"), "RANGE 3 10 20 150\n");
+    }
+
+    #[test]
+    fn expired_ranges_are_requeued_lowest_first() {
+        let c = joined(Duration::from_millis(1), &["w1", "w2"]);
+        c.handle("LEASE w1 d 100");
+        c.handle("LEASE w1 d 100");
+        c.handle("LEASE w1 d 100");
+        thread::sleep(Duration::from_millis(10));
+        c.expire();
+        assert!(c.lock().active.is_empty());
+        assert_eq!(c.handle("LEASE w2 d 100"), "RANGE 4 0 10 1\n");
+        assert_eq!(c.handle("LEASE w2 d 100"), "RANGE 5 10 20 1\n");
+        assert_eq!(c.handle("LEASE w2 d 100"), "RANGE 6 20 30 1\n");
+        assert_eq!(c.handle("LEASE w2 d 100"), "RANGE 7 30 40 1\n");
+
+        // The first worker comes back late: its ranges are no longer its own
+        assert_eq!(c.handle("BEAT w1 1"), "STOP\n");
+        assert_eq!(c.handle("FOUND w1 1 5"), "STALE\n");
+        assert_eq!(c.handle("DONE w1 1 10"), "STALE\n");
+        assert!(c.outcome().found.is_empty());
+        assert!(c.outcome().covered.is_empty());
+    }
+
+    #[test]
+    fn found_counts_only_inside_an_active_range_of_its_worker() {
+        let c = joined(LONG, &["w1", "w2"]);
+        c.handle("LEASE w1 d 100");
+        assert_eq!(c.handle("FOUND w1 99 5"), "STALE\n");
+        assert!(c.handle("FOUND w2 1 5").starts_with("ERROR "));
+        assert!(c.handle("FOUND w1 1 10").starts_with("ERROR "));
+        assert!(c.handle("DONE w2 1 10").starts_with("ERROR "));
+        assert_eq!(c.handle("BEAT w2 1"), "STOP\n");
+        assert_eq!(c.handle("BEAT w1 1"), "OK\n");
+        assert!(c.outcome().found.is_empty());
+        assert!(!c.finished(&c.lock()));
+
+        assert_eq!(c.handle("FOUND w1 1 5"), "OK\n");
+        assert_eq!(c.outcome().found, vec![(5, "w1".to_string())]);
+        assert_eq!(c.handle("LEASE w2 d 100"), "STOP\n");
+        assert_eq!(c.handle("BEAT w1 1"), "STOP\n");
+        assert_eq!(c.handle("DONE w1 1 6"), "OK\n");
+        assert_eq!(c.outcome().covered, vec![0..6]);
+    }
+
+    #[test]
+    fn token_guards_every_request() {
+        let c = coordinator(LONG).with_token(Some("s3cret".to_string()));
+        assert!(c.handle("HELLO w1").starts_with("ERROR "));
+        assert!(c.handle("TOKEN s3cre HELLO w1").starts_with("ERROR "));
+        assert!(c.handle("TOKEN s3cretx HELLO w1").starts_with("ERROR "));
+        assert!(c.handle("TOKEN s3cret HELLO w1").starts_with("ARGS 3\n"));
+        assert!(c.handle("LEASE w1 d 100").starts_with("ERROR "));
+        assert_eq!(c.handle("TOKEN s3cret LEASE w1 d 100"), "RANGE 1 0 10 150\n");
+    }
+}
@@ -0,0 +1,272 @@
+// ── Moedas: formato de endereco e coin type (SLIP-44) ────────────────────────
+//
+// A busca compara scriptPubKeys, que sao iguais entre moedas derivadas do
+// Bitcoin; a moeda so muda o coin type do caminho (m/purpose'/coin'/0') e a
+// codificacao do endereco (versoes base58, prefixo bech32 ou CashAddr).
+
+use crate::cashaddr;
+use crate::derivation::AddressType;
+use crate::monero::keccak::keccak256;
+use crate::target::script_type;
+use anyhow::{Context, Result};
+use bitcoin::bech32::{self, Hrp};
+use bitcoin::hashes::Hash;
+use bitcoin::secp256k1::SecretKey;
+use bitcoin::{base58, NetworkKind, PubkeyHash, Script, ScriptBuf, ScriptHash, WitnessProgram, WitnessVersion};
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Coin {
+    Bitcoin,
+    /// Bitcoin testnet and signet (tb1..., m/n..., 2...; coin type 1)
+    #[value(alias = "signet")]
+    Testnet,
+    /// Bitcoin regtest (bcrt1..., m/n..., 2...; coin type 1)
+    Regtest,
+    Litecoin,
+    Dogecoin,
+    BitcoinCash,
+    /// Cardano (CIP-1852, addr1...): derived by the `cardano` module, not by the BIP-32 search
+    #[value(name = "ada", alias = "cardano")]
+    Cardano,
+}
+
+struct Params {
+    name:      &'static str,
+    coin_type: u32,
+    p2pkh:     u8,
+    // Primeira versao e usada para codificar; as demais so sao aceitas na leitura
+    p2sh:      &'static [u8],
+    // Prefixo bech32; sem ele a moeda nao tem SegWit (so BIP44)
+    hrp:       Option<&'static str>,
+    taproot:   bool,
+    // Prefixo CashAddr, usado para codificar; base58 continua aceito na leitura
+    cashaddr:  Option<&'static str>,
+    // Versao da chave privada em WIF
+    wif:       u8,
+}
+
+const BITCOIN: Params = Params {
+    name: "bitcoin", coin_type: 0, p2pkh: 0x00, p2sh: &[0x05],
+    hrp: Some("bc"), taproot: true, cashaddr: None, wif: 0x80,
+};
+// Testnet e signet: mesmo prefixo e versoes; regtest so muda o bech32
+const TESTNET: Params = Params {
+    name: "testnet", coin_type: 1, p2pkh: 0x6f, p2sh: &[0xc4],
+    hrp: Some("tb"), taproot: true, cashaddr: None, wif: 0xef,
+};
+const REGTEST: Params = Params {
+    name: "regtest", coin_type: 1, p2pkh: 0x6f, p2sh: &[0xc4],
+    hrp: Some("bcrt"), taproot: true, cashaddr: None, wif: 0xef,
+};
+// Litecoin: M... (0x32) para P2SH; enderecos P2SH antigos com 3... (0x05) tambem valem
+const LITECOIN: Params = Params {
+    name: "litecoin", coin_type: 2, p2pkh: 0x30, p2sh: &[0x32, 0x05],
+    hrp: Some("ltc"), taproot: false, cashaddr: None, wif: 0xb0,
+};
+const DOGECOIN: Params = Params {
+    name: "dogecoin", coin_type: 3, p2pkh: 0x1e, p2sh: &[0x16],
+    hrp: None, taproot: false, cashaddr: None, wif: 0x9e,
+};
+const BITCOIN_CASH: Params = Params {
+    name: "bitcoin-cash", coin_type: 145, p2pkh: 0x00, p2sh: &[0x05],
+    hrp: None, taproot: false, cashaddr: Some("bitcoincash"), wif: 0x80,
+};
+// Cardano: so nome e coin type; enderecos e chaves ficam em crate::cardano
+const CARDANO: Params = Params {
+    name: "cardano", coin_type: 1815, p2pkh: 0x00, p2sh: &[],
+    hrp: None, taproot: false, cashaddr: None, wif: 0x00,
+};
+
+impl Coin {
+    /// Coins with a scriptPubKey address, in the order [`classify`] tries them: an address
+    /// two of them accept (a legacy `1...` is Bitcoin and Bitcoin Cash) goes to the first.
+    pub const SCRIPT_COINS: [Coin; 6] =
+        [Coin::Bitcoin, Coin::Testnet, Coin::Regtest, Coin::Litecoin, Coin::Dogecoin, Coin::BitcoinCash];
+
+    fn params(&self) -> &'static Params {
+        match self {
+            Coin::Bitcoin     => &BITCOIN,
+            Coin::Testnet     => &TESTNET,
+            Coin::Regtest     => &REGTEST,
+            Coin::Litecoin    => &LITECOIN,
+            Coin::Dogecoin    => &DOGECOIN,
+            Coin::BitcoinCash => &BITCOIN_CASH,
+            Coin::Cardano     => &CARDANO,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.params().name
+    }
+
+    /// Network of the extended keys: tprv/tpub for testnet and regtest, xprv/xpub otherwise.
+    pub fn network(&self) -> NetworkKind {
+        match self {
+            Coin::Testnet | Coin::Regtest => NetworkKind::Test,
+            _                             => NetworkKind::Main,
+        }
+    }
+
+    /// Coin type SLIP-44 (segundo nivel do caminho).
+    pub fn coin_type(&self) -> u32 {
+        self.params().coin_type
+    }
+
+    pub fn supports(&self, address_type: AddressType) -> bool {
+        let p = self.params();
+        match address_type {
+            AddressType::Bip44                      => *self != Coin::Cardano,
+            AddressType::Bip49 | AddressType::Bip84 => p.hrp.is_some(),
+            AddressType::Bip86                      => p.taproot,
+        }
+    }
+
+    /// Endereco → scriptPubKey.
+    pub fn parse_address(&self, s: &str) -> Result<ScriptBuf> {
+        let p = self.params();
+        if *self == Coin::Cardano {
+            anyhow::bail!("Cardano addresses have no scriptPubKey; use cardano::parse_address");
+        }
+        if let Some(prefix) = p.cashaddr.filter(|_| is_cashaddr(s)) {
+            let (kind, hash) = cashaddr::decode(prefix, s)?;
+            return match kind {
+                cashaddr::TYPE_P2PKH => Ok(ScriptBuf::new_p2pkh(&PubkeyHash::from_byte_array(hash))),
+                cashaddr::TYPE_P2SH  => Ok(ScriptBuf::new_p2sh(&ScriptHash::from_byte_array(hash))),
+                _ => anyhow::bail!("Unsupported CashAddr type {} in {}", kind, s),
+            };
+        }
+        if let Some(hrp) = p.hrp.filter(|h| s.to_lowercase().starts_with(&format!("{}1", h))) {
+            let (decoded, version, program) = bech32::segwit::decode(s)
+                .with_context(|| format!("Invalid {} bech32 address: {}", p.name, s))?;
+            if decoded != Hrp::parse_unchecked(hrp) {
+                anyhow::bail!("Address {} is not a {} address", s, p.name);
+            }
+            let version = WitnessVersion::try_from(version)
+                .with_context(|| format!("Invalid witness version in {}", s))?;
+            let program = WitnessProgram::new(version, &program)
+                .with_context(|| format!("Invalid witness program in {}", s))?;
+            return Ok(ScriptBuf::new_witness_program(&program));
+        }
+
+        let data = base58::decode_check(s)
+            .with_context(|| format!("Invalid {} address: {}", p.name, s))?;
+        if data.len() != 21 {
+            anyhow::bail!("Invalid {} address length: {}", p.name, s);
+        }
+        let mut hash = [0u8; 20];
+        hash.copy_from_slice(&data[1..]);
+        match data[0] {
+            v if v == p.p2pkh        => Ok(ScriptBuf::new_p2pkh(&PubkeyHash::from_byte_array(hash))),
+            v if p.p2sh.contains(&v) => Ok(ScriptBuf::new_p2sh(&ScriptHash::from_byte_array(hash))),
+            _ => anyhow::bail!("Address {} is not a {} address", s, p.name),
+        }
+    }
+
+    /// scriptPubKey → endereco (`None` para scripts sem forma de endereco).
+    pub fn encode_address(&self, script: &Script) -> Option<String> {
+        let p = self.params();
+        if *self == Coin::Cardano {
+            return None;
+        }
+        let bytes = script.as_bytes();
+        // (versao base58, tipo CashAddr, hash160) para P2PKH / P2SH
+        let (version, kind, hash) = if script.is_p2pkh() {
+            (p.p2pkh, cashaddr::TYPE_P2PKH, &bytes[3..23])
+        } else if script.is_p2sh() {
+            (p.p2sh[0], cashaddr::TYPE_P2SH, &bytes[2..22])
+        } else {
+            let version = script.witness_version()?;
+            return bech32::segwit::encode(Hrp::parse_unchecked(p.hrp?), version.to_fe(), &bytes[2..]).ok();
+        };
+        match p.cashaddr {
+            Some(prefix) => Some(cashaddr::encode(prefix, kind, hash.try_into().ok()?)),
+            None => {
+                let mut data = vec![version];
+                data.extend_from_slice(hash);
+                Some(base58::encode_check(&data))
+            }
+        }
+    }
+
+    /// Private key in WIF, as wallets import or sweep it; `compressed` marks the form of the
+    /// public key its addresses use (every BIP32 wallet compresses).
+    pub fn encode_wif(&self, key: &SecretKey, compressed: bool) -> String {
+        let mut data = vec![self.params().wif];
+        data.extend_from_slice(&key.secret_bytes());
+        if compressed {
+            data.push(0x01);
+        }
+        base58::encode_check(&data)
+    }
+}
+
+// CashAddr com prefixo, ou so o payload (q... / p..., 42 caracteres)
+fn is_cashaddr(s: &str) -> bool {
+    let lower = s.to_lowercase();
+    lower.contains(':') || (lower.len() == 42 && (lower.starts_with('q') || lower.starts_with('p')))
+}
+
+/// Coin (network included) and address type of a target, from the decoded address rather
+/// than its first characters: each coin's parser is tried and the type follows the script.
+/// `Ok(None)` when no coin reads it; an error for addresses that are recognized but that no
+/// single key derives (P2WSH, whose script comes from the multisig options, witness versions
+/// 2+) or that this tool does not derive (Ethereum).
+///
+/// ```
+/// use brute_force_mnemonics::coin::{classify, Coin};
+/// use brute_force_mnemonics::AddressType;
+///
+/// let taproot = "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr";
+/// assert_eq!(classify(taproot)?, Some((Coin::Bitcoin, AddressType::Bip86)));
+/// let testnet = "tb1q6rz28mcfaxtmd6v789l9rrlrusdprr9pqcpvkl";
+/// assert_eq!(classify(testnet)?, Some((Coin::Testnet, AddressType::Bip84)));
+/// assert!(classify("0x9858EfFD232B4033E47d90003D41EC34EcaEda94").is_err());
+/// assert_eq!(classify("not an address")?, None);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn classify(addr: &str) -> Result<Option<(Coin, AddressType)>> {
+    if let Some(hex) = addr.strip_prefix("0x").filter(|h| h.len() == 40 && h.chars().all(|c| c.is_ascii_hexdigit())) {
+        if !eip55_valid(hex) {
+            anyhow::bail!("{} looks like an Ethereum address but its EIP-55 checksum is wrong", addr);
+        }
+        anyhow::bail!("{} is an Ethereum address; only Bitcoin-family scripts (and Cardano) are derived", addr);
+    }
+    let Some((coin, script)) = decode_address(addr) else {
+        return Ok(None);
+    };
+    if let Some(address_type) = script_type(&script) {
+        return Ok(Some((coin, address_type)));
+    }
+    let program = &script.as_bytes()[2..];
+    match script.witness_version() {
+        Some(WitnessVersion::V0) => {
+            anyhow::bail!("{} is a P2WSH (script) address; give its script with --witness-script or --multisig", addr)
+        }
+        Some(v) => anyhow::bail!("{} is a witness v{} program of {} bytes; only P2WPKH (v0) and Taproot (v1) are derived",
+            addr, v.to_num(), program.len()),
+        None => Ok(None),
+    }
+}
+
+/// First coin of [`Coin::SCRIPT_COINS`] that reads `addr`, and its scriptPubKey.
+pub fn decode_address(addr: &str) -> Option<(Coin, ScriptBuf)> {
+    Coin::SCRIPT_COINS.into_iter().find_map(|c| Some((c, c.parse_address(addr).ok()?)))
+}
+
+/// Moeda e tipo de endereco, sem o motivo de uma recusa (ver [`classify`]).
+pub fn detect(addr: &str) -> Option<(Coin, AddressType)> {
+    classify(addr).ok().flatten()
+}
+
+// EIP-55: so confere quando ha maiusculas e minusculas; uma letra e maiuscula
+// quando o nibble do keccak256 do endereco em minusculas e >= 8
+fn eip55_valid(hex: &str) -> bool {
+    if hex == hex.to_lowercase() || hex == hex.to_uppercase() {
+        return true;
+    }
+    let hash = keccak256(hex.to_lowercase().as_bytes());
+    hex.chars().enumerate().all(|(i, c)| {
+        let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+        !c.is_ascii_alphabetic() || c.is_ascii_uppercase() == (nibble >= 8)
+    })
+}
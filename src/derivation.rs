@@ -0,0 +1,400 @@
+// ── Derivacao BIP32 e enderecos (BIP44/49/84/86) ─────────────────────────────
+
+use anyhow::{Context, Result};
+use bitcoin::base58;
+use bitcoin::bip32::{ChildNumber, DerivationPath, Fingerprint, Xpriv, Xpub};
+use bitcoin::hashes::{hash160, hmac, sha512, Hash, HashEngine};
+use bitcoin::key::{TapTweak, XOnlyPublicKey};
+use bitcoin::secp256k1::{Scalar, SecretKey};
+use bitcoin::{CompressedPublicKey, Network, PublicKey, ScriptBuf};
+
+// ── Thread-local Secp256k1 context ──────────────────────────────────────────
+thread_local! {
+    static SECP: bitcoin::secp256k1::Secp256k1<bitcoin::secp256k1::All> =
+        bitcoin::secp256k1::Secp256k1::new();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressType {
+    Bip44,
+    Bip49,
+    Bip84,
+    Bip86,
+}
+
+impl AddressType {
+    pub const ALL: [AddressType; 4] = [AddressType::Bip44, AddressType::Bip49, AddressType::Bip84, AddressType::Bip86];
+
+    pub fn purpose(&self) -> u32 {
+        match self {
+            AddressType::Bip44 => 44,
+            AddressType::Bip49 => 49,
+            AddressType::Bip84 => 84,
+            AddressType::Bip86 => 86,
+        }
+    }
+    /// Tipo pelo proposito do caminho (84' → BIP84); `None` para outros caminhos.
+    pub fn from_path(path: &DerivationPath) -> Option<AddressType> {
+        let first = path.into_iter().next()?;
+        AddressType::ALL.into_iter()
+            .find(|t| *first == ChildNumber::Hardened { index: t.purpose() })
+    }
+    /// Conta BIP44/49/84/86 (m/purpose'/coin_type'/0').
+    pub fn account_path(&self, coin_type: u32) -> String {
+        format!("m/{}'/{}'/0'", self.purpose(), coin_type)
+    }
+    pub fn name(&self) -> &'static str {
+        match self {
+            AddressType::Bip44 => "BIP44 (Legacy P2PKH)",
+            AddressType::Bip49 => "BIP49 (P2SH-wrapped SegWit)",
+            AddressType::Bip84 => "BIP84 (Native SegWit)",
+            AddressType::Bip86 => "BIP86 (Taproot P2TR)",
+        }
+    }
+}
+
+// ── scriptPubKey a partir da chave publica do filho ──────────────────────────
+//
+// A comparacao com os alvos e feita no scriptPubKey, que nao depende da moeda:
+// o mesmo script vira "1..." no Bitcoin e "L..." no Litecoin (ver coin.rs).
+pub fn script_from_pubkey(pubkey: bitcoin::secp256k1::PublicKey, address_type: AddressType) -> ScriptBuf {
+    match address_type {
+        AddressType::Bip44 => ScriptBuf::new_p2pkh(&PublicKey::new(pubkey).pubkey_hash()),
+        AddressType::Bip49 => {
+            let redeem = ScriptBuf::new_p2wpkh(&CompressedPublicKey(pubkey).wpubkey_hash());
+            ScriptBuf::new_p2sh(&redeem.script_hash())
+        }
+        AddressType::Bip84 => ScriptBuf::new_p2wpkh(&CompressedPublicKey(pubkey).wpubkey_hash()),
+        AddressType::Bip86 => {
+            // Chave interna x-only, tweak BIP341 sem script tree
+            let internal = XOnlyPublicKey::from(pubkey);
+            SECP.with(|secp| ScriptBuf::new_p2tr(secp, internal, None))
+        }
+    }
+}
+
+// Carteiras muito antigas: P2PKH sobre a chave publica nao comprimida (65 bytes)
+pub fn p2pkh_uncompressed(pubkey: bitcoin::secp256k1::PublicKey) -> ScriptBuf {
+    ScriptBuf::new_p2pkh(&PublicKey { compressed: false, inner: pubkey }.pubkey_hash())
+}
+
+// ── Conteudo do scriptPubKey (hash ou chave), sem montar o script ────────────
+//
+// Na busca, cada candidato so precisa do hash160 (ou da chave de saida do
+// Taproot) para ser comparado: arrays fixos, sem alocar um ScriptBuf. O script
+// so e montado num acerto.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Payload {
+    /// P2PKH: hash160 da chave publica
+    PubkeyHash([u8; 20]),
+    /// P2SH: hash160 do redeem script
+    ScriptHash([u8; 20]),
+    /// P2WPKH: programa de testemunha v0
+    WitnessV0([u8; 20]),
+    /// P2TR: chave de saida x-only (ja com o tweak)
+    Taproot([u8; 32]),
+}
+
+impl Payload {
+    /// Payload of a P2PKH, P2SH, P2WPKH or P2TR script; `None` for other scripts.
+    pub fn from_script(script: &bitcoin::Script) -> Option<Payload> {
+        let bytes = script.as_bytes();
+        let hash = |range: std::ops::Range<usize>| bytes[range].try_into().ok();
+        if script.is_p2pkh() {
+            hash(3..23).map(Payload::PubkeyHash)
+        } else if script.is_p2sh() {
+            hash(2..22).map(Payload::ScriptHash)
+        } else if script.is_p2wpkh() {
+            hash(2..22).map(Payload::WitnessV0)
+        } else if script.is_p2tr() {
+            bytes[2..34].try_into().ok().map(Payload::Taproot)
+        } else {
+            None
+        }
+    }
+}
+
+/// What `script_from_pubkey` (or `p2pkh_uncompressed`) would put in the script,
+/// computed from the key alone.
+///
+/// ```
+/// use bitcoin::secp256k1::{PublicKey, Secp256k1, SecretKey};
+/// use brute_force_mnemonics::derivation::{
+///     p2pkh_uncompressed, pubkey_payload, script_from_pubkey, AddressType, Payload,
+/// };
+///
+/// let secp = Secp256k1::new();
+/// let key = PublicKey::from_secret_key(&secp, &SecretKey::from_slice(&[7; 32]).unwrap());
+/// for t in AddressType::ALL {
+///     assert_eq!(Payload::from_script(&script_from_pubkey(key, t)), Some(pubkey_payload(key, t, false)));
+/// }
+/// let long = pubkey_payload(key, AddressType::Bip44, true);
+/// assert_eq!(Payload::from_script(&p2pkh_uncompressed(key)), Some(long));
+/// ```
+pub fn pubkey_payload(pubkey: bitcoin::secp256k1::PublicKey, address_type: AddressType, uncompressed: bool) -> Payload {
+    let hash160 = |bytes: &[u8]| hash160::Hash::hash(bytes).to_byte_array();
+    match address_type {
+        AddressType::Bip44 if uncompressed => Payload::PubkeyHash(hash160(&pubkey.serialize_uncompressed())),
+        AddressType::Bip44 => Payload::PubkeyHash(hash160(&pubkey.serialize())),
+        AddressType::Bip49 => {
+            // Redeem script P2WPKH: OP_0 PUSH20 <hash160>
+            let mut redeem = [0u8; 22];
+            redeem[1] = 0x14;
+            redeem[2..].copy_from_slice(&hash160(&pubkey.serialize()));
+            Payload::ScriptHash(hash160(&redeem))
+        }
+        AddressType::Bip84 => Payload::WitnessV0(hash160(&pubkey.serialize())),
+        AddressType::Bip86 => {
+            let (output, _) = SECP.with(|secp| XOnlyPublicKey::from(pubkey).tap_tweak(secp, None));
+            Payload::Taproot(output.to_x_only_public_key().serialize())
+        }
+    }
+}
+
+// ── CKDpriv direto: so chave e chain code ────────────────────────────────────
+//
+//   hardened:     I = HMAC-SHA512(c, 0x00 || k || i)
+//   nao-hardened: I = HMAC-SHA512(c, serP(k·G) || i)
+//   k' = (I_L + k) mod n,  c' = I_R
+//
+// Xpriv::derive_priv preenche a cada nivel a fingerprint do pai: um k·G e um
+// hash160 a mais por nivel, que a busca joga fora. Aqui um nivel endurecido
+// custa um HMAC e uma soma de escalares, um normal tambem o k·G do pai (que o
+// BIP32 exige, e que a GPU pode entregar pronto). O HMAC de "Bitcoin seed"
+// parte de um estado calculado uma vez, e a chave serializada fica na pilha.
+
+/// A BIP32 node without the metadata of an `Xpriv` (depth, parent fingerprint).
+///
+/// ```
+/// use bitcoin::bip32::{DerivationPath, Xpriv};
+/// use bitcoin::secp256k1::Secp256k1;
+/// use bitcoin::Network;
+/// use brute_force_mnemonics::derivation::Node;
+///
+/// let seed = [42u8; 64];
+/// let path: DerivationPath = "m/84'/0'/0'/0/7".parse().unwrap();
+/// let secp = Secp256k1::new();
+/// let xpriv = Xpriv::new_master(Network::Bitcoin, &seed).unwrap().derive_priv(&secp, &path).unwrap();
+/// let node = Node::master(&seed).unwrap().derive(path.as_ref()).unwrap();
+/// assert_eq!(node.key, xpriv.private_key);
+/// assert_eq!(node.chain, xpriv.chain_code.to_bytes());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Node {
+    pub key:   SecretKey,
+    pub chain: [u8; 32],
+}
+
+pub fn hmac_sha512(key: &[u8], parts: &[&[u8]]) -> [u8; 64] {
+    let mut engine = hmac::HmacEngine::<sha512::Hash>::new(key);
+    for p in parts {
+        engine.input(p);
+    }
+    hmac::Hmac::<sha512::Hash>::from_engine(engine).to_byte_array()
+}
+
+fn master_engine() -> hmac::HmacEngine<sha512::Hash> {
+    static ENGINE: std::sync::OnceLock<hmac::HmacEngine<sha512::Hash>> = std::sync::OnceLock::new();
+    ENGINE.get_or_init(|| hmac::HmacEngine::new(b"Bitcoin seed")).clone()
+}
+
+impl Node {
+    /// Master node of `seed`; `None` for a seed BIP32 rejects.
+    pub fn master(seed: &[u8]) -> Option<Node> {
+        let mut engine = master_engine();
+        engine.input(seed);
+        Node::split(&hmac::Hmac::<sha512::Hash>::from_engine(engine).to_byte_array(), None)
+    }
+
+    pub fn from_xpriv(xpriv: &Xpriv) -> Node {
+        Node { key: xpriv.private_key, chain: xpriv.chain_code.to_bytes() }
+    }
+
+    fn split(i: &[u8; 64], parent: Option<&SecretKey>) -> Option<Node> {
+        let (il, chain) = i.split_at(32);
+        let il: [u8; 32] = il.try_into().ok()?;
+        let key = match parent {
+            None    => SecretKey::from_slice(&il).ok()?,
+            Some(k) => k.add_tweak(&Scalar::from_be_bytes(il).ok()?).ok()?,
+        };
+        Some(Node { key, chain: chain.try_into().ok()? })
+    }
+
+    /// Child `child`. A normal child needs this node's public key: `pubkey` when it
+    /// is already known (serialized), otherwise computed here.
+    pub fn child(&self, child: ChildNumber, pubkey: Option<&[u8; 33]>) -> Option<Node> {
+        let index = u32::from(child).to_be_bytes();
+        let i = match (child, pubkey) {
+            (ChildNumber::Hardened { .. }, _) => {
+                hmac_sha512(&self.chain, &[&[0u8], &self.key.secret_bytes(), &index])
+            }
+            (ChildNumber::Normal { .. }, Some(pubkey)) => hmac_sha512(&self.chain, &[pubkey, &index]),
+            (ChildNumber::Normal { .. }, None) => {
+                hmac_sha512(&self.chain, &[&self.public_key().serialize(), &index])
+            }
+        };
+        Node::split(&i, Some(&self.key))
+    }
+
+    pub fn derive(&self, path: &[ChildNumber]) -> Option<Node> {
+        path.iter().try_fold(*self, |node, &child| node.child(child, None))
+    }
+
+    pub fn public_key(&self) -> bitcoin::secp256k1::PublicKey {
+        SECP.with(|secp| self.key.public_key(secp))
+    }
+}
+
+// ── Derivacao BIP32 a partir da seed ─────────────────────────────────────────
+pub fn derive_xpriv(seed: &[u8], derivation_path: &DerivationPath) -> Option<Xpriv> {
+    derive_from(&Xpriv::new_master(Network::Bitcoin, seed).ok()?, derivation_path)
+}
+
+// Como `derive_xpriv`, a partir de uma chave mestra ja pronta
+pub fn derive_from(master: &Xpriv, derivation_path: &DerivationPath) -> Option<Xpriv> {
+    SECP.with(|secp| master.derive_priv(secp, derivation_path).ok())
+}
+
+/// Fingerprint of the master key of `seed` (the `[73c5da0a/...]` of descriptors).
+pub fn master_fingerprint(seed: &[u8]) -> Option<Fingerprint> {
+    SECP.with(|secp| Some(Xpriv::new_master(Network::Bitcoin, seed).ok()?.fingerprint(secp)))
+}
+
+pub fn derive_pubkey(
+    seed: &[u8],
+    derivation_path: &DerivationPath,
+) -> Option<bitcoin::secp256k1::PublicKey> {
+    Some(Node::master(seed)?.derive(derivation_path.as_ref())?.public_key())
+}
+
+// Filho direto de uma chave ja derivada
+pub fn child_pubkey(parent: &Xpriv, child: ChildNumber) -> Option<bitcoin::secp256k1::PublicKey> {
+    Some(Node::from_xpriv(parent).child(child, None)?.public_key())
+}
+
+// ── Derivacao BIP32 + scriptPubKey a partir da seed ──────────────────────────
+pub fn derive_script(
+    seed: &[u8],
+    derivation_path: &DerivationPath,
+    address_type: AddressType,
+) -> Option<ScriptBuf> {
+    Some(script_from_pubkey(derive_pubkey(seed, derivation_path)?, address_type))
+}
+
+// ── Chaves privadas do acerto (xprv / WIF) ───────────────────────────────────
+//
+// A conta e o maior prefixo endurecido do caminho (m/84'/0'/0' em
+// m/84'/0'/0'/0/5); para caminhos BIP49/84 a Electrum importa a conta com as
+// versoes SLIP-132 (yprv/zprv), os mesmos bytes com outro prefixo.
+const XPRV_VERSION: [u8; 4] = [0x04, 0x88, 0xAD, 0xE4];
+const YPRV_VERSION: [u8; 4] = [0x04, 0x9D, 0x78, 0x78];
+const ZPRV_VERSION: [u8; 4] = [0x04, 0xB2, 0x43, 0x0C];
+
+/// Private keys behind a match, to sweep or import it elsewhere.
+pub struct MatchKeys {
+    pub master:       Xpriv,
+    pub fingerprint:  Fingerprint,
+    /// Hardened part of the match path (the account) and its keys
+    pub account_path: DerivationPath,
+    pub account:      Xpriv,
+    pub account_xpub: Xpub,
+    /// Rest of the path, below the account (0/5)
+    pub chain_path:   DerivationPath,
+    /// Key of the matched address
+    pub child:        SecretKey,
+    pub child_pubkey: bitcoin::secp256k1::PublicKey,
+}
+
+pub fn match_keys(seed: &[u8], path: &DerivationPath) -> Option<MatchKeys> {
+    match_keys_from(Xpriv::new_master(Network::Bitcoin, seed).ok()?, path)
+}
+
+/// Like `match_keys`, from the master key itself (e.g. a known master xprv).
+pub fn match_keys_from(master: Xpriv, path: &DerivationPath) -> Option<MatchKeys> {
+    let hardened = path.into_iter().take_while(|c| c.is_hardened()).count();
+    let account_path = DerivationPath::from(&path[..hardened]);
+    let chain_path   = DerivationPath::from(&path[hardened..]);
+    SECP.with(|secp| {
+        let account = master.derive_priv(secp, &account_path).ok()?;
+        let child   = account.derive_priv(secp, &chain_path).ok()?.private_key;
+        Some(MatchKeys {
+            fingerprint:  master.fingerprint(secp),
+            account_xpub: Xpub::from_priv(secp, &account),
+            master,
+            account_path,
+            account,
+            chain_path,
+            child_pubkey: child.public_key(secp),
+            child,
+        })
+    })
+}
+
+/// `xprv` with the SLIP-132 version of its address type (yprv for BIP49, zprv for BIP84).
+pub fn slip132_xprv(xprv: &Xpriv, address_type: AddressType) -> String {
+    let mut data = xprv.encode();
+    data[..4].copy_from_slice(match address_type {
+        AddressType::Bip49 => &YPRV_VERSION,
+        AddressType::Bip84 => &ZPRV_VERSION,
+        _                  => &XPRV_VERSION,
+    });
+    base58::encode_check(&data)
+}
+
+pub fn is_extended_privkey(s: &str) -> bool {
+    ["xprv", "yprv", "zprv"].iter().any(|p| s.starts_with(p))
+}
+
+/// Decodifica xprv/yprv/zprv (como `parse_extended_pubkey`); devolve tambem o
+/// tipo implicado pelo prefixo.
+pub fn parse_extended_privkey(s: &str) -> Result<(Xpriv, Option<AddressType>)> {
+    let mut data = base58::decode_check(s).context("Invalid extended private key (base58check)")?;
+    if data.len() != 78 {
+        anyhow::bail!("Invalid extended private key length: {} bytes", data.len());
+    }
+    let implied = match [data[0], data[1], data[2], data[3]] {
+        XPRV_VERSION => None,
+        YPRV_VERSION => Some(AddressType::Bip49),
+        ZPRV_VERSION => Some(AddressType::Bip84),
+        _ => anyhow::bail!("Unsupported extended key version (expected mainnet xprv, yprv or zprv)"),
+    };
+    data[..4].copy_from_slice(&XPRV_VERSION);
+    let xprv = Xpriv::decode(&data).context("Invalid extended private key")?;
+    Ok((xprv, implied))
+}
+
+/// Extended public key of `xprv`.
+pub fn neuter(xprv: &Xpriv) -> Xpub {
+    SECP.with(|secp| Xpub::from_priv(secp, xprv))
+}
+
+// ── xpub / ypub / zpub ───────────────────────────────────────────────────────
+//
+// ypub (BIP49) e zpub (BIP84) sao xpubs com outros bytes de versao (SLIP-132);
+// troca a versao pela da xpub e decodifica normalmente. O prefixo tambem indica
+// o tipo de endereco da conta.
+const XPUB_VERSION: [u8; 4] = [0x04, 0x88, 0xB2, 0x1E];
+const YPUB_VERSION: [u8; 4] = [0x04, 0x9D, 0x7C, 0xB2];
+const ZPUB_VERSION: [u8; 4] = [0x04, 0xB2, 0x47, 0x46];
+
+pub fn is_extended_pubkey(s: &str) -> bool {
+    ["xpub", "ypub", "zpub"].iter().any(|p| s.starts_with(p))
+}
+
+/// Decodifica xpub/ypub/zpub; devolve tambem o tipo implicado pelo prefixo
+/// (`None` para xpub, que serve tanto a BIP44 quanto a BIP86).
+pub fn parse_extended_pubkey(s: &str) -> Result<(Xpub, Option<AddressType>)> {
+    let mut data = base58::decode_check(s).context("Invalid extended public key (base58check)")?;
+    if data.len() != 78 {
+        anyhow::bail!("Invalid extended public key length: {} bytes", data.len());
+    }
+    let implied = match [data[0], data[1], data[2], data[3]] {
+        XPUB_VERSION => None,
+        YPUB_VERSION => Some(AddressType::Bip49),
+        ZPUB_VERSION => Some(AddressType::Bip84),
+        _ => anyhow::bail!("Unsupported extended key version (expected mainnet xpub, ypub or zpub)"),
+    };
+    data[..4].copy_from_slice(&XPUB_VERSION);
+    let xpub = Xpub::decode(&data).context("Invalid extended public key")?;
+    Ok((xpub, implied))
+}
@@ -0,0 +1,269 @@
+// ── Output descriptors (BIP-380..386) ────────────────────────────────────────
+//
+// Descriptor publico da conta de um acerto, com a origem da chave
+// ([fingerprint/caminho]) e o checksum que o Bitcoin Core exige no
+// importdescriptors. O tipo do script vem do endereco achado: pkh (BIP44),
+// sh(wpkh) (BIP49), wpkh (BIP84) e tr (BIP86); num multisig, o sortedmulti
+// (ou o multi / pk do --witness-script) com as xpubs dos cosignatarios.
+
+use crate::derivation::{AddressType, MatchKeys};
+use crate::multisig::{Multisig, Template};
+use anyhow::{Context, Result};
+use bitcoin::bip32::{ChildNumber, DerivationPath, Fingerprint, Xpub};
+use std::str::FromStr;
+
+// Alfabeto de entrada: a posicao de cada caractere alimenta o checksum em
+// grupos de 5 bits + uma "classe" a cada 3 caracteres
+const INPUT_CHARSET: &str =
+    "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+const CHECKSUM_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+fn polymod(c: u64, value: u64) -> u64 {
+    const GEN: [u64; 5] = [0xf5dee51989, 0xa9fdca3312, 0x1bab10e32d, 0x3706b1677a, 0x644d626ffd];
+    let c0 = c >> 35;
+    let mut c = ((c & 0x07_ffff_ffff) << 5) ^ value;
+    for (i, g) in GEN.iter().enumerate() {
+        if (c0 >> i) & 1 == 1 {
+            c ^= g;
+        }
+    }
+    c
+}
+
+/// Descriptor checksum (8 characters); `None` for characters descriptors can't hold.
+///
+/// ```
+/// use brute_force_mnemonics::descriptor::checksum;
+///
+/// assert_eq!(checksum("raw(deadbeef)").as_deref(), Some("89f8spxm"));
+/// ```
+pub fn checksum(descriptor: &str) -> Option<String> {
+    let mut c = 1;
+    let mut class = 0;
+    let mut class_count = 0;
+    for ch in descriptor.chars() {
+        let pos = INPUT_CHARSET.find(ch)? as u64;
+        c = polymod(c, pos & 31);
+        class = class * 3 + (pos >> 5);
+        class_count += 1;
+        if class_count == 3 {
+            c = polymod(c, class);
+            class = 0;
+            class_count = 0;
+        }
+    }
+    if class_count > 0 {
+        c = polymod(c, class);
+    }
+    for _ in 0..8 {
+        c = polymod(c, 0);
+    }
+    c ^= 1;
+    Some((0..8).map(|j| CHECKSUM_CHARSET[((c >> (5 * (7 - j))) & 31) as usize] as char).collect())
+}
+
+/// Public descriptors for the account of a match, labelled by chain. A match on
+/// the receive or change chain (…/0/i, …/1/i) yields both chains; any other
+/// path yields the chain it was found on.
+pub fn account_descriptors(keys: &MatchKeys, address_type: AddressType) -> Vec<(&'static str, String)> {
+    chains(keys).into_iter()
+        .map(|(label, chain)| (label, descriptor(keys, address_type, &chain, false)))
+        .collect()
+}
+
+/// Public descriptors of a multisig wallet whose key of the match is one of the keys,
+/// labelled by chain like `account_descriptors`.
+pub fn multisig_descriptors(keys: &MatchKeys, multisig: &Multisig) -> Vec<(&'static str, String)> {
+    chains(keys).into_iter()
+        .map(|(label, chain)| {
+            let mine = format!("[{}]{}{}/*", origin(keys), keys.account_xpub, chain);
+            let mut xkeys: Vec<String> = multisig.cosigners.iter().map(|x| format!("{}{}/*", x, chain)).collect();
+            let inner = match multisig.template {
+                Template::Pk => format!("pk({})", mine),
+                t => {
+                    xkeys.insert(if let Template::Multi { mine: at } = t { at } else { 0 }, mine);
+                    format!("{}({},{})", t.name(), multisig.quorum, xkeys.join(","))
+                }
+            };
+            (label, with_checksum(multisig.script_type.wrap_descriptor(&inner)))
+        })
+        .collect()
+}
+
+// (rotulo, caminho abaixo da conta sem o ultimo indice, que vira *)
+fn chains(keys: &MatchKeys) -> Vec<(&'static str, String)> {
+    let chain: Vec<ChildNumber> = keys.chain_path.into_iter().copied().collect();
+    let chain = &chain[..chain.len().saturating_sub(1)];
+    match chain {
+        [ChildNumber::Normal { index: 0 | 1 }] => vec![("Receive", "/0".into()), ("Change", "/1".into())],
+        _ => vec![("Chain", chain.iter().map(|c| format!("/{}", c)).collect())],
+    }
+}
+
+// Descriptor com checksum; `private` usa a xprv da conta no lugar da xpub
+fn descriptor(keys: &MatchKeys, address_type: AddressType, chain: &str, private: bool) -> String {
+    let xkey = if private { keys.account.to_string() } else { keys.account_xpub.to_string() };
+    let key = format!("[{}]{}{}/*", origin(keys), xkey, chain);
+    with_checksum(match address_type {
+        AddressType::Bip44 => format!("pkh({})", key),
+        AddressType::Bip49 => format!("sh(wpkh({}))", key),
+        AddressType::Bip84 => format!("wpkh({})", key),
+        AddressType::Bip86 => format!("tr({})", key),
+    })
+}
+
+// fingerprint/caminho da conta
+fn origin(keys: &MatchKeys) -> String {
+    match keys.account_path.is_empty() {
+        true  => keys.fingerprint.to_string(),
+        false => format!("{}/{}", keys.fingerprint, keys.account_path),
+    }
+}
+
+fn with_checksum(descriptor: String) -> String {
+    let sum = checksum(&descriptor).expect("descriptor uses only checksum characters");
+    format!("{}#{}", descriptor, sum)
+}
+
+// ── Descriptor como alvo ─────────────────────────────────────────────────────
+//
+// Um descriptor de chave unica com curinga (o que a carteira exporta) no lugar
+// do endereco: a funcao diz o tipo (pkh, sh(wpkh), wpkh, tr), a origem
+// [fingerprint/caminho] diz onde a chave fica, e o acerto e a chave do
+// candidato nesse nivel igual a do descriptor, como com uma xpub de conta.
+// Os passos depois da chave (/0/*, /<0;1>/*) so valem para o endereco mostrado.
+
+/// A single-key descriptor used as the target (see [`parse_target`]).
+#[derive(Debug, Clone)]
+pub struct DescriptorTarget {
+    pub address_type: AddressType,
+    pub xpub:         Xpub,
+    /// Path of the key from the master, from the key origin `[fingerprint/path]`
+    pub origin:       Option<(Fingerprint, DerivationPath)>,
+    /// The chain of `.../0/*` or `.../1/*`; `None` for `/<0;1>/*` or no chain step
+    pub chain:        Option<u32>,
+}
+
+/// Does `s` look like a descriptor rather than an address or a key?
+pub fn is_descriptor(s: &str) -> bool {
+    s.contains('(')
+}
+
+/// Parses `pkh(KEY)`, `sh(wpkh(KEY))`, `wpkh(KEY)` or `tr(KEY)`, KEY an xpub or tpub with
+/// an optional origin and unhardened steps ending in a wildcard; a `#checksum` is verified.
+///
+/// ```
+/// use brute_force_mnemonics::descriptor::parse_target;
+/// use brute_force_mnemonics::AddressType;
+///
+/// let target = parse_target("wpkh([73c5da0a/84'/0'/0']xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V/0/*)#wc3n3van")?;
+/// assert_eq!(target.address_type, AddressType::Bip84);
+/// assert_eq!(target.origin.unwrap().1.to_string(), "84'/0'/0'");
+/// assert_eq!(target.chain, Some(0));
+/// assert!(parse_target("wpkh(xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V/0/*)#wc3n3van").is_err());
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn parse_target(s: &str) -> Result<DescriptorTarget> {
+    let s = s.trim();
+    let body = match s.split_once('#') {
+        Some((body, sum)) => {
+            if checksum(body).as_deref() != Some(sum) {
+                anyhow::bail!("Descriptor checksum mismatch: expected #{}", checksum(body).unwrap_or_default());
+            }
+            body
+        }
+        None => s,
+    };
+    let functions = [
+        ("sh(wpkh(", "))", AddressType::Bip49),
+        ("wpkh(", ")", AddressType::Bip84),
+        ("pkh(", ")", AddressType::Bip44),
+        ("tr(", ")", AddressType::Bip86),
+    ];
+    let (key, address_type) = functions.iter()
+        .find_map(|(open, close, t)| Some((body.strip_prefix(open)?.strip_suffix(close)?, *t)))
+        .with_context(|| format!("Unsupported descriptor {:?}: a target is pkh(), sh(wpkh()), wpkh() or tr() of one key \
+            (multisig wallets: --multisig or --witness-script)", body))?;
+    if key.contains(',') || key.contains('(') {
+        anyhow::bail!("Descriptor {:?} has more than one key or a script tree; only the key path of tr() is supported", body);
+    }
+
+    let (origin, key) = match key.strip_prefix('[') {
+        Some(rest) => {
+            let (origin, key) = rest.split_once(']').context("Descriptor key origin without ']'")?;
+            let (fingerprint, path) = origin.split_once('/').unwrap_or((origin, ""));
+            let fingerprint = Fingerprint::from_str(fingerprint)
+                .with_context(|| format!("Invalid fingerprint {:?} in the key origin", fingerprint))?;
+            let path = DerivationPath::from_str(format!("m/{}", path.replace('h', "'")).trim_end_matches('/'))
+                .with_context(|| format!("Invalid path {:?} in the key origin", path))?;
+            (Some((fingerprint, path)), key)
+        }
+        None => (None, key),
+    };
+    let (xkey, steps) = key.split_once('/').unwrap_or((key, ""));
+    let xpub = Xpub::from_str(xkey)
+        .with_context(|| format!("Invalid extended public key {:?} in the descriptor (xpub or tpub)", xkey))?;
+    if let Some((_, path)) = origin.as_ref().filter(|(_, p)| p.len() != xpub.depth as usize) {
+        anyhow::bail!("The key origin has {} steps but the xpub is at depth {}", path.len(), xpub.depth);
+    }
+
+    // Passos depois da chave: numeros nao endurecidos, um <a;b> e o curinga no fim
+    let steps: Vec<&str> = steps.split('/').filter(|s| !s.is_empty()).collect();
+    match steps.last() {
+        Some(&"*") => {}
+        Some(w) if w.starts_with('*') => anyhow::bail!("Hardened wildcard {:?}: the key must derive the addresses publicly", w),
+        _ => anyhow::bail!("Descriptor {:?} has no wildcard (/*); give the address itself as the target", body),
+    }
+    let steps = &steps[..steps.len() - 1];
+    for step in steps {
+        let multipath = step.strip_prefix('<').and_then(|s| s.strip_suffix('>'));
+        let numbers: Vec<&str> = multipath.map_or(vec![*step], |m| m.split(';').collect());
+        if !numbers.iter().all(|n| n.parse::<u32>().is_ok_and(|n| n < 0x8000_0000)) {
+            anyhow::bail!("Invalid step {:?} after the key (only unhardened indices and <a;b>)", step);
+        }
+    }
+    let chain = match steps {
+        [chain] => chain.parse().ok(),
+        _       => None,
+    };
+    Ok(DescriptorTarget { address_type, xpub, origin, chain })
+}
+
+// ── importdescriptors (Bitcoin Core) ─────────────────────────────────────────
+//
+// O timestamp diz ao Core de onde reescanear: a data mais antiga em que a
+// carteira pode ter recebido algo. Frases BIP-39 nao existiam antes do BIP,
+// e enderecos SegWit/Taproot nao antes da ativacao de cada um.
+const BIP39_TIME: u64 = 1_378_771_200; // 2013-09-10, publicacao do BIP-39
+const SEGWIT_TIME: u64 = 1_503_539_857; // bloco 481824
+const TAPROOT_TIME: u64 = 1_636_866_927; // bloco 709632
+// Enderecos por cadeia alem do indice achado (o keypool padrao do Core)
+const RANGE: u32 = 1000;
+
+/// Earliest time a wallet of this type can have received funds (Unix seconds).
+pub fn birth_time(address_type: AddressType) -> u64 {
+    match address_type {
+        AddressType::Bip44                      => BIP39_TIME,
+        AddressType::Bip49 | AddressType::Bip84 => SEGWIT_TIME,
+        AddressType::Bip86                      => TAPROOT_TIME,
+    }
+}
+
+/// JSON array for Bitcoin Core's `importdescriptors`: the account's private
+/// descriptors (receive and change, active), ranged past the found index, with
+/// `birth_time` as the rescan start.
+pub fn core_import_json(keys: &MatchKeys, address_type: AddressType) -> String {
+    let last = match keys.chain_path.into_iter().last() {
+        Some(ChildNumber::Normal { index }) => *index,
+        _                                   => 0,
+    };
+    let entries: Vec<String> = chains(keys).into_iter()
+        .map(|(label, chain)| format!(
+            "  {{\"desc\": \"{}\", \"timestamp\": {}, \"active\": true, \"internal\": {}, \"range\": [0, {}]}}",
+            descriptor(keys, address_type, &chain, true),
+            birth_time(address_type),
+            label == "Change",
+            last + RANGE - 1))
+        .collect();
+    format!("[\n{}\n]\n", entries.join(",\n"))
+}
@@ -0,0 +1,205 @@
+// ── Ed25519: so o necessario para derivar chaves publicas (Monero, Cardano) ──
+//
+// Corpo GF(2^255 - 19) em 5 limbs de 51 bits; pontos em coordenadas
+// estendidas (X:Y:Z:T) com a soma unificada de Hisil et al. (a = -1). A
+// multiplicacao pelo ponto base usa a tabela 2^i·B, entao custa so somas.
+// Nada aqui e tempo-constante: as chaves sao candidatas de uma busca local.
+
+use std::sync::OnceLock;
+
+const MASK: u64 = (1 << 51) - 1;
+
+#[derive(Debug, Clone, Copy)]
+struct Fe([u64; 5]);
+
+const ZERO: Fe = Fe([0; 5]);
+const ONE: Fe = Fe([1, 0, 0, 0, 0]);
+// 2·d, d = -121665/121666
+const D2: Fe = Fe([0x69b9426b2f159, 0x35050762add7a, 0x3cf44c0038052, 0x6738cc7407977, 0x2406d9dc56dff]);
+const BASE_X: Fe = Fe([0x62d608f25d51a, 0x412a4b4f6592a, 0x75b7171a4b31d, 0x1ff60527118fe, 0x216936d3cd6e5]);
+const BASE_Y: Fe = Fe([0x6666666666658, 0x4cccccccccccc, 0x1999999999999, 0x3333333333333, 0x6666666666666]);
+const BASE_T: Fe = Fe([0x68ab3a5b7dda3, 0x00eea2a5eadbb, 0x2af8df483c27e, 0x332b375274732, 0x67875f0fd78b7]);
+
+/// Order of the base point, little-endian 64-bit limbs.
+const L: [u64; 4] = [0x5812631a5cf5d3ed, 0x14def9dea2f79cd6, 0, 0x1000000000000000];
+
+impl Fe {
+    fn carry(mut self) -> Fe {
+        let l = &mut self.0;
+        for i in 0..4 {
+            l[i + 1] += l[i] >> 51;
+            l[i] &= MASK;
+        }
+        l[0] += 19 * (l[4] >> 51);
+        l[4] &= MASK;
+        self
+    }
+
+    fn add(self, b: Fe) -> Fe {
+        let mut r = self.0;
+        r.iter_mut().zip(b.0).for_each(|(r, b)| *r += b);
+        Fe(r).carry()
+    }
+
+    // a + 2p - b: nenhum limb fica negativo
+    fn sub(self, b: Fe) -> Fe {
+        const TWO_P: [u64; 5] = [0xfffffffffffda, 0xffffffffffffe, 0xffffffffffffe, 0xffffffffffffe, 0xffffffffffffe];
+        let mut r = self.0;
+        for i in 0..5 {
+            r[i] = r[i] + TWO_P[i] - b.0[i];
+        }
+        Fe(r).carry()
+    }
+
+    fn mul(self, b: Fe) -> Fe {
+        let a = self.0.map(|v| v as u128);
+        let b = b.0.map(|v| v as u128);
+        // 2^255 ≡ 19: os produtos acima do limb 4 voltam multiplicados por 19
+        let b19 = b.map(|v| v * 19);
+        let mut t = [
+            a[0] * b[0] + a[1] * b19[4] + a[2] * b19[3] + a[3] * b19[2] + a[4] * b19[1],
+            a[0] * b[1] + a[1] * b[0] + a[2] * b19[4] + a[3] * b19[3] + a[4] * b19[2],
+            a[0] * b[2] + a[1] * b[1] + a[2] * b[0] + a[3] * b19[4] + a[4] * b19[3],
+            a[0] * b[3] + a[1] * b[2] + a[2] * b[1] + a[3] * b[0] + a[4] * b19[4],
+            a[0] * b[4] + a[1] * b[3] + a[2] * b[2] + a[3] * b[1] + a[4] * b[0],
+        ];
+        for i in 0..4 {
+            t[i + 1] += t[i] >> 51;
+            t[i] &= MASK as u128;
+        }
+        t[0] += 19 * (t[4] >> 51);
+        t[4] &= MASK as u128;
+        Fe(t.map(|v| v as u64)).carry()
+    }
+
+    // a^(p-2) = 1/a
+    fn invert(self) -> Fe {
+        // p - 2 = 2^255 - 21: todos os bits 0..=254, menos o 2 e o 4
+        let mut result = ONE;
+        for bit in (0..255).rev() {
+            result = result.mul(result);
+            if bit != 2 && bit != 4 {
+                result = result.mul(self);
+            }
+        }
+        result
+    }
+
+    fn to_bytes(self) -> [u8; 32] {
+        // Reducao completa: soma 19 e ve se passa de 2^255 (ou seja, se >= p)
+        let mut l = self.carry().carry().0;
+        let mut q = (l[0] + 19) >> 51;
+        for limb in &l[1..] {
+            q = (limb + q) >> 51;
+        }
+        l[0] += 19 * q;
+        for i in 0..4 {
+            l[i + 1] += l[i] >> 51;
+            l[i] &= MASK;
+        }
+        l[4] &= MASK;
+
+        let mut out = [0u8; 32];
+        let mut acc: u128 = 0;
+        let mut bits = 0;
+        let mut pos  = 0;
+        for limb in l {
+            acc  |= (limb as u128) << bits;
+            bits += 51;
+            while bits >= 8 && pos < 32 {
+                out[pos] = acc as u8;
+                acc >>= 8;
+                bits -= 8;
+                pos  += 1;
+            }
+        }
+        if pos < 32 {
+            out[pos] = acc as u8;
+        }
+        out
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Point {
+    x: Fe,
+    y: Fe,
+    z: Fe,
+    t: Fe,
+}
+
+const IDENTITY: Point = Point { x: ZERO, y: ONE, z: ONE, t: ZERO };
+
+impl Point {
+    fn add(&self, q: &Point) -> Point {
+        let a = self.y.sub(self.x).mul(q.y.sub(q.x));
+        let b = self.y.add(self.x).mul(q.y.add(q.x));
+        let c = self.t.mul(D2).mul(q.t);
+        let d = self.z.add(self.z).mul(q.z);
+        let (e, f, g, h) = (b.sub(a), d.sub(c), d.add(c), b.add(a));
+        Point { x: e.mul(f), y: g.mul(h), z: f.mul(g), t: e.mul(h) }
+    }
+
+    // y com o bit de paridade de x no bit mais alto
+    fn compress(&self) -> [u8; 32] {
+        let zinv = self.z.invert();
+        let x = self.x.mul(zinv).to_bytes();
+        let mut y = self.y.mul(zinv).to_bytes();
+        y[31] |= (x[0] & 1) << 7;
+        y
+    }
+}
+
+// 2^i·B para i em 0..253
+fn base_powers() -> &'static [Point; 253] {
+    static TABLE: OnceLock<[Point; 253]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = [IDENTITY; 253];
+        let mut p = Point { x: BASE_X, y: BASE_Y, z: ONE, t: BASE_T };
+        for entry in table.iter_mut() {
+            *entry = p;
+            p = p.add(&p);
+        }
+        table
+    })
+}
+
+/// `scalar`·B, compressed; `scalar` is little-endian and must be reduced mod l.
+pub fn public_key(scalar: &[u8; 32]) -> [u8; 32] {
+    let table = base_powers();
+    let mut acc = IDENTITY;
+    for (i, p) in table.iter().enumerate() {
+        if (scalar[i / 8] >> (i % 8)) & 1 == 1 {
+            acc = acc.add(p);
+        }
+    }
+    acc.compress()
+}
+
+/// Reduces a little-endian 256-bit number mod l (Monero's `sc_reduce32`).
+pub fn reduce32(bytes: &[u8; 32]) -> [u8; 32] {
+    let mut n: [u64; 4] = std::array::from_fn(|i| u64::from_le_bytes(bytes[8 * i..8 * i + 8].try_into().expect("8 bytes")));
+    // 2^256 / l < 16: poucas subtracoes bastam
+    let ge = |n: &[u64; 4]| {
+        for i in (0..4).rev() {
+            if n[i] != L[i] {
+                return n[i] > L[i];
+            }
+        }
+        true
+    };
+    while ge(&n) {
+        let mut borrow = false;
+        for i in 0..4 {
+            let (d, b1) = n[i].overflowing_sub(L[i]);
+            let (d, b2) = d.overflowing_sub(borrow as u64);
+            n[i]   = d;
+            borrow = b1 || b2;
+        }
+    }
+    let mut out = [0u8; 32];
+    for (chunk, limb) in out.chunks_mut(8).zip(n) {
+        chunk.copy_from_slice(&limb.to_le_bytes());
+    }
+    out
+}
@@ -0,0 +1,398 @@
+// ── Enderecos com saldo (index-funded / --funded-index) ──────────────────────
+//
+// Os dumps publicos de "todos os enderecos com saldo" (TSV/CSV endereco,saldo,
+// dezenas de milhoes de linhas), ou um snapshot do UTXO set do Bitcoin Core
+// (dumptxoutset, ver utxo.rs), viram um indice binario em disco, ordenado:
+//
+//   "BFMIDX01" | registros u64 LE | moeda (nome, 16 bytes com NUL)
+//   registros de 41 bytes: tag | chave de 32 bytes | saldo u64 LE
+//
+// A tag e o tipo do `Payload` (0 = P2PKH, 1 = P2SH, 2 = P2WPKH, 3 = P2TR); os
+// hash160 ocupam os 20 primeiros bytes da chave, o resto e zero. Na busca o
+// indice alimenta um filtro de Bloom (ver bloom.rs) e confirma os "talvez"
+// por busca binaria no arquivo, sem rede e sem carregar os registros.
+//
+// A importacao ordena em memoria lotes de ate 8M registros (~400 MB), gravados
+// em arquivos temporarios e intercalados no fim: o UTXO set inteiro (~180M
+// saidas) cabe sem 9 GB de RAM. Registros do mesmo script somam os saldos (as
+// saidas de um snapshot, as linhas repetidas de um dump), e o saldo minimo vale
+// para a soma. Enderecos que nao saem de uma chave so (P2WSH, multisig nu, P2PK)
+// e de outras moedas ficam de fora, contados.
+
+use crate::coin::{self, Coin};
+use crate::derivation::{AddressType, Payload};
+use crate::utxo::{self, Snapshot};
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::sync::Mutex;
+
+const MAGIC: &[u8; 8] = b"BFMIDX01";
+const HEADER: u64 = 32;
+const RECORD: usize = 41;
+// Registros ordenados em memoria antes de ir para um arquivo temporario
+const RUN: usize = 1 << 23;
+
+/// Balance of an address listed without one (a dump of addresses only).
+pub const UNKNOWN_BALANCE: u64 = u64::MAX;
+
+type Key = [u8; 33];
+// Lote (ou a memoria) lido em ordem durante a intercalacao
+type Records = Box<dyn Iterator<Item = Result<(Key, u64)>>>;
+
+fn key(payload: &Payload) -> Key {
+    let mut key = [0; 33];
+    let (tag, bytes): (u8, &[u8]) = match payload {
+        Payload::PubkeyHash(h) => (0, h),
+        Payload::ScriptHash(h) => (1, h),
+        Payload::WitnessV0(h)  => (2, h),
+        Payload::Taproot(k)    => (3, k),
+    };
+    key[0] = tag;
+    key[1..1 + bytes.len()].copy_from_slice(bytes);
+    key
+}
+
+fn payload(key: &Key) -> Option<Payload> {
+    let hash = || key[1..21].try_into().ok();
+    match key[0] {
+        0 => hash().map(Payload::PubkeyHash),
+        1 => hash().map(Payload::ScriptHash),
+        2 => hash().map(Payload::WitnessV0),
+        3 => key[1..].try_into().ok().map(Payload::Taproot),
+        _ => None,
+    }
+}
+
+/// Address type whose outputs carry `payload` (P2SH is taken as BIP49).
+pub fn payload_type(payload: &Payload) -> AddressType {
+    match payload {
+        Payload::PubkeyHash(_) => AddressType::Bip44,
+        Payload::ScriptHash(_) => AddressType::Bip49,
+        Payload::WitnessV0(_)  => AddressType::Bip84,
+        Payload::Taproot(_)    => AddressType::Bip86,
+    }
+}
+
+/// Outcome of [`import`].
+#[derive(Debug, Clone, Copy)]
+pub struct Imported {
+    pub coin:    Coin,
+    /// Records written (distinct scripts)
+    pub records:  u64,
+    /// Lines (or unspent outputs) left out: not an address of the coin, or not a
+    /// single-key script (P2WSH, P2PK)
+    pub skipped:  u64,
+    /// Scripts left out for a balance under the minimum
+    pub below:    u64,
+    /// Header of the `dumptxoutset` snapshot, when the input was one
+    pub snapshot: Option<Snapshot>,
+}
+
+/// Reads a dump of funded addresses (`address<TAB>balance`, `address,balance` or
+/// `address;balance`, balance in the smallest unit; a header line, blank lines and `#`
+/// comments are skipped) and writes the index `out`. The coin is `coin` or that of the
+/// first address; addresses listed without a balance are kept as [`UNKNOWN_BALANCE`].
+/// A `dumptxoutset` snapshot is recognized and read instead (see [`utxo::read_snapshot`]).
+///
+/// ```
+/// use brute_force_mnemonics::funded::{import, FundedIndex};
+/// use brute_force_mnemonics::derivation::Payload;
+/// use brute_force_mnemonics::Coin;
+///
+/// let dir  = std::env::temp_dir().join(format!("funded-doc-{}", std::process::id()));
+/// std::fs::create_dir_all(&dir)?;
+/// let dump = dir.join("dump.tsv");
+/// std::fs::write(&dump, "address\tbalance\n\
+///     bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu\t5000\n\
+///     1BoatSLRHtKNngkdXEeobR76b53LETtpyT\t12\n\
+///     bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu\t250\n")?;
+///
+/// let imported = import(&dump, &dir.join("funded.idx"), None, 100)?;
+/// assert_eq!((imported.coin, imported.records, imported.below), (Coin::Bitcoin, 1, 1));
+///
+/// let index  = FundedIndex::open(&dir.join("funded.idx"))?;
+/// let script = Coin::Bitcoin.parse_address("bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu")?;
+/// assert_eq!(index.balance(&Payload::from_script(&script).unwrap())?, Some(5250));
+/// # std::fs::remove_dir_all(&dir)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn import(dump: &Path, out: &Path, coin: Option<Coin>, min_balance: u64) -> Result<Imported> {
+    let mut writer = IndexWriter::new(out, min_balance);
+    if utxo::is_snapshot(dump)? {
+        let (snapshot, skipped) = utxo::read_snapshot(dump, coin, |payload, value| writer.push(&payload, value))?;
+        let (records, below) = writer.finish(snapshot.coin)?;
+        return Ok(Imported { coin: snapshot.coin, records, skipped, below, snapshot: Some(snapshot) });
+    }
+    let file = File::open(dump).with_context(|| format!("Failed to read dump {}", dump.display()))?;
+    let mut coin    = coin;
+    let mut skipped = 0;
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.with_context(|| format!("Failed to read dump {}", dump.display()))?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split(['\t', ',', ';']).map(|f| f.trim().trim_matches('"'));
+        let address = fields.next().unwrap_or_default();
+        let balance = match fields.next().filter(|f| !f.is_empty()) {
+            Some(f) => f.parse::<u64>().ok(),
+            None    => Some(UNKNOWN_BALANCE),
+        };
+        let script = match coin {
+            Some(c) => c.parse_address(address).ok(),
+            None    => coin::decode_address(address).map(|(c, s)| {
+                coin = Some(c);
+                s
+            }),
+        };
+        match (script.as_deref().and_then(Payload::from_script), balance) {
+            (Some(p), Some(b)) => writer.push(&p, b)?,
+            // 1a linha que nao e endereco: o cabecalho
+            _ if i == 0 => {}
+            _ => skipped += 1,
+        }
+    }
+    let Some(coin) = coin else {
+        anyhow::bail!("Dump {} has no address of a supported coin", dump.display());
+    };
+    let (records, below) = writer.finish(coin)?;
+    Ok(Imported { coin, records, skipped, below, snapshot: None })
+}
+
+// Lotes ordenados em arquivos temporarios, intercalados no indice por `finish`
+struct IndexWriter {
+    out:         PathBuf,
+    min_balance: u64,
+    entries:     Vec<(Key, u64)>,
+    runs:        Vec<PathBuf>,
+}
+
+impl IndexWriter {
+    fn new(out: &Path, min_balance: u64) -> Self {
+        Self { out: out.to_path_buf(), min_balance, entries: Vec::new(), runs: Vec::new() }
+    }
+
+    fn push(&mut self, payload: &Payload, balance: u64) -> Result<()> {
+        self.entries.push((key(payload), balance));
+        if self.entries.len() >= RUN {
+            self.flush_run()?;
+        }
+        Ok(())
+    }
+
+    fn sorted(&mut self) -> Vec<(Key, u64)> {
+        let mut entries = std::mem::take(&mut self.entries);
+        entries.sort_unstable_by_key(|e| e.0);
+        entries.dedup_by(|next, kept| {
+            let same = next.0 == kept.0;
+            if same {
+                kept.1 = kept.1.saturating_add(next.1);
+            }
+            same
+        });
+        entries
+    }
+
+    fn flush_run(&mut self) -> Result<()> {
+        let path = tmp_path(&self.out, &format!("run{}.tmp", self.runs.len()));
+        let entries = self.sorted();
+        let write = || -> std::io::Result<()> {
+            let mut w = BufWriter::new(File::create(&path)?);
+            for (key, balance) in &entries {
+                w.write_all(key)?;
+                w.write_all(&balance.to_le_bytes())?;
+            }
+            w.flush()
+        };
+        write().with_context(|| format!("Failed to write {}", path.display()))?;
+        self.runs.push(path);
+        Ok(())
+    }
+
+    /// Writes the index (atomically): (records, scripts under the minimum balance).
+    fn finish(mut self, coin: Coin) -> Result<(u64, u64)> {
+        let tmp = tmp_path(&self.out, "tmp");
+        let result = self.merge(&tmp, coin);
+        for run in &self.runs {
+            let _ = std::fs::remove_file(run);
+        }
+        let counts = result.with_context(|| format!("Failed to write index {}", tmp.display()))?;
+        std::fs::rename(&tmp, &self.out).with_context(|| format!("Failed to write index {}", self.out.display()))?;
+        Ok(counts)
+    }
+
+    // Intercala os lotes (ou so o que esta na memoria), somando o mesmo script
+    fn merge(&mut self, tmp: &Path, coin: Coin) -> Result<(u64, u64)> {
+        let mut sources: Vec<Records> = Vec::new();
+        if self.runs.is_empty() {
+            sources.push(Box::new(self.sorted().into_iter().map(Ok)));
+        } else {
+            if !self.entries.is_empty() {
+                self.flush_run()?;
+            }
+            for run in &self.runs {
+                let file = File::open(run).with_context(|| format!("Failed to read {}", run.display()))?;
+                sources.push(Box::new(read_records(BufReader::new(file)).map(|r| r.map_err(anyhow::Error::from))));
+            }
+        }
+
+        let mut w = BufWriter::new(File::create(tmp)?);
+        let mut name = [0u8; 16];
+        let id = coin_id(coin);
+        name[..id.len()].copy_from_slice(id.as_bytes());
+        w.write_all(MAGIC)?;
+        w.write_all(&0u64.to_le_bytes())?;
+        w.write_all(&name)?;
+
+        let mut heap = BinaryHeap::new();
+        for (i, source) in sources.iter_mut().enumerate() {
+            if let Some(entry) = source.next().transpose()? {
+                heap.push(Reverse((entry, i)));
+            }
+        }
+        let (mut records, mut below) = (0u64, 0u64);
+        let mut pending: Option<(Key, u64)> = None;
+        let min_balance = self.min_balance;
+        let mut emit = |entry: (Key, u64), w: &mut BufWriter<File>| -> std::io::Result<()> {
+            if entry.1 < min_balance {
+                below += 1;
+                return Ok(());
+            }
+            w.write_all(&entry.0)?;
+            w.write_all(&entry.1.to_le_bytes())?;
+            records += 1;
+            Ok(())
+        };
+        while let Some(Reverse(((key, balance), i))) = heap.pop() {
+            if let Some(entry) = sources[i].next().transpose()? {
+                heap.push(Reverse((entry, i)));
+            }
+            pending = match pending {
+                Some((k, b)) if k == key => Some((k, b.saturating_add(balance))),
+                Some(done) => {
+                    emit(done, &mut w)?;
+                    Some((key, balance))
+                }
+                None => Some((key, balance)),
+            };
+        }
+        if let Some(done) = pending {
+            emit(done, &mut w)?;
+        }
+        let mut file = w.into_inner()?;
+        file.seek(SeekFrom::Start(MAGIC.len() as u64))?;
+        file.write_all(&records.to_le_bytes())?;
+        file.sync_all()?;
+        Ok((records, below))
+    }
+}
+
+// "<out>.<suffix>", ao lado do indice (mesmo disco, rename atomico)
+fn tmp_path(out: &Path, suffix: &str) -> PathBuf {
+    let mut tmp = out.as_os_str().to_owned();
+    tmp.push(".");
+    tmp.push(suffix);
+    PathBuf::from(tmp)
+}
+
+// Nome da moeda como no --coin ("bitcoin", "bitcoin-cash", ...)
+fn coin_id(coin: Coin) -> String {
+    clap::ValueEnum::to_possible_value(&coin).map(|v| v.get_name().to_string()).unwrap_or_default()
+}
+
+/// An index written by [`import`], read from disk on each lookup.
+#[derive(Debug)]
+pub struct FundedIndex {
+    file:  Mutex<File>,
+    path:  PathBuf,
+    coin:  Coin,
+    count: u64,
+}
+
+impl FundedIndex {
+    pub fn open(path: &Path) -> Result<Self> {
+        let mut file = File::open(path).with_context(|| format!("Failed to read index {}", path.display()))?;
+        let mut header = [0u8; HEADER as usize];
+        file.read_exact(&mut header).ok().filter(|_| header.starts_with(MAGIC))
+            .with_context(|| format!("{} is not a funded-address index (see index-funded)", path.display()))?;
+        let count = u64::from_le_bytes(header[8..16].try_into()?);
+        let name  = String::from_utf8_lossy(&header[16..]).trim_end_matches('\0').to_string();
+        let coin  = <Coin as clap::ValueEnum>::from_str(&name, false)
+            .map_err(|_| anyhow::anyhow!("Index {} has an unknown coin {:?}", path.display(), name))?;
+        let len = file.metadata()?.len();
+        if len != HEADER + count * RECORD as u64 {
+            anyhow::bail!("Index {} is truncated ({} bytes for {} records)", path.display(), len, count);
+        }
+        Ok(Self { file: Mutex::new(file), path: path.to_path_buf(), coin, count })
+    }
+
+    /// Records (distinct scripts) in the index.
+    pub fn len(&self) -> u64 {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Coin of the addresses the index was built from.
+    pub fn coin(&self) -> Coin {
+        self.coin
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Every `(payload, balance)` of the index, in order, read as a stream.
+    pub fn entries(&self) -> Result<impl Iterator<Item = Result<(Payload, u64)>>> {
+        let mut file = File::open(&self.path).with_context(|| format!("Failed to read index {}", self.path.display()))?;
+        file.seek(SeekFrom::Start(HEADER))?;
+        let path = self.path.clone();
+        Ok(read_records(BufReader::new(file).take(self.count * RECORD as u64)).map(move |record| {
+            let (key, balance) = record.with_context(|| format!("Failed to read index {}", path.display()))?;
+            let payload = payload(&key).with_context(|| format!("Corrupt record in index {}", path.display()))?;
+            Ok((payload, balance))
+        }))
+    }
+
+    /// Balance of `payload`, `None` when the index does not list it (a binary search
+    /// over the file).
+    pub fn balance(&self, payload: &Payload) -> Result<Option<u64>> {
+        let wanted = key(payload);
+        let mut file = self.file.lock().expect("index lock");
+        let (mut lo, mut hi) = (0, self.count);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let mut record = [0u8; RECORD];
+            file.seek(SeekFrom::Start(HEADER + mid * RECORD as u64))
+                .and_then(|_| file.read_exact(&mut record))
+                .with_context(|| format!("Failed to read index {}", self.path.display()))?;
+            match record[..33].cmp(&wanted) {
+                std::cmp::Ordering::Less    => lo = mid + 1,
+                std::cmp::Ordering::Greater => hi = mid,
+                std::cmp::Ordering::Equal   => return Ok(Some(u64::from_le_bytes(record[33..].try_into()?))),
+            }
+        }
+        Ok(None)
+    }
+}
+
+// (chave, saldo) de cada registro ate o fim de `reader`
+fn read_records(mut reader: impl Read) -> impl Iterator<Item = std::io::Result<(Key, u64)>> {
+    std::iter::from_fn(move || {
+        let mut record = [0u8; RECORD];
+        match reader.read_exact(&mut record) {
+            Ok(()) => {
+                let (key, balance) = record.split_at(33);
+                Some(Ok((key.try_into().ok()?, u64::from_le_bytes(balance.try_into().ok()?))))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => None,
+            Err(e) => Some(Err(e)),
+        }
+    })
+}
@@ -0,0 +1,174 @@
+// ── Backend CUDA (feature "cuda") ────────────────────────────────────────────
+//
+// Os kernels (.cu) sao compilados em tempo de execucao via NVRTC para a
+// arquitetura do dispositivo escolhido, e a libcuda e carregada
+// dinamicamente — o binario roda (sem GPU) mesmo sem o driver instalado.
+//
+//   pbkdf2_sha512     — 1 thread por frase
+//   secp256k1_pubkey  — 1 thread por chave privada (k·G)
+
+use super::SeedBackend;
+use anyhow::{anyhow, Context as _, Result};
+use cudarc::driver::sys::CUdevice_attribute;
+use cudarc::driver::{CudaContext, CudaFunction, CudaStream, LaunchConfig, PushKernelArg};
+use cudarc::nvrtc::{compile_ptx_with_opts, CompileOptions};
+use std::sync::Arc;
+
+const PBKDF2_SOURCE: &str = include_str!("pbkdf2_sha512.cu");
+const SECP_SOURCE:   &str = include_str!("secp256k1.cu");
+const PBKDF2_ROUNDS: u32  = 2048;
+
+// Kernels usam muitos registradores (w[80] de u64); blocos menores evitam
+// CUDA_ERROR_LAUNCH_OUT_OF_RESOURCES
+const PBKDF2_BLOCK: u32 = 256;
+const SECP_BLOCK:   u32 = 128;
+
+pub struct CudaBackend {
+    name:   String,
+    stream: Arc<CudaStream>,
+    pbkdf2: CudaFunction,
+    pubkey: CudaFunction,
+    batch:  usize,
+}
+
+/// Nomes de todos os dispositivos CUDA visiveis, na ordem dos ordinais.
+pub fn devices() -> Result<Vec<String>> {
+    let count = CudaContext::device_count().map_err(|e| anyhow!("CUDA driver unavailable: {:?}", e))?;
+    (0..count.max(0) as usize)
+        .map(|i| {
+            let ctx = CudaContext::new(i).map_err(|e| anyhow!("cuCtxCreate({}) failed: {:?}", i, e))?;
+            ctx.name().map_err(|e| anyhow!("cuDeviceGetName failed: {:?}", e))
+        })
+        .collect()
+}
+
+fn launch_config(n: usize, block: u32) -> LaunchConfig {
+    LaunchConfig {
+        grid_dim:         ((n as u32).div_ceil(block), 1, 1),
+        block_dim:        (block, 1, 1),
+        shared_mem_bytes: 0,
+    }
+}
+
+impl CudaBackend {
+    pub fn new(ordinal: usize) -> Result<Self> {
+        let names = devices()?;
+        if ordinal >= names.len() {
+            anyhow::bail!("CUDA device {} not found ({})", ordinal, super::describe_devices(&names));
+        }
+
+        let ctx = CudaContext::new(ordinal).map_err(|e| anyhow!("cuCtxCreate failed: {:?}", e))?;
+        let (major, minor) = ctx.compute_capability()
+            .map_err(|e| anyhow!("cuDeviceGetAttribute failed: {:?}", e))?;
+        let sms = ctx.attribute(CUdevice_attribute::CU_DEVICE_ATTRIBUTE_MULTIPROCESSOR_COUNT)
+            .unwrap_or(16)
+            .max(1) as usize;
+
+        // Compila para a arquitetura exata (sm_XY) — habilita o desenrolamento completo
+        let arch: &'static str = Box::leak(format!("sm_{}{}", major, minor).into_boxed_str());
+        let opts = CompileOptions { arch: Some(arch), ..Default::default() };
+        let source = format!("{}\n{}", PBKDF2_SOURCE, SECP_SOURCE);
+        let ptx = compile_ptx_with_opts(source, opts)
+            .map_err(|e| anyhow!("NVRTC kernel build failed: {:?}", e))?;
+
+        let module = ctx.load_module(ptx).map_err(|e| anyhow!("cuModuleLoad failed: {:?}", e))?;
+        let pbkdf2 = module.load_function("pbkdf2_sha512")
+            .map_err(|e| anyhow!("cuModuleGetFunction(pbkdf2_sha512) failed: {:?}", e))?;
+        let pubkey = module.load_function("secp256k1_pubkey")
+            .map_err(|e| anyhow!("cuModuleGetFunction(secp256k1_pubkey) failed: {:?}", e))?;
+
+        Ok(Self {
+            name:   format!("CUDA: [{}] {} (sm_{}{}, {} SMs)", ordinal, names[ordinal], major, minor, sms),
+            stream: ctx.default_stream(),
+            pbkdf2,
+            pubkey,
+            batch:  (sms * 2048).clamp(8192, 1 << 19),
+        })
+    }
+}
+
+impl SeedBackend for CudaBackend {
+    fn device_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn batch_size(&self) -> usize {
+        self.batch
+    }
+
+    fn seeds(&mut self, phrases: &[String], salts: &[&[u8]]) -> Result<Vec<[u8; 64]>> {
+        let n = phrases.len();
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+
+        let (pw, off, len) = super::pack(phrases.iter().map(|p| p.as_bytes()));
+        let (salt, salt_off, salt_len) = super::pack(salts.iter().copied());
+
+        let s = &self.stream;
+        let pw_d       = s.memcpy_stod(&pw).context("cuMemcpyHtoD failed")?;
+        let off_d      = s.memcpy_stod(&off).context("cuMemcpyHtoD failed")?;
+        let len_d      = s.memcpy_stod(&len).context("cuMemcpyHtoD failed")?;
+        let salt_d     = s.memcpy_stod(&salt).context("cuMemcpyHtoD failed")?;
+        let salt_off_d = s.memcpy_stod(&salt_off).context("cuMemcpyHtoD failed")?;
+        let salt_len_d = s.memcpy_stod(&salt_len).context("cuMemcpyHtoD failed")?;
+        let mut out_d  = s.alloc_zeros::<u64>(n * 8).context("cuMemAlloc failed")?;
+
+        let count = n as u32;
+        let mut launch = s.launch_builder(&self.pbkdf2);
+        launch
+            .arg(&pw_d)
+            .arg(&off_d)
+            .arg(&len_d)
+            .arg(&salt_d)
+            .arg(&salt_off_d)
+            .arg(&salt_len_d)
+            .arg(&PBKDF2_ROUNDS)
+            .arg(&mut out_d)
+            .arg(&count);
+        unsafe { launch.launch(launch_config(n, PBKDF2_BLOCK)) }.context("pbkdf2_sha512 launch failed")?;
+
+        let out = s.memcpy_dtov(&out_d).context("cuMemcpyDtoH failed")?;
+        Ok(out
+            .chunks_exact(8)
+            .map(|words| {
+                let mut seed = [0u8; 64];
+                for (dst, w) in seed.chunks_exact_mut(8).zip(words) {
+                    dst.copy_from_slice(&w.to_be_bytes());
+                }
+                seed
+            })
+            .collect())
+    }
+
+    fn supports_point_mul(&self) -> bool {
+        true
+    }
+
+    fn point_mul(&mut self, secrets: &[[u8; 32]]) -> Result<Vec<[u8; 33]>> {
+        let n = secrets.len();
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+
+        let s = &self.stream;
+        let flat: Vec<u8> = secrets.iter().flatten().copied().collect();
+        let in_d = s.memcpy_stod(&flat).context("cuMemcpyHtoD failed")?;
+        let mut out_d = s.alloc_zeros::<u8>(n * 33).context("cuMemAlloc failed")?;
+
+        let count = n as u32;
+        let mut launch = s.launch_builder(&self.pubkey);
+        launch.arg(&in_d).arg(&mut out_d).arg(&count);
+        unsafe { launch.launch(launch_config(n, SECP_BLOCK)) }.context("secp256k1_pubkey launch failed")?;
+
+        let out = s.memcpy_dtov(&out_d).context("cuMemcpyDtoH failed")?;
+        Ok(out
+            .chunks_exact(33)
+            .map(|c| {
+                let mut pk = [0u8; 33];
+                pk.copy_from_slice(c);
+                pk
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,156 @@
+// ── Backends de GPU para o PBKDF2 ────────────────────────────────────────────
+//
+// O gargalo por candidato checksum-valido e o PBKDF2 (2048x HMAC-SHA512).
+// Um backend recebe um lote de frases ja normalizadas (cada uma com seu salt)
+// e devolve as seeds; a derivacao BIP32 + endereco continua na CPU (Rayon),
+// exceto quando o backend tambem faz multiplicacao de ponto (CUDA) — ai os
+// k·G dos passos nao-hardened e do filho final vao para a GPU em lote (ver
+// derive_pubkeys).
+
+use crate::derivation::Node;
+use anyhow::Result;
+use bitcoin::bip32::{ChildNumber, DerivationPath};
+use bitcoin::secp256k1::PublicKey;
+use rayon::prelude::*;
+
+#[cfg(feature = "opencl")]
+mod opencl;
+#[cfg(feature = "cuda")]
+mod cuda;
+
+/// GPU backend used for PBKDF2 seed stretching
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuKind {
+    /// OpenCL (any vendor; requires building with `--features opencl`)
+    Opencl,
+    /// NVIDIA CUDA, also batches secp256k1 point multiplication (requires `--features cuda`)
+    Cuda,
+}
+
+/// Calcula seeds BIP-39 em lote: `seed = PBKDF2-HMAC-SHA512(phrase, salt, 2048)`,
+/// com `salt = "mnemonic" + passphrase`. `Send`: roda no thread do estagio de
+/// PBKDF2 do pipeline da busca.
+pub trait SeedBackend: Send {
+    /// Descricao do dispositivo (para o cabecalho de execucao).
+    fn device_name(&self) -> String;
+
+    /// Tamanho de lote preferido (numero de frases por chamada).
+    fn batch_size(&self) -> usize;
+
+    /// Uma seed de 64 bytes por frase, na mesma ordem; `salts[i]` e o salt de `phrases[i]`.
+    fn seeds(&mut self, phrases: &[String], salts: &[&[u8]]) -> Result<Vec<[u8; 64]>>;
+
+    /// O backend implementa `point_mul`?
+    fn supports_point_mul(&self) -> bool {
+        false
+    }
+
+    /// k·G em lote → chaves publicas comprimidas (33 bytes). Um prefixo 0x00
+    /// marca um caso que a GPU nao resolveu; o chamador refaz na CPU.
+    fn point_mul(&mut self, _secrets: &[[u8; 32]]) -> Result<Vec<[u8; 33]>> {
+        anyhow::bail!("point multiplication not supported by this backend")
+    }
+}
+
+pub fn open(kind: GpuKind, device: usize) -> Result<Box<dyn SeedBackend>> {
+    match kind {
+        #[cfg(feature = "opencl")]
+        GpuKind::Opencl => Ok(Box::new(opencl::OpenClBackend::new(device)?)),
+        #[cfg(not(feature = "opencl"))]
+        GpuKind::Opencl => {
+            let _ = device;
+            anyhow::bail!("Built without OpenCL support. Rebuild with: cargo build --release --features opencl")
+        }
+        #[cfg(feature = "cuda")]
+        GpuKind::Cuda => Ok(Box::new(cuda::CudaBackend::new(device)?)),
+        #[cfg(not(feature = "cuda"))]
+        GpuKind::Cuda => {
+            let _ = device;
+            anyhow::bail!("Built without CUDA support. Rebuild with: cargo build --release --features cuda")
+        }
+    }
+}
+
+/// Concatena os itens para o dispositivo: (bytes, offsets, tamanhos).
+/// Nunca devolve um buffer vazio (tamanho zero e invalido em OpenCL).
+#[cfg(any(feature = "opencl", feature = "cuda"))]
+fn pack<'a>(items: impl ExactSizeIterator<Item = &'a [u8]>) -> (Vec<u8>, Vec<u32>, Vec<u32>) {
+    let mut data = Vec::with_capacity(items.len() * 96);
+    let mut off  = Vec::with_capacity(items.len());
+    let mut len  = Vec::with_capacity(items.len());
+    for item in items {
+        off.push(data.len() as u32);
+        len.push(item.len() as u32);
+        data.extend_from_slice(item);
+    }
+    if data.is_empty() { data.push(0); }
+    (data, off, len)
+}
+
+/// "available: [0] nome, [1] nome" — para mensagens de erro de --gpu-device.
+#[cfg(any(feature = "opencl", feature = "cuda"))]
+fn describe_devices(names: &[String]) -> String {
+    if names.is_empty() {
+        return "no devices available".to_string();
+    }
+    let list: Vec<String> = names.iter().enumerate()
+        .map(|(i, n)| format!("[{}] {}", i, n.trim()))
+        .collect();
+    format!("available: {}", list.join(", "))
+}
+
+// ── Derivacao BIP32 em lote com k·G na GPU ───────────────────────────────────
+//
+// O CKDpriv e o de derivation.rs (`Node`). Cada passo nao-hardened e o filho
+// final custam uma chamada point_mul com o lote inteiro; o resto (HMAC + soma
+// de escalares) roda na CPU via Rayon.
+
+/// k·G para todos os nos validos; os casos nao resolvidos pela GPU sao refeitos
+/// na CPU. Nos invalidos (`None`) recebem `None`.
+fn batch_pubkeys(backend: &mut dyn SeedBackend, nodes: &[Option<Node>]) -> Result<Vec<Option<[u8; 33]>>> {
+    let live: Vec<usize> = (0..nodes.len()).filter(|&i| nodes[i].is_some()).collect();
+    let secrets: Vec<[u8; 32]> = live.iter()
+        .filter_map(|&i| nodes[i].map(|n| n.key.secret_bytes()))
+        .collect();
+    let points = backend.point_mul(&secrets)?;
+
+    let mut out = vec![None; nodes.len()];
+    for (&i, mut pk) in live.iter().zip(points) {
+        if pk[0] != 0x02 && pk[0] != 0x03 {
+            if let Some(node) = nodes[i] {
+                pk = node.public_key().serialize();
+            }
+        }
+        out[i] = Some(pk);
+    }
+    Ok(out)
+}
+
+/// Chave publica do filho `path` para cada seed (`None` = derivacao invalida).
+pub fn derive_pubkeys(
+    backend: &mut dyn SeedBackend,
+    seeds: &[[u8; 64]],
+    path: &DerivationPath,
+) -> Result<Vec<Option<PublicKey>>> {
+    let mut nodes: Vec<Option<Node>> = seeds.par_iter()
+        .map(|seed| Node::master(seed))
+        .collect();
+
+    for &child in path.as_ref() {
+        let pubs = match child {
+            ChildNumber::Normal { .. } => Some(batch_pubkeys(backend, &nodes)?),
+            ChildNumber::Hardened { .. } => None,
+        };
+        nodes = nodes.par_iter().enumerate()
+            .map(|(i, node)| {
+                let pk = pubs.as_ref().and_then(|p| p[i].as_ref());
+                node.as_ref()?.child(child, pk)
+            })
+            .collect();
+    }
+
+    Ok(batch_pubkeys(backend, &nodes)?
+        .into_iter()
+        .map(|pk| pk.and_then(|b| PublicKey::from_slice(&b).ok()))
+        .collect())
+}
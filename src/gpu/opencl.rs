@@ -0,0 +1,138 @@
+// ── Backend OpenCL (feature "opencl") ────────────────────────────────────────
+//
+// Um programa, um kernel, uma fila. Por lote:
+//   [1] concatena as frases num unico buffer (offsets + tamanhos)
+//   [2] copia frases + salt para o dispositivo (CL_MEM_COPY_HOST_PTR)
+//   [3] roda pbkdf2_sha512 com 1 work-item por frase
+//   [4] le 8 ulongs por frase e converte para bytes big-endian
+
+use super::SeedBackend;
+use anyhow::{anyhow, Context as _, Result};
+use opencl3::command_queue::CommandQueue;
+use opencl3::context::Context;
+use opencl3::device::{get_all_devices, Device, CL_DEVICE_TYPE_ALL, CL_DEVICE_TYPE_GPU};
+use opencl3::kernel::{ExecuteKernel, Kernel};
+use opencl3::memory::{Buffer, CL_MEM_COPY_HOST_PTR, CL_MEM_READ_ONLY, CL_MEM_WRITE_ONLY};
+use opencl3::program::Program;
+use opencl3::types::{cl_device_id, cl_uchar, cl_uint, cl_ulong, CL_BLOCKING};
+use std::ffi::c_void;
+use std::ptr;
+
+const PROGRAM_SOURCE: &str = include_str!("pbkdf2_sha512.cl");
+const KERNEL_NAME: &str = "pbkdf2_sha512";
+const PBKDF2_ROUNDS: cl_uint = 2048;
+
+pub struct OpenClBackend {
+    device:  Device,
+    context: Context,
+    queue:   CommandQueue,
+    kernel:  Kernel,
+    index:   usize,
+    batch:   usize,
+}
+
+/// Dispositivos candidatos, na ordem usada por --gpu-device. Prefere GPUs;
+/// se nao houver, aceita qualquer dispositivo (ex.: pocl na CPU).
+fn device_ids() -> Result<Vec<cl_device_id>> {
+    match get_all_devices(CL_DEVICE_TYPE_GPU) {
+        Ok(ids) if !ids.is_empty() => Ok(ids),
+        _ => get_all_devices(CL_DEVICE_TYPE_ALL).context("Failed to enumerate OpenCL devices"),
+    }
+}
+
+impl OpenClBackend {
+    pub fn new(index: usize) -> Result<Self> {
+        let ids = device_ids()?;
+        let Some(&id) = ids.get(index) else {
+            let names: Vec<String> = ids.iter()
+                .map(|&id| Device::new(id).name().unwrap_or_else(|_| "unknown".into()))
+                .collect();
+            anyhow::bail!("OpenCL device {} not found ({})", index, super::describe_devices(&names));
+        };
+        let device = Device::new(id);
+
+        let context = Context::from_device(&device).context("clCreateContext failed")?;
+        let queue = CommandQueue::create_default(&context, 0).context("clCreateCommandQueue failed")?;
+        let program = Program::create_and_build_from_source(&context, PROGRAM_SOURCE, "")
+            .map_err(|log| anyhow!("OpenCL kernel build failed:\n{}", log))?;
+        let kernel = Kernel::create(&program, KERNEL_NAME).context("clCreateKernel failed")?;
+
+        // Varias "ondas" por compute unit para esconder latencia
+        let units = device.max_compute_units().unwrap_or(8).max(1) as usize;
+        let batch = (units * 1024).clamp(4096, 1 << 18);
+
+        Ok(Self { device, context, queue, kernel, index, batch })
+    }
+}
+
+impl SeedBackend for OpenClBackend {
+    fn device_name(&self) -> String {
+        let name   = self.device.name().unwrap_or_else(|_| "unknown".into());
+        let vendor = self.device.vendor().unwrap_or_default();
+        format!("OpenCL: [{}] {} ({})", self.index, name.trim(), vendor.trim())
+    }
+
+    fn batch_size(&self) -> usize {
+        self.batch
+    }
+
+    fn seeds(&mut self, phrases: &[String], salts: &[&[u8]]) -> Result<Vec<[u8; 64]>> {
+        let n = phrases.len();
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+
+        // [1] Frases e salts concatenados
+        let (mut pw, mut off, mut len) = super::pack(phrases.iter().map(|p| p.as_bytes()));
+        let (mut salt, mut salt_off, mut salt_len) = super::pack(salts.iter().copied());
+
+        // [2] Buffers no dispositivo
+        let flags = CL_MEM_READ_ONLY | CL_MEM_COPY_HOST_PTR;
+        let (pw_d, off_d, len_d, salt_d, salt_off_d, salt_len_d, out_d) = unsafe {
+            (
+                Buffer::<cl_uchar>::create(&self.context, flags, pw.len(), pw.as_mut_ptr() as *mut c_void)?,
+                Buffer::<cl_uint>::create(&self.context, flags, n, off.as_mut_ptr() as *mut c_void)?,
+                Buffer::<cl_uint>::create(&self.context, flags, n, len.as_mut_ptr() as *mut c_void)?,
+                Buffer::<cl_uchar>::create(&self.context, flags, salt.len(), salt.as_mut_ptr() as *mut c_void)?,
+                Buffer::<cl_uint>::create(&self.context, flags, n, salt_off.as_mut_ptr() as *mut c_void)?,
+                Buffer::<cl_uint>::create(&self.context, flags, n, salt_len.as_mut_ptr() as *mut c_void)?,
+                Buffer::<cl_ulong>::create(&self.context, CL_MEM_WRITE_ONLY, n * 8, ptr::null_mut())?,
+            )
+        };
+
+        // [3] Kernel
+        let event = unsafe {
+            ExecuteKernel::new(&self.kernel)
+                .set_arg(&pw_d)
+                .set_arg(&off_d)
+                .set_arg(&len_d)
+                .set_arg(&salt_d)
+                .set_arg(&salt_off_d)
+                .set_arg(&salt_len_d)
+                .set_arg(&PBKDF2_ROUNDS)
+                .set_arg(&out_d)
+                .set_global_work_size(n)
+                .enqueue_nd_range(&self.queue)
+                .context("clEnqueueNDRangeKernel failed")?
+        };
+
+        // [4] Leitura bloqueante apos o kernel
+        let mut out: Vec<cl_ulong> = vec![0; n * 8];
+        unsafe {
+            self.queue
+                .enqueue_read_buffer(&out_d, CL_BLOCKING, 0, &mut out, &[event.get()])
+                .context("clEnqueueReadBuffer failed")?;
+        }
+
+        Ok(out
+            .chunks_exact(8)
+            .map(|words| {
+                let mut seed = [0u8; 64];
+                for (dst, w) in seed.chunks_exact_mut(8).zip(words) {
+                    dst.copy_from_slice(&w.to_be_bytes());
+                }
+                seed
+            })
+            .collect())
+    }
+}
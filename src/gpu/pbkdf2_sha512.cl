@@ -0,0 +1,182 @@
+// ── PBKDF2-HMAC-SHA512 para seeds BIP-39 ─────────────────────────────────────
+//
+// Um work-item por frase candidata. Entrada:
+//   pw / pw_off / pw_len       — frases concatenadas (UTF-8 ja normalizado)
+//   salt / salt_off / salt_len — "mnemonic" + passphrase de cada frase
+//   iterations                 — 2048 no BIP-39
+// Saida: 8 ulongs (big-endian logico) por frase = seed de 64 bytes.
+//
+// O estado HMAC (ipad/opad) e comprimido uma unica vez; cada iteracao custa
+// exatamente 2 compressoes SHA-512 sobre blocos de tamanho fixo.
+
+#define ROTR64(x, n) rotate((ulong)(x), (ulong)(64 - (n)))
+
+#define S0(x) (ROTR64(x, 28) ^ ROTR64(x, 34) ^ ROTR64(x, 39))
+#define S1(x) (ROTR64(x, 14) ^ ROTR64(x, 18) ^ ROTR64(x, 41))
+#define s0(x) (ROTR64(x, 1)  ^ ROTR64(x, 8)  ^ ((x) >> 7))
+#define s1(x) (ROTR64(x, 19) ^ ROTR64(x, 61) ^ ((x) >> 6))
+#define CH(x, y, z)  (((x) & (y)) ^ (~(x) & (z)))
+#define MAJ(x, y, z) (((x) & (y)) ^ ((x) & (z)) ^ ((y) & (z)))
+
+__constant ulong K512[80] = {
+    0x428a2f98d728ae22UL, 0x7137449123ef65cdUL, 0xb5c0fbcfec4d3b2fUL, 0xe9b5dba58189dbbcUL,
+    0x3956c25bf348b538UL, 0x59f111f1b605d019UL, 0x923f82a4af194f9bUL, 0xab1c5ed5da6d8118UL,
+    0xd807aa98a3030242UL, 0x12835b0145706fbeUL, 0x243185be4ee4b28cUL, 0x550c7dc3d5ffb4e2UL,
+    0x72be5d74f27b896fUL, 0x80deb1fe3b1696b1UL, 0x9bdc06a725c71235UL, 0xc19bf174cf692694UL,
+    0xe49b69c19ef14ad2UL, 0xefbe4786384f25e3UL, 0x0fc19dc68b8cd5b5UL, 0x240ca1cc77ac9c65UL,
+    0x2de92c6f592b0275UL, 0x4a7484aa6ea6e483UL, 0x5cb0a9dcbd41fbd4UL, 0x76f988da831153b5UL,
+    0x983e5152ee66dfabUL, 0xa831c66d2db43210UL, 0xb00327c898fb213fUL, 0xbf597fc7beef0ee4UL,
+    0xc6e00bf33da88fc2UL, 0xd5a79147930aa725UL, 0x06ca6351e003826fUL, 0x142929670a0e6e70UL,
+    0x27b70a8546d22ffcUL, 0x2e1b21385c26c926UL, 0x4d2c6dfc5ac42aedUL, 0x53380d139d95b3dfUL,
+    0x650a73548baf63deUL, 0x766a0abb3c77b2a8UL, 0x81c2c92e47edaee6UL, 0x92722c851482353bUL,
+    0xa2bfe8a14cf10364UL, 0xa81a664bbc423001UL, 0xc24b8b70d0f89791UL, 0xc76c51a30654be30UL,
+    0xd192e819d6ef5218UL, 0xd69906245565a910UL, 0xf40e35855771202aUL, 0x106aa07032bbd1b8UL,
+    0x19a4c116b8d2d0c8UL, 0x1e376c085141ab53UL, 0x2748774cdf8eeb99UL, 0x34b0bcb5e19b48a8UL,
+    0x391c0cb3c5c95a63UL, 0x4ed8aa4ae3418acbUL, 0x5b9cca4f7763e373UL, 0x682e6ff3d6b2b8a3UL,
+    0x748f82ee5defb2fcUL, 0x78a5636f43172f60UL, 0x84c87814a1f0ab72UL, 0x8cc702081a6439ecUL,
+    0x90befffa23631e28UL, 0xa4506cebde82bde9UL, 0xbef9a3f7b2c67915UL, 0xc67178f2e372532bUL,
+    0xca273eceea26619cUL, 0xd186b8c721c0c207UL, 0xeada7dd6cde0eb1eUL, 0xf57d4f7fee6ed178UL,
+    0x06f067aa72176fbaUL, 0x0a637dc5a2c898a6UL, 0x113f9804bef90daeUL, 0x1b710b35131c471bUL,
+    0x28db77f523047d84UL, 0x32caab7b40c72493UL, 0x3c9ebe0a15c9bebcUL, 0x431d67c49c100d4cUL,
+    0x4cc5d4becb3e42b6UL, 0x597f299cfc657e2aUL, 0x5fcb6fab3ad6faecUL, 0x6c44198c4a475817UL,
+};
+
+__constant ulong IV512[8] = {
+    0x6a09e667f3bcc908UL, 0xbb67ae8584caa73bUL, 0x3c6ef372fe94f82bUL, 0xa54ff53a5f1d36f1UL,
+    0x510e527fade682d1UL, 0x9b05688c2b3e6c1fUL, 0x1f83d9abfb41bd6bUL, 0x5be0cd19137e2179UL,
+};
+
+static void sha512_compress(ulong *h, const ulong *block)
+{
+    ulong w[80];
+    for (int i = 0; i < 16; i++) w[i] = block[i];
+    for (int i = 16; i < 80; i++) w[i] = s1(w[i - 2]) + w[i - 7] + s0(w[i - 15]) + w[i - 16];
+
+    ulong a = h[0], b = h[1], c = h[2], d = h[3];
+    ulong e = h[4], f = h[5], g = h[6], k = h[7];
+    for (int i = 0; i < 80; i++) {
+        ulong t1 = k + S1(e) + CH(e, f, g) + K512[i] + w[i];
+        ulong t2 = S0(a) + MAJ(a, b, c);
+        k = g; g = f; f = e; e = d + t1;
+        d = c; c = b; b = a; a = t1 + t2;
+    }
+    h[0] += a; h[1] += b; h[2] += c; h[3] += d;
+    h[4] += e; h[5] += f; h[6] += g; h[7] += k;
+}
+
+// ── Contexto incremental (so usado para chave longa e para o salt) ──────────
+typedef struct {
+    ulong h[8];
+    ulong w[16];
+    uint  fill;  // bytes ja escritos no bloco atual
+    ulong total; // bytes totais da mensagem (inclui o bloco de pad do HMAC)
+} sha512_ctx;
+
+static void ctx_clear_block(sha512_ctx *c)
+{
+    for (int i = 0; i < 16; i++) c->w[i] = 0;
+    c->fill = 0;
+}
+
+static void ctx_init(sha512_ctx *c, const ulong *state, ulong already)
+{
+    for (int i = 0; i < 8; i++) c->h[i] = state[i];
+    ctx_clear_block(c);
+    c->total = already;
+}
+
+static void ctx_byte(sha512_ctx *c, uchar b)
+{
+    uint i = c->fill;
+    c->w[i >> 3] |= ((ulong)b) << (56 - ((i & 7) << 3));
+    c->fill = i + 1;
+    c->total++;
+    if (c->fill == 128) {
+        sha512_compress(c->h, c->w);
+        ctx_clear_block(c);
+    }
+}
+
+static void ctx_update_global(sha512_ctx *c, __global const uchar *p, uint n)
+{
+    for (uint i = 0; i < n; i++) ctx_byte(c, p[i]);
+}
+
+static void ctx_final(sha512_ctx *c, ulong *out)
+{
+    ulong bits = c->total << 3;
+    uint i = c->fill;
+    c->w[i >> 3] |= ((ulong)0x80) << (56 - ((i & 7) << 3));
+    if (i >= 112) {
+        sha512_compress(c->h, c->w);
+        ctx_clear_block(c);
+    }
+    c->w[15] = bits;
+    sha512_compress(c->h, c->w);
+    for (int j = 0; j < 8; j++) out[j] = c->h[j];
+}
+
+__kernel void pbkdf2_sha512(__global const uchar *pw,
+                            __global const uint  *pw_off,
+                            __global const uint  *pw_len,
+                            __global const uchar *salt,
+                            __global const uint  *salt_off,
+                            __global const uint  *salt_len,
+                            const uint iterations,
+                            __global ulong *out)
+{
+    const uint gid = get_global_id(0);
+    __global const uchar *p = pw + pw_off[gid];
+    const uint plen = pw_len[gid];
+
+    // [1] Chave HMAC: frases > 128 bytes sao primeiro reduzidas com SHA-512
+    ulong key[16];
+    for (int i = 0; i < 16; i++) key[i] = 0;
+    if (plen > 128) {
+        sha512_ctx kc;
+        ulong iv[8];
+        for (int i = 0; i < 8; i++) iv[i] = IV512[i];
+        ctx_init(&kc, iv, 0);
+        ctx_update_global(&kc, p, plen);
+        ctx_final(&kc, key);
+    } else {
+        for (uint i = 0; i < plen; i++)
+            key[i >> 3] |= ((ulong)p[i]) << (56 - ((i & 7) << 3));
+    }
+
+    // [2] Estados pre-comprimidos de ipad/opad
+    ulong istate[8], ostate[8], blk[16];
+    for (int i = 0; i < 8; i++) { istate[i] = IV512[i]; ostate[i] = IV512[i]; }
+    for (int i = 0; i < 16; i++) blk[i] = key[i] ^ 0x3636363636363636UL;
+    sha512_compress(istate, blk);
+    for (int i = 0; i < 16; i++) blk[i] = key[i] ^ 0x5c5c5c5c5c5c5c5cUL;
+    sha512_compress(ostate, blk);
+
+    // [3] U1 = HMAC(salt || INT_32_BE(1))
+    ulong u[8], t[8], s[8];
+    sha512_ctx sc;
+    ctx_init(&sc, istate, 128);
+    ctx_update_global(&sc, salt + salt_off[gid], salt_len[gid]);
+    ctx_byte(&sc, 0); ctx_byte(&sc, 0); ctx_byte(&sc, 0); ctx_byte(&sc, 1);
+    ctx_final(&sc, u);
+
+    // Bloco fixo de 64 bytes + pad: mensagem total = 128 (pad) + 64 bytes
+    for (int i = 8; i < 16; i++) blk[i] = 0;
+    blk[8]  = 0x8000000000000000UL;
+    blk[15] = (128 + 64) * 8;
+
+    for (int i = 0; i < 8; i++) { blk[i] = u[i]; s[i] = ostate[i]; }
+    sha512_compress(s, blk);
+    for (int i = 0; i < 8; i++) { u[i] = s[i]; t[i] = s[i]; }
+
+    // [4] U2..Un — 2 compressoes por iteracao
+    for (uint it = 1; it < iterations; it++) {
+        for (int i = 0; i < 8; i++) { blk[i] = u[i]; s[i] = istate[i]; }
+        sha512_compress(s, blk);
+        for (int i = 0; i < 8; i++) { blk[i] = s[i]; s[i] = ostate[i]; }
+        sha512_compress(s, blk);
+        for (int i = 0; i < 8; i++) { u[i] = s[i]; t[i] ^= s[i]; }
+    }
+
+    for (int i = 0; i < 8; i++) out[gid * 8 + i] = t[i];
+}
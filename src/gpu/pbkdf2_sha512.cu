@@ -0,0 +1,192 @@
+// ── PBKDF2-HMAC-SHA512 (CUDA) ────────────────────────────────────────────────
+//
+// Mesma interface do kernel OpenCL (pbkdf2_sha512.cl), mas ajustado para NVIDIA:
+//   - constantes em __constant__ e loops totalmente desenrolados
+//   - bloco de 64 bytes do laco principal com o padding fixo ja embutido:
+//     w[8..15] sao constantes, entao so w[0..7] sao carregados por iteracao
+//   - ipad/opad comprimidos uma vez por thread (nao por iteracao)
+
+typedef unsigned char      u8;
+typedef unsigned int       u32;
+typedef unsigned long long u64;
+
+__device__ __forceinline__ u64 rotr64(u64 x, u32 n) { return (x >> n) | (x << (64 - n)); }
+
+#define S0(x) (rotr64(x, 28) ^ rotr64(x, 34) ^ rotr64(x, 39))
+#define S1(x) (rotr64(x, 14) ^ rotr64(x, 18) ^ rotr64(x, 41))
+#define s0(x) (rotr64(x, 1)  ^ rotr64(x, 8)  ^ ((x) >> 7))
+#define s1(x) (rotr64(x, 19) ^ rotr64(x, 61) ^ ((x) >> 6))
+#define CH(x, y, z)  (((x) & (y)) ^ (~(x) & (z)))
+#define MAJ(x, y, z) (((x) & (y)) ^ ((x) & (z)) ^ ((y) & (z)))
+
+__constant__ u64 K512[80] = {
+    0x428a2f98d728ae22ULL, 0x7137449123ef65cdULL, 0xb5c0fbcfec4d3b2fULL, 0xe9b5dba58189dbbcULL,
+    0x3956c25bf348b538ULL, 0x59f111f1b605d019ULL, 0x923f82a4af194f9bULL, 0xab1c5ed5da6d8118ULL,
+    0xd807aa98a3030242ULL, 0x12835b0145706fbeULL, 0x243185be4ee4b28cULL, 0x550c7dc3d5ffb4e2ULL,
+    0x72be5d74f27b896fULL, 0x80deb1fe3b1696b1ULL, 0x9bdc06a725c71235ULL, 0xc19bf174cf692694ULL,
+    0xe49b69c19ef14ad2ULL, 0xefbe4786384f25e3ULL, 0x0fc19dc68b8cd5b5ULL, 0x240ca1cc77ac9c65ULL,
+    0x2de92c6f592b0275ULL, 0x4a7484aa6ea6e483ULL, 0x5cb0a9dcbd41fbd4ULL, 0x76f988da831153b5ULL,
+    0x983e5152ee66dfabULL, 0xa831c66d2db43210ULL, 0xb00327c898fb213fULL, 0xbf597fc7beef0ee4ULL,
+    0xc6e00bf33da88fc2ULL, 0xd5a79147930aa725ULL, 0x06ca6351e003826fULL, 0x142929670a0e6e70ULL,
+    0x27b70a8546d22ffcULL, 0x2e1b21385c26c926ULL, 0x4d2c6dfc5ac42aedULL, 0x53380d139d95b3dfULL,
+    0x650a73548baf63deULL, 0x766a0abb3c77b2a8ULL, 0x81c2c92e47edaee6ULL, 0x92722c851482353bULL,
+    0xa2bfe8a14cf10364ULL, 0xa81a664bbc423001ULL, 0xc24b8b70d0f89791ULL, 0xc76c51a30654be30ULL,
+    0xd192e819d6ef5218ULL, 0xd69906245565a910ULL, 0xf40e35855771202aULL, 0x106aa07032bbd1b8ULL,
+    0x19a4c116b8d2d0c8ULL, 0x1e376c085141ab53ULL, 0x2748774cdf8eeb99ULL, 0x34b0bcb5e19b48a8ULL,
+    0x391c0cb3c5c95a63ULL, 0x4ed8aa4ae3418acbULL, 0x5b9cca4f7763e373ULL, 0x682e6ff3d6b2b8a3ULL,
+    0x748f82ee5defb2fcULL, 0x78a5636f43172f60ULL, 0x84c87814a1f0ab72ULL, 0x8cc702081a6439ecULL,
+    0x90befffa23631e28ULL, 0xa4506cebde82bde9ULL, 0xbef9a3f7b2c67915ULL, 0xc67178f2e372532bULL,
+    0xca273eceea26619cULL, 0xd186b8c721c0c207ULL, 0xeada7dd6cde0eb1eULL, 0xf57d4f7fee6ed178ULL,
+    0x06f067aa72176fbaULL, 0x0a637dc5a2c898a6ULL, 0x113f9804bef90daeULL, 0x1b710b35131c471bULL,
+    0x28db77f523047d84ULL, 0x32caab7b40c72493ULL, 0x3c9ebe0a15c9bebcULL, 0x431d67c49c100d4cULL,
+    0x4cc5d4becb3e42b6ULL, 0x597f299cfc657e2aULL, 0x5fcb6fab3ad6faecULL, 0x6c44198c4a475817ULL,
+};
+
+__constant__ u64 IV512[8] = {
+    0x6a09e667f3bcc908ULL, 0xbb67ae8584caa73bULL, 0x3c6ef372fe94f82bULL, 0xa54ff53a5f1d36f1ULL,
+    0x510e527fade682d1ULL, 0x9b05688c2b3e6c1fULL, 0x1f83d9abfb41bd6bULL, 0x5be0cd19137e2179ULL,
+};
+
+__device__ __forceinline__ void sha512_compress(u64 *h, const u64 *block)
+{
+    u64 w[80];
+#pragma unroll
+    for (int i = 0; i < 16; i++) w[i] = block[i];
+#pragma unroll
+    for (int i = 16; i < 80; i++) w[i] = s1(w[i - 2]) + w[i - 7] + s0(w[i - 15]) + w[i - 16];
+
+    u64 a = h[0], b = h[1], c = h[2], d = h[3];
+    u64 e = h[4], f = h[5], g = h[6], k = h[7];
+#pragma unroll
+    for (int i = 0; i < 80; i++) {
+        u64 t1 = k + S1(e) + CH(e, f, g) + K512[i] + w[i];
+        u64 t2 = S0(a) + MAJ(a, b, c);
+        k = g; g = f; f = e; e = d + t1;
+        d = c; c = b; b = a; a = t1 + t2;
+    }
+    h[0] += a; h[1] += b; h[2] += c; h[3] += d;
+    h[4] += e; h[5] += f; h[6] += g; h[7] += k;
+}
+
+// Compressao de um bloco "64 bytes + padding" de uma mensagem de 192 bytes
+// (128 do pad HMAC + 64 do digest anterior): so msg[0..7] variam.
+__device__ __forceinline__ void sha512_compress_64(u64 *out, const u64 *state, const u64 *msg)
+{
+    u64 blk[16];
+#pragma unroll
+    for (int i = 0; i < 8; i++) { blk[i] = msg[i]; out[i] = state[i]; }
+    blk[8]  = 0x8000000000000000ULL;
+    blk[9]  = 0; blk[10] = 0; blk[11] = 0; blk[12] = 0; blk[13] = 0; blk[14] = 0;
+    blk[15] = (128 + 64) * 8;
+    sha512_compress(out, blk);
+}
+
+// ── Contexto incremental (chave > 128 bytes e salt) ─────────────────────────
+struct sha512_ctx {
+    u64 h[8];
+    u64 w[16];
+    u32 fill;
+    u64 total;
+};
+
+__device__ void ctx_init(sha512_ctx *c, const u64 *state, u64 already)
+{
+    for (int i = 0; i < 8; i++) c->h[i] = state[i];
+    for (int i = 0; i < 16; i++) c->w[i] = 0;
+    c->fill = 0;
+    c->total = already;
+}
+
+__device__ void ctx_byte(sha512_ctx *c, u8 b)
+{
+    u32 i = c->fill;
+    c->w[i >> 3] |= ((u64)b) << (56 - ((i & 7) << 3));
+    c->fill = i + 1;
+    c->total++;
+    if (c->fill == 128) {
+        sha512_compress(c->h, c->w);
+        for (int j = 0; j < 16; j++) c->w[j] = 0;
+        c->fill = 0;
+    }
+}
+
+__device__ void ctx_final(sha512_ctx *c, u64 *out)
+{
+    u64 bits = c->total << 3;
+    u32 i = c->fill;
+    c->w[i >> 3] |= ((u64)0x80) << (56 - ((i & 7) << 3));
+    if (i >= 112) {
+        sha512_compress(c->h, c->w);
+        for (int j = 0; j < 16; j++) c->w[j] = 0;
+    }
+    c->w[15] = bits;
+    sha512_compress(c->h, c->w);
+    for (int j = 0; j < 8; j++) out[j] = c->h[j];
+}
+
+extern "C" __global__ void pbkdf2_sha512(const u8  *pw,
+                                         const u32 *pw_off,
+                                         const u32 *pw_len,
+                                         const u8  *salt,
+                                         const u32 *salt_off,
+                                         const u32 *salt_len,
+                                         u32 iterations,
+                                         u64 *out,
+                                         u32 count)
+{
+    const u32 gid = blockIdx.x * blockDim.x + threadIdx.x;
+    if (gid >= count) return;
+
+    const u8 *p = pw + pw_off[gid];
+    const u32 plen = pw_len[gid];
+
+    // [1] Chave HMAC
+    u64 key[16];
+#pragma unroll
+    for (int i = 0; i < 16; i++) key[i] = 0;
+    if (plen > 128) {
+        sha512_ctx kc;
+        u64 iv[8];
+        for (int i = 0; i < 8; i++) iv[i] = IV512[i];
+        ctx_init(&kc, iv, 0);
+        for (u32 i = 0; i < plen; i++) ctx_byte(&kc, p[i]);
+        ctx_final(&kc, key);
+    } else {
+        for (u32 i = 0; i < plen; i++)
+            key[i >> 3] |= ((u64)p[i]) << (56 - ((i & 7) << 3));
+    }
+
+    // [2] ipad/opad pre-comprimidos
+    u64 istate[8], ostate[8], blk[16];
+#pragma unroll
+    for (int i = 0; i < 8; i++) { istate[i] = IV512[i]; ostate[i] = IV512[i]; }
+#pragma unroll
+    for (int i = 0; i < 16; i++) blk[i] = key[i] ^ 0x3636363636363636ULL;
+    sha512_compress(istate, blk);
+#pragma unroll
+    for (int i = 0; i < 16; i++) blk[i] = key[i] ^ 0x5c5c5c5c5c5c5c5cULL;
+    sha512_compress(ostate, blk);
+
+    // [3] U1 = HMAC(salt || INT_32_BE(1))
+    u64 u[8], t[8], s[8];
+    sha512_ctx sc;
+    ctx_init(&sc, istate, 128);
+    const u8 *sp = salt + salt_off[gid];
+    for (u32 i = 0; i < salt_len[gid]; i++) ctx_byte(&sc, sp[i]);
+    ctx_byte(&sc, 0); ctx_byte(&sc, 0); ctx_byte(&sc, 0); ctx_byte(&sc, 1);
+    ctx_final(&sc, s);
+    sha512_compress_64(u, ostate, s);
+#pragma unroll
+    for (int i = 0; i < 8; i++) t[i] = u[i];
+
+    // [4] U2..Un
+    for (u32 it = 1; it < iterations; it++) {
+        sha512_compress_64(s, istate, u);
+        sha512_compress_64(u, ostate, s);
+#pragma unroll
+        for (int i = 0; i < 8; i++) t[i] ^= u[i];
+    }
+
+#pragma unroll
+    for (int i = 0; i < 8; i++) out[(u64)gid * 8 + i] = t[i];
+}
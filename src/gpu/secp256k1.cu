@@ -0,0 +1,321 @@
+// ── secp256k1: multiplicacao k·G em lote (CUDA) ──────────────────────────────
+//
+// Uma thread por chave privada. Usado pela derivacao BIP32 em lote para obter
+// a chave publica comprimida dos passos nao-hardened e do filho final.
+//
+// Campo: p = 2^256 - 2^32 - 977, elementos com 8 limbs u32 little-endian.
+// Reducao usa 2^256 ≡ 2^32 + 977 (mod p).
+// Pontos em coordenadas Jacobianas; soma mista com G afim; double-and-add.
+//
+// Saida: 33 bytes (02|03 || X big-endian). Se a thread cair num caso
+// degenerado (soma com ±G ou escalar zero), escreve 0x00 no prefixo e o host
+// recalcula essa chave na CPU.
+
+typedef unsigned char      u8;
+typedef unsigned int       u32;
+typedef unsigned long long u64;
+
+__constant__ u32 SECP_P[8] = {
+    0xFFFFFC2F, 0xFFFFFFFE, 0xFFFFFFFF, 0xFFFFFFFF,
+    0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF, 0xFFFFFFFF,
+};
+
+__constant__ u32 SECP_GX[8] = {
+    0x16F81798, 0x59F2815B, 0x2DCE28D9, 0x029BFCDB,
+    0xCE870B07, 0x55A06295, 0xF9DCBBAC, 0x79BE667E,
+};
+
+__constant__ u32 SECP_GY[8] = {
+    0xFB10D4B8, 0x9C47D08F, 0xA6855419, 0xFD17B448,
+    0x0E1108A8, 0x5DA4FBFC, 0x26A3C465, 0x483ADA77,
+};
+
+// ── Aritmetica de campo ──────────────────────────────────────────────────────
+__device__ __forceinline__ void fe_copy(u32 *r, const u32 *a)
+{
+#pragma unroll
+    for (int i = 0; i < 8; i++) r[i] = a[i];
+}
+
+__device__ __forceinline__ bool fe_is_zero(const u32 *a)
+{
+    u32 acc = 0;
+#pragma unroll
+    for (int i = 0; i < 8; i++) acc |= a[i];
+    return acc == 0;
+}
+
+__device__ __forceinline__ bool fe_gte_p(const u32 *a)
+{
+    for (int i = 7; i >= 0; i--) {
+        if (a[i] > SECP_P[i]) return true;
+        if (a[i] < SECP_P[i]) return false;
+    }
+    return true;
+}
+
+__device__ __forceinline__ void fe_sub_p(u32 *a)
+{
+    u64 borrow = 0;
+#pragma unroll
+    for (int i = 0; i < 8; i++) {
+        u64 d = (u64)a[i] - SECP_P[i] - borrow;
+        a[i] = (u32)d;
+        borrow = (d >> 63) & 1;
+    }
+}
+
+__device__ void fe_add(u32 *r, const u32 *a, const u32 *b)
+{
+    u64 c = 0;
+#pragma unroll
+    for (int i = 0; i < 8; i++) {
+        c += (u64)a[i] + b[i];
+        r[i] = (u32)c;
+        c >>= 32;
+    }
+    if (c || fe_gte_p(r)) fe_sub_p(r);
+}
+
+__device__ void fe_sub(u32 *r, const u32 *a, const u32 *b)
+{
+    u64 borrow = 0;
+#pragma unroll
+    for (int i = 0; i < 8; i++) {
+        u64 d = (u64)a[i] - b[i] - borrow;
+        r[i] = (u32)d;
+        borrow = (d >> 63) & 1;
+    }
+    if (borrow) {
+        u64 c = 0;
+#pragma unroll
+        for (int i = 0; i < 8; i++) {
+            c += (u64)r[i] + SECP_P[i];
+            r[i] = (u32)c;
+            c >>= 32;
+        }
+    }
+}
+
+__device__ void fe_mul(u32 *r, const u32 *a, const u32 *b)
+{
+    u32 t[16];
+    u64 c;
+
+    // Produto 256x256 → 512 bits (schoolbook)
+#pragma unroll
+    for (int i = 0; i < 16; i++) t[i] = 0;
+#pragma unroll
+    for (int i = 0; i < 8; i++) {
+        c = 0;
+#pragma unroll
+        for (int j = 0; j < 8; j++) {
+            c += (u64)a[i] * b[j] + t[i + j];
+            t[i + j] = (u32)c;
+            c >>= 32;
+        }
+        t[i + 8] = (u32)c;
+    }
+
+    // 1a reducao: L + H·977 + H·2^32
+    u32 m[8];
+    c = 0;
+#pragma unroll
+    for (int i = 0; i < 8; i++) {
+        c += (u64)t[i] + (u64)t[8 + i] * 977ULL;
+        if (i > 0) c += t[7 + i];
+        m[i] = (u32)c;
+        c >>= 32;
+    }
+    u64 hi = c + t[15];
+
+    // 2a reducao: hi < 2^34
+    c = (u64)m[0] + hi * 977ULL;
+    m[0] = (u32)c; c >>= 32;
+    c += (u64)m[1] + hi;
+    m[1] = (u32)c; c >>= 32;
+#pragma unroll
+    for (int i = 2; i < 8; i++) {
+        c += m[i];
+        m[i] = (u32)c;
+        c >>= 32;
+    }
+
+    // Raro: ainda sobrou um bit acima de 2^256
+    if (c) {
+        c = (u64)m[0] + 977ULL;
+        m[0] = (u32)c; c >>= 32;
+        c += (u64)m[1] + 1;
+        m[1] = (u32)c; c >>= 32;
+        for (int i = 2; i < 8; i++) {
+            c += m[i];
+            m[i] = (u32)c;
+            c >>= 32;
+        }
+    }
+
+    if (fe_gte_p(m)) fe_sub_p(m);
+    fe_copy(r, m);
+}
+
+__device__ __forceinline__ void fe_sqr(u32 *r, const u32 *a) { fe_mul(r, a, a); }
+
+// a^(p-2) — bits de p-2 do mais para o menos significativo
+__device__ void fe_inv(u32 *r, const u32 *a)
+{
+    u32 e[8];
+    fe_copy(e, SECP_P);
+    e[0] -= 2;
+
+    u32 acc[8] = {1, 0, 0, 0, 0, 0, 0, 0};
+    for (int i = 255; i >= 0; i--) {
+        fe_sqr(acc, acc);
+        if ((e[i >> 5] >> (i & 31)) & 1) fe_mul(acc, acc, a);
+    }
+    fe_copy(r, acc);
+}
+
+// ── Pontos Jacobianos (a = 0) ────────────────────────────────────────────────
+// dbl-2009-l
+__device__ void jac_double(u32 *x, u32 *y, u32 *z)
+{
+    u32 a[8], b[8], c[8], d[8], e[8], f[8], t[8];
+
+    fe_sqr(a, x);
+    fe_sqr(b, y);
+    fe_sqr(c, b);
+
+    fe_add(t, x, b);
+    fe_sqr(t, t);
+    fe_sub(t, t, a);
+    fe_sub(t, t, c);
+    fe_add(d, t, t);
+
+    fe_add(e, a, a);
+    fe_add(e, e, a);
+    fe_sqr(f, e);
+
+    // Z3 = 2·Y·Z (antes de sobrescrever Y)
+    fe_mul(z, y, z);
+    fe_add(z, z, z);
+
+    // X3 = F - 2D
+    fe_sub(x, f, d);
+    fe_sub(x, x, d);
+
+    // Y3 = E·(D - X3) - 8C
+    fe_sub(t, d, x);
+    fe_mul(y, e, t);
+    fe_add(c, c, c);
+    fe_add(c, c, c);
+    fe_add(c, c, c);
+    fe_sub(y, y, c);
+}
+
+// madd-2007-bl: (X1,Y1,Z1) + (x2,y2,1). Retorna false no caso degenerado H == 0.
+__device__ bool jac_add_affine(u32 *x, u32 *y, u32 *z, const u32 *x2, const u32 *y2)
+{
+    u32 z1z1[8], u2[8], s2[8], h[8], hh[8], i4[8], j[8], r[8], v[8], t[8];
+
+    fe_sqr(z1z1, z);
+    fe_mul(u2, x2, z1z1);
+    fe_mul(s2, y2, z);
+    fe_mul(s2, s2, z1z1);
+
+    fe_sub(h, u2, x);
+    if (fe_is_zero(h)) return false;
+
+    fe_sqr(hh, h);
+    fe_add(i4, hh, hh);
+    fe_add(i4, i4, i4);
+    fe_mul(j, h, i4);
+
+    fe_sub(r, s2, y);
+    fe_add(r, r, r);
+
+    fe_mul(v, x, i4);
+
+    // X3 = r² - J - 2V
+    fe_sqr(t, r);
+    fe_sub(t, t, j);
+    fe_sub(t, t, v);
+    fe_sub(t, t, v);
+
+    // Y3 = r·(V - X3) - 2·Y1·J
+    fe_sub(v, v, t);
+    fe_mul(v, r, v);
+    fe_mul(j, y, j);
+    fe_add(j, j, j);
+    fe_sub(y, v, j);
+
+    // Z3 = (Z1 + H)² - Z1Z1 - HH
+    fe_add(z, z, h);
+    fe_sqr(z, z);
+    fe_sub(z, z, z1z1);
+    fe_sub(z, z, hh);
+
+    fe_copy(x, t);
+    return true;
+}
+
+extern "C" __global__ void secp256k1_pubkey(const u8 *secrets, u8 *out, u32 count)
+{
+    const u32 gid = blockIdx.x * blockDim.x + threadIdx.x;
+    if (gid >= count) return;
+
+    const u8 *k = secrets + (u64)gid * 32;
+    u8 *o = out + (u64)gid * 33;
+
+    // Escalar big-endian → limbs little-endian
+    u32 s[8];
+#pragma unroll
+    for (int i = 0; i < 8; i++) {
+        const u8 *b = k + (7 - i) * 4;
+        s[i] = ((u32)b[0] << 24) | ((u32)b[1] << 16) | ((u32)b[2] << 8) | b[3];
+    }
+
+    u32 gx[8], gy[8], x[8], y[8], z[8];
+    fe_copy(gx, SECP_GX);
+    fe_copy(gy, SECP_GY);
+
+    bool inf = true;
+    bool ok  = true;
+    for (int i = 255; i >= 0; i--) {
+        if (!inf) jac_double(x, y, z);
+        if ((s[i >> 5] >> (i & 31)) & 1) {
+            if (inf) {
+                fe_copy(x, gx);
+                fe_copy(y, gy);
+                for (int j = 0; j < 8; j++) z[j] = 0;
+                z[0] = 1;
+                inf = false;
+            } else if (!jac_add_affine(x, y, z, gx, gy)) {
+                ok = false;
+                break;
+            }
+        }
+    }
+
+    if (inf || !ok) {
+        o[0] = 0;
+        return;
+    }
+
+    // Afim: x = X/Z², y = Y/Z³
+    u32 zi[8], zi2[8];
+    fe_inv(zi, z);
+    fe_sqr(zi2, zi);
+    fe_mul(x, x, zi2);
+    fe_mul(zi2, zi2, zi);
+    fe_mul(y, y, zi2);
+
+    o[0] = 0x02 | (y[0] & 1);
+#pragma unroll
+    for (int i = 0; i < 8; i++) {
+        u32 w = x[7 - i];
+        o[1 + i * 4 + 0] = (u8)(w >> 24);
+        o[1 + i * 4 + 1] = (u8)(w >> 16);
+        o[1 + i * 4 + 2] = (u8)(w >> 8);
+        o[1 + i * 4 + 3] = (u8)w;
+    }
+}
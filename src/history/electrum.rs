@@ -0,0 +1,193 @@
+// ── Cliente Electrum (JSON-RPC por linha, TCP sem TLS) ───────────────────────
+//
+// blockchain.scripthash.get_history recebe o sha256 do scriptPubKey com os
+// bytes invertidos, em hex. As consultas de uma seed vao juntas (pipeline: N
+// linhas escritas, N respostas lidas, casadas pelo id; um id fora do lote ou
+// um objeto "error" encerram a consulta com erro). Cada thread do Rayon
+// pega uma conexao do pool; uma conexao com erro de E/S e descartada e a
+// consulta e refeita uma vez numa conexao nova.
+
+use super::json::{self, Value};
+use super::HistoryBackend;
+use anyhow::{Context, Result};
+use bitcoin::hashes::{sha256, Hash};
+use bitcoin::hex::DisplayHex;
+use bitcoin::ScriptBuf;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::ops::Range;
+use std::net::TcpStream;
+use std::sync::Mutex;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+const CLIENT_NAME: &str = "brute_force_mnemonics";
+const PROTOCOL_VERSION: &str = "1.4";
+
+struct Connection {
+    reader:  BufReader<TcpStream>,
+    writer:  TcpStream,
+    next_id: u64,
+}
+
+impl Connection {
+    fn open(server: &str) -> Result<Connection> {
+        let stream = TcpStream::connect(server)
+            .with_context(|| format!("Failed to connect to Electrum server {}", server))?;
+        log::debug!("connected to {}", server);
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        stream.set_write_timeout(Some(TIMEOUT))?;
+        let mut conn = Connection {
+            reader:  BufReader::new(stream.try_clone()?),
+            writer:  stream,
+            next_id: 0,
+        };
+        // O protocolo exige server.version antes de qualquer outra chamada
+        let params = format!("[\"{}\", \"{}\"]", CLIENT_NAME, PROTOCOL_VERSION);
+        conn.call(&[("server.version", params)])
+            .with_context(|| format!("{} did not answer server.version", server))?;
+        Ok(conn)
+    }
+
+    // Resultados, na ordem das chamadas
+    fn call(&mut self, calls: &[(&str, String)]) -> Result<Vec<Value>> {
+        let first = self.next_id;
+        let mut request = String::new();
+        for (method, params) in calls {
+            request += &format!("{{\"jsonrpc\": \"2.0\", \"id\": {}, \"method\": \"{}\", \"params\": {}}}\n",
+                self.next_id, method, params);
+            self.next_id += 1;
+        }
+        self.writer.write_all(request.as_bytes())?;
+        read_results(&mut self.reader, first..self.next_id)
+    }
+}
+
+// Uma resposta por id de `ids`, em qualquer ordem, devolvidas na ordem dos ids
+fn read_results(reader: &mut impl BufRead, ids: Range<u64>) -> Result<Vec<Value>> {
+    let mut results = HashMap::new();
+    while results.len() < ids.clone().count() {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            anyhow::bail!("Electrum server closed the connection");
+        }
+        let Some((id, result)) = reply(&line)? else { continue };
+        if !ids.contains(&id) || results.insert(id, result).is_some() {
+            anyhow::bail!("Electrum response with unexpected id {}", id);
+        }
+    }
+    Ok(ids.map(|id| results.remove(&id).expect("one result per id")).collect())
+}
+
+// {"jsonrpc": "2.0", "id": n, "result": ...} ou {..., "error": {"code": n, "message": "..."}};
+// None para uma notificacao (sem id)
+fn reply(line: &str) -> Result<Option<(u64, Value)>> {
+    let reply = json::parse(line).context("Malformed Electrum response")?;
+    match reply.get("error") {
+        None | Some(Value::Null) => {}
+        Some(error) => {
+            let message = error.get("message").and_then(Value::as_str).map_or_else(|| format!("{:?}", error), str::to_string);
+            anyhow::bail!("Electrum server error: {}", message);
+        }
+    }
+    let id = match reply.get("id") {
+        None | Some(Value::Null) if reply.get("method").is_some() => return Ok(None),
+        id => id.and_then(Value::as_u64).context("Electrum response without a numeric id")?,
+    };
+    let result = reply.get("result").context("Electrum response without result")?;
+    Ok(Some((id, result.clone())))
+}
+
+// Electrum identifica scripts pelo sha256 com bytes invertidos
+fn script_hash(script: &ScriptBuf) -> String {
+    let mut hash = sha256::Hash::hash(script.as_bytes()).to_byte_array();
+    hash.reverse();
+    hash.to_lower_hex_string()
+}
+
+/// Electrum/Fulcrum server reached over plain TCP (usually port 50001).
+pub struct ElectrumClient {
+    server: String,
+    pool:   Mutex<Vec<Connection>>,
+}
+
+impl ElectrumClient {
+    /// Connects once up front so that a wrong HOST:PORT fails before the search starts.
+    pub fn connect(server: &str) -> Result<ElectrumClient> {
+        let conn = Connection::open(server)?;
+        Ok(ElectrumClient { server: server.to_string(), pool: Mutex::new(vec![conn]) })
+    }
+
+    fn take(&self) -> Result<Connection> {
+        match self.pool.lock().expect("pool lock").pop() {
+            Some(conn) => Ok(conn),
+            None => Connection::open(&self.server),
+        }
+    }
+}
+
+impl HistoryBackend for ElectrumClient {
+    fn name(&self) -> String {
+        format!("electrum tcp://{}", self.server)
+    }
+
+    fn used(&self, scripts: &[ScriptBuf]) -> Result<Vec<bool>> {
+        let calls: Vec<(&str, String)> = scripts.iter()
+            .map(|s| ("blockchain.scripthash.get_history", format!("[\"{}\"]", script_hash(s))))
+            .collect();
+        let mut conn = self.take()?;
+        let results = match conn.call(&calls) {
+            Ok(r) => r,
+            // Conexao caida ou resposta truncada: uma nova tentativa
+            Err(e) => {
+                log::warn!("get_history failed ({:#}), retrying on a new connection", e);
+                conn = Connection::open(&self.server)?;
+                conn.call(&calls)?
+            }
+        };
+        self.pool.lock().expect("pool lock").push(conn);
+        // Historico vazio = []
+        let used = results.iter()
+            .map(|r| r.as_array().map(|txs| !txs.is_empty()).context("get_history did not return a list"))
+            .collect::<Result<Vec<bool>>>()?;
+        log::debug!("get_history: {} script(s), {} used", scripts.len(), used.iter().filter(|&&u| u).count());
+        Ok(used)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    // Respostas no formato do Fulcrum/ElectrumX (protocolo 1.4)
+    const HISTORY: &str = r#"{"jsonrpc": "2.0", "result": [{"tx_hash": "a8d0c0184dde994a09ec054286f1ce581bebf46446a512166eae7628734ea0a5", "height": 200004}, {"tx_hash": "6cce6d2b2c3a2c3d8c1cb0e3f4d0d2b74ba5e6f2b1f9d6f4a5a1c86d7d81b0a5", "height": 0, "fee": 2000}], "id": 7}"#;
+    const EMPTY: &str = r#"{"jsonrpc": "2.0", "result": [], "id": 8}"#;
+    const ERROR: &str = r#"{"jsonrpc": "2.0", "error": {"code": 1, "message": "invalid scripthash \"xyz\""}, "id": 9}"#;
+    const NOTIFICATION: &str = r#"{"jsonrpc": "2.0", "method": "blockchain.headers.subscribe", "params": [{"height": 200005, "hex": "00"}]}"#;
+
+    fn lines(replies: &[&str]) -> Cursor<Vec<u8>> {
+        Cursor::new(replies.iter().map(|r| format!("{}\n", r)).collect::<String>().into_bytes())
+    }
+
+    #[test]
+    fn matches_replies_by_id_and_skips_notifications() {
+        let results = read_results(&mut lines(&[EMPTY, NOTIFICATION, HISTORY]), 7..9).unwrap();
+        assert_eq!(results[0].as_array().map(<[Value]>::len), Some(2));
+        assert_eq!(results[1].as_array().map(<[Value]>::len), Some(0));
+    }
+
+    #[test]
+    fn an_error_reply_is_an_error() {
+        let error = read_results(&mut lines(&[HISTORY, ERROR]), 7..10).unwrap_err();
+        assert_eq!(error.to_string(), "Electrum server error: invalid scripthash \"xyz\"");
+    }
+
+    #[test]
+    fn refuses_unexpected_or_repeated_ids() {
+        assert!(read_results(&mut lines(&[HISTORY]), 8..9).is_err());
+        assert!(read_results(&mut lines(&[HISTORY, HISTORY]), 7..9).is_err());
+        assert!(read_results(&mut lines(&[HISTORY]), 7..9).is_err());
+        assert!(read_results(&mut lines(&["<html>"]), 7..8).is_err());
+    }
+}
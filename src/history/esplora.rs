@@ -0,0 +1,117 @@
+// ── Cliente Esplora (HTTP): GET /address/:addr ───────────────────────────────
+//
+// A API nao tem consulta em lote: os enderecos de uma seed sao consultados em
+// grupos de BATCH requisicoes simultaneas (conexoes keep-alive do Agent),
+// parando no primeiro grupo com um endereco usado. Erros de rede, 429 e 5xx
+// sao refeitos com espera crescente; depois de MAX_RETRIES a busca para.
+
+use super::json::{self, Value};
+use super::HistoryBackend;
+use crate::coin::Coin;
+use anyhow::{Context, Result};
+use bitcoin::ScriptBuf;
+use std::thread;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+// Requisicoes simultaneas por seed
+const BATCH: usize = 8;
+const MAX_RETRIES: u32 = 5;
+const FIRST_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Esplora REST API (Blockstream, mempool.space or a self-hosted electrs).
+pub struct EsploraClient {
+    base_url: String,
+    coin:     Coin,
+    agent:    ureq::Agent,
+}
+
+impl EsploraClient {
+    pub fn new(base_url: &str, coin: Coin) -> Result<EsploraClient> {
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .timeout_global(Some(TIMEOUT))
+            .http_status_as_error(false)
+            .max_idle_connections_per_host(BATCH * rayon::current_num_threads())
+            .build()
+            .into();
+        let client = EsploraClient {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            coin,
+            agent,
+        };
+        // Falha cedo com URL errada: /blocks/tip/height responde um numero
+        let tip = client.get("/blocks/tip/height")?;
+        if tip.trim().parse::<u64>().is_err() {
+            anyhow::bail!("{} does not look like an Esplora API (tip height: {:?})", client.base_url, tip);
+        }
+        Ok(client)
+    }
+
+    fn get(&self, path: &str) -> Result<String> {
+        let url = format!("{}{}", self.base_url, path);
+        let mut backoff = FIRST_BACKOFF;
+        for attempt in 0..=MAX_RETRIES {
+            if attempt > 0 {
+                thread::sleep(backoff);
+                backoff *= 2;
+            }
+            let mut response = match self.agent.get(&url).call() {
+                Ok(r) => r,
+                Err(e) if attempt < MAX_RETRIES => {
+                    log::warn!("GET {} failed ({}), retrying in {:?}", url, e, backoff);
+                    continue;
+                }
+                Err(e) => return Err(e).with_context(|| format!("GET {} failed", url)),
+            };
+            let status = response.status().as_u16();
+            log::debug!("GET {} -> {}", url, status);
+            if status == 429 || status >= 500 {
+                log::warn!("GET {} returned HTTP {}, retrying in {:?}", url, status, backoff);
+                continue;
+            }
+            let body = response.body_mut().read_to_string()
+                .with_context(|| format!("Failed to read the response of {}", url))?;
+            if status != 200 {
+                anyhow::bail!("GET {} returned HTTP {}: {}", url, status, body.trim());
+            }
+            return Ok(body);
+        }
+        anyhow::bail!("GET {} still rate-limited or failing after {} retries", url, MAX_RETRIES)
+    }
+
+    // chain_stats.tx_count + mempool_stats.tx_count > 0
+    fn address_used(&self, script: &ScriptBuf) -> Result<bool> {
+        let address = self.coin.encode_address(script)
+            .with_context(|| format!("Script {} has no {} address", script, self.coin.name()))?;
+        let body = self.get(&format!("/address/{}", address))?;
+        let reply = json::parse(&body).with_context(|| format!("Unexpected Esplora response for {}: {}", address, body.trim()))?;
+        let count = |stats: &str| reply.get(stats)
+            .and_then(|s| s.get("tx_count"))
+            .and_then(Value::as_u64)
+            .with_context(|| format!("Unexpected Esplora response for {}: {}", address, body.trim()));
+        Ok(count("chain_stats")? + count("mempool_stats")? > 0)
+    }
+}
+
+impl HistoryBackend for EsploraClient {
+    fn name(&self) -> String {
+        format!("esplora {}", self.base_url)
+    }
+
+    fn used(&self, scripts: &[ScriptBuf]) -> Result<Vec<bool>> {
+        let mut used = vec![false; scripts.len()];
+        for (b, batch) in scripts.chunks(BATCH).enumerate() {
+            let results: Vec<Result<bool>> = thread::scope(|s| {
+                let handles: Vec<_> = batch.iter().map(|script| s.spawn(|| self.address_used(script))).collect();
+                handles.into_iter().map(|h| h.join().expect("esplora request thread")).collect()
+            });
+            for (i, r) in results.into_iter().enumerate() {
+                used[b * BATCH + i] = r?;
+            }
+            if used.contains(&true) {
+                break;
+            }
+        }
+        Ok(used)
+    }
+}
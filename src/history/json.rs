@@ -0,0 +1,306 @@
+// ── JSON das respostas dos servidores ────────────────────────────────────────
+//
+// Leitor pequeno de JSON (RFC 8259) para as respostas de bitcoind, Electrum e
+// Esplora: o documento inteiro vira um Value, e os campos sao lidos pelo
+// caminho certo (result.unspents[].scriptPubKey), nunca por busca de texto.
+// Numeros ficam como o texto original; as_u64 converte os inteiros.
+
+use anyhow::Result;
+
+// Aninhamento maximo: um servidor hostil nao estoura a pilha
+const MAX_DEPTH: usize = 128;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Member `key` of an object; `None` for a missing key or a value that is not an object.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Number(n) => n.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+}
+
+/// Parses one JSON document; anything but whitespace after it is an error.
+pub fn parse(text: &str) -> Result<Value> {
+    let mut parser = Parser { bytes: text.as_bytes(), at: 0 };
+    let value = parser.value(0)?;
+    parser.skip_space();
+    if parser.at < parser.bytes.len() {
+        anyhow::bail!("Unexpected data after the JSON value at byte {}", parser.at);
+    }
+    Ok(value)
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    at:    usize,
+}
+
+impl Parser<'_> {
+    fn skip_space(&mut self) {
+        while self.bytes.get(self.at).is_some_and(|b| b" \t\r\n".contains(b)) {
+            self.at += 1;
+        }
+    }
+
+    fn fail<T>(&self, what: &str) -> Result<T> {
+        anyhow::bail!("Malformed JSON: {} at byte {}", what, self.at)
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<()> {
+        self.skip_space();
+        match self.bytes.get(self.at) {
+            Some(&b) if b == byte => { self.at += 1; Ok(()) }
+            _ => self.fail(&format!("expected '{}'", byte as char)),
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: Value) -> Result<Value> {
+        if self.bytes[self.at..].starts_with(word.as_bytes()) {
+            self.at += word.len();
+            Ok(value)
+        } else {
+            self.fail("unknown literal")
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value> {
+        if depth > MAX_DEPTH {
+            return self.fail("nesting too deep");
+        }
+        self.skip_space();
+        match self.bytes.get(self.at) {
+            Some(b'{') => self.object(depth),
+            Some(b'[') => self.array(depth),
+            Some(b'"') => Ok(Value::String(self.string()?)),
+            Some(b't') => self.literal("true", Value::Bool(true)),
+            Some(b'f') => self.literal("false", Value::Bool(false)),
+            Some(b'n') => self.literal("null", Value::Null),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => self.fail("unexpected character"),
+            None => self.fail("unexpected end"),
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> Result<Value> {
+        self.at += 1;
+        let mut members = Vec::new();
+        self.skip_space();
+        if self.bytes.get(self.at) == Some(&b'}') {
+            self.at += 1;
+            return Ok(Value::Object(members));
+        }
+        loop {
+            self.skip_space();
+            if self.bytes.get(self.at) != Some(&b'"') {
+                return self.fail("expected a key");
+            }
+            let key = self.string()?;
+            self.expect(b':')?;
+            members.push((key, self.value(depth + 1)?));
+            self.skip_space();
+            match self.bytes.get(self.at) {
+                Some(b',') => self.at += 1,
+                Some(b'}') => { self.at += 1; return Ok(Value::Object(members)); }
+                _ => return self.fail("expected ',' or '}'"),
+            }
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<Value> {
+        self.at += 1;
+        let mut items = Vec::new();
+        self.skip_space();
+        if self.bytes.get(self.at) == Some(&b']') {
+            self.at += 1;
+            return Ok(Value::Array(items));
+        }
+        loop {
+            items.push(self.value(depth + 1)?);
+            self.skip_space();
+            match self.bytes.get(self.at) {
+                Some(b',') => self.at += 1,
+                Some(b']') => { self.at += 1; return Ok(Value::Array(items)); }
+                _ => return self.fail("expected ',' or ']'"),
+            }
+        }
+    }
+
+    // -?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?
+    fn number(&mut self) -> Result<Value> {
+        let start = self.at;
+        let digits = |p: &mut Self| {
+            let from = p.at;
+            while p.bytes.get(p.at).is_some_and(u8::is_ascii_digit) {
+                p.at += 1;
+            }
+            p.at - from
+        };
+        if self.bytes[self.at] == b'-' {
+            self.at += 1;
+        }
+        match digits(self) {
+            0 => return self.fail("expected a digit"),
+            n if n > 1 && self.bytes[self.at - n] == b'0' => return self.fail("leading zero"),
+            _ => {}
+        }
+        if self.bytes.get(self.at) == Some(&b'.') {
+            self.at += 1;
+            if digits(self) == 0 {
+                return self.fail("expected a digit");
+            }
+        }
+        if matches!(self.bytes.get(self.at), Some(b'e' | b'E')) {
+            self.at += 1;
+            if matches!(self.bytes.get(self.at), Some(b'+' | b'-')) {
+                self.at += 1;
+            }
+            if digits(self) == 0 {
+                return self.fail("expected a digit");
+            }
+        }
+        // So ASCII entre start e at
+        Ok(Value::Number(String::from_utf8_lossy(&self.bytes[start..self.at]).into_owned()))
+    }
+
+    fn string(&mut self) -> Result<String> {
+        self.at += 1;
+        let mut out = Vec::new();
+        loop {
+            let Some(&b) = self.bytes.get(self.at) else { return self.fail("unterminated string") };
+            self.at += 1;
+            match b {
+                b'"' => break,
+                b'\\' => {
+                    let Some(&e) = self.bytes.get(self.at) else { return self.fail("unterminated string") };
+                    self.at += 1;
+                    let c = match e {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => self.escaped_char()?,
+                        _ => return self.fail("bad escape"),
+                    };
+                    out.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                }
+                0..=0x1f => return self.fail("control character in string"),
+                _ => out.push(b),
+            }
+        }
+        match String::from_utf8(out) {
+            Ok(s) => Ok(s),
+            Err(_) => self.fail("invalid UTF-8 in string"),
+        }
+    }
+
+    // \uXXXX, com os pares substitutos (\uD83D\uDE00) juntados
+    fn escaped_char(&mut self) -> Result<char> {
+        let high = self.hex4()?;
+        let code = match high {
+            0xd800..=0xdbff => {
+                if !self.bytes[self.at..].starts_with(b"\\u") {
+                    return self.fail("lone surrogate");
+                }
+                self.at += 2;
+                let low = self.hex4()?;
+                if !(0xdc00..=0xdfff).contains(&low) {
+                    return self.fail("lone surrogate");
+                }
+                0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+            }
+            0xdc00..=0xdfff => return self.fail("lone surrogate"),
+            code => code,
+        };
+        match char::from_u32(code) {
+            Some(c) => Ok(c),
+            None => self.fail("bad \\u escape"),
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32> {
+        let digits = self.bytes.get(self.at..self.at + 4).filter(|h| h.iter().all(u8::is_ascii_hexdigit));
+        match digits.and_then(|h| u32::from_str_radix(std::str::from_utf8(h).ok()?, 16).ok()) {
+            Some(code) => { self.at += 4; Ok(code) }
+            None => self.fail("bad \\u escape"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_nested_values() {
+        let value = parse(r#" {"a": [1, -2.5e3, true, null], "b": {"c": "x\"y\\z\u00e9\ud83d\ude00"}, "d": 0} "#).unwrap();
+        let a = value.get("a").and_then(Value::as_array).unwrap();
+        assert_eq!(a[0], Value::Number("1".to_string()));
+        assert_eq!(a[1], Value::Number("-2.5e3".to_string()));
+        assert_eq!(a[2].as_bool(), Some(true));
+        assert!(a[3].is_null());
+        assert_eq!(value.get("b").and_then(|b| b.get("c")).and_then(Value::as_str), Some("x\"y\\zé😀"));
+        assert_eq!(value.get("d"), Some(&Value::Number("0".to_string())));
+        assert_eq!(value.get("e"), None);
+    }
+
+    #[test]
+    fn keys_inside_strings_are_not_members() {
+        let value = parse(r#"{"note": "\"result\": [1]", "result": []}"#).unwrap();
+        assert_eq!(value.get("result").and_then(Value::as_array), Some(&[][..]));
+    }
+
+    #[test]
+    fn rejects_malformed_documents() {
+        for text in ["", "{", "[1,]", "{\"a\" 1}", "01", "1.", "\"\\ud800\"", "\"a\nb\"", "tru", "{} x", "[1] [2]"] {
+            assert!(parse(text).is_err(), "{:?}", text);
+        }
+        assert!(parse(&"[".repeat(MAX_DEPTH + 2)).is_err());
+    }
+}
@@ -0,0 +1,51 @@
+// ── Historico de enderecos: alvo "qualquer endereco ja usado" ────────────────
+//
+// Sem o endereco exato, a carteira ainda pode ser reconhecida por ter
+// movimentado fundos: para cada seed checksum-valida, os scripts derivados sao
+// consultados num servidor (Electrum/Fulcrum, ou uma API Esplora) e o primeiro
+// com historico conta como acerto. Cada consulta custa uma ida e volta na
+// rede — ordens de grandeza acima do PBKDF2 —, entao isso so vale para buscas
+// pequenas (--max-swaps, poucas lacunas).
+
+use crate::coin::Coin;
+use anyhow::Result;
+use bitcoin::ScriptBuf;
+use std::fmt;
+use std::sync::Arc;
+
+mod bitcoind;
+mod electrum;
+mod json;
+#[cfg(feature = "esplora")]
+mod esplora;
+
+pub use bitcoind::BitcoindClient;
+pub use electrum::ElectrumClient;
+
+/// Tells whether scripts have ever appeared in a transaction.
+pub trait HistoryBackend: Send + Sync {
+    /// Descricao do servidor (para o cabecalho de execucao).
+    fn name(&self) -> String;
+
+    /// Um bool por script, na mesma ordem: `true` se ja recebeu ou gastou algo.
+    fn used(&self, scripts: &[ScriptBuf]) -> Result<Vec<bool>>;
+}
+
+impl fmt::Debug for dyn HistoryBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name())
+    }
+}
+
+/// Esplora HTTP API (e.g. `https://blockstream.info/api`); addresses are encoded for `coin`.
+pub fn esplora(base_url: &str, coin: Coin) -> Result<Arc<dyn HistoryBackend>> {
+    #[cfg(feature = "esplora")]
+    {
+        Ok(Arc::new(esplora::EsploraClient::new(base_url, coin)?))
+    }
+    #[cfg(not(feature = "esplora"))]
+    {
+        let _ = (base_url, coin);
+        anyhow::bail!("Built without Esplora support. Rebuild with: cargo build --release --features esplora")
+    }
+}
@@ -0,0 +1,216 @@
+// ── Busca exportada para outras ferramentas (--export-job) ───────────────────
+//
+// As restricoes da busca no formato de outra ferramenta, para conferir um
+// resultado com ela:
+//   tokenlist   → tokenlist do btcrecover (o inverso de tokenlist.rs): uma
+//                 linha por palavra, posicoes conhecidas ancoradas (^N^), uma
+//                 lacuna vira as 2048 palavras ancoradas na posicao dela
+//   seedrecover → linha de comando do seedrecover.py que roda essa tokenlist
+// O que o formato nao expressa (--max-swaps, --before, fatias de indice) fica
+// de fora e volta em `dropped`: o trabalho exportado cobre um espaco maior,
+// que contem o desta busca.
+
+use crate::coin::Coin;
+use crate::mnemonic::{find_word, language_name, prefix_matches, WORD_COUNTS};
+use crate::permutation::WILDCARD;
+use crate::search::SearchConfig;
+use anyhow::{Context, Result};
+use bip39::Language;
+use bitcoin::bip32::ChildNumber;
+
+/// File name the `seedrecover` command expects the tokenlist in.
+pub const TOKENLIST_FILE: &str = "tokenlist.txt";
+
+/// Formats of `--export-job`.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobFormat {
+    /// btcrecover tokenlist (save it as tokenlist.txt for the seedrecover command)
+    Tokenlist,
+    /// seedrecover.py command line running tokenlist.txt
+    Seedrecover,
+}
+
+/// An exported job and the constraints the format could not express.
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub text:    String,
+    /// Options left out: the job covers more than the search (never less).
+    pub dropped: Vec<&'static str>,
+}
+
+/// Writes the constraints of `config` in `format`.
+///
+/// ```
+/// use brute_force_mnemonics::job::{export, JobFormat};
+/// use brute_force_mnemonics::{AddressType, SearchConfig};
+///
+/// let words = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon ? about";
+/// let mut config = SearchConfig::new(Vec::new(), words.split(' ').map(String::from).collect(),
+///     bip39::Language::English, AddressType::Bip84);
+/// config.fixed = vec![(11, "about".to_string())];
+/// let job = export(&config, JobFormat::Tokenlist)?;
+/// let lines: Vec<&str> = job.text.lines().collect();
+/// assert_eq!(lines.len(), 12);
+/// assert!(lines[0].starts_with("^11^abandon ^11^ability "));
+/// assert_eq!(lines[1], "^12^about");
+/// assert_eq!(lines[2], "abandon");
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn export(config: &SearchConfig, format: JobFormat) -> Result<Job> {
+    let n = config.words.len();
+    if !WORD_COUNTS.contains(&n) {
+        anyhow::bail!("Expected 12, 15, 18, 21 or 24 words, got {}", n);
+    }
+    let mut dropped = Vec::new();
+    if config.max_swaps.is_some() {
+        dropped.push("--max-swaps (every order is in the job)");
+    }
+    if !config.before.is_empty() {
+        dropped.push("--before (every order is in the job)");
+    }
+    if config.start_index > 0 || config.max_permutations.is_some() || config.shard.is_some() {
+        dropped.push("index slices (--start-index, --end-index, --max-permutations, --shard)");
+    }
+    let text = match format {
+        JobFormat::Tokenlist   => tokenlist(config)?,
+        JobFormat::Seedrecover => seedrecover(config, &mut dropped)?,
+    };
+    Ok(Job { text, dropped })
+}
+
+// Uma linha por palavra: posicoes conhecidas primeiro, em ordem, depois as permutadas
+fn tokenlist(config: &SearchConfig) -> Result<String> {
+    let wordlist = config.language.word_list();
+    let spelled  = |w: &str| -> Result<String> {
+        find_word(w, config.language)
+            .map(|i| wordlist[i as usize].to_string())
+            .with_context(|| format!("'{}' is not in the {} wordlist", w, language_name(config.language)))
+    };
+    let anchored = |pos: usize, words: &[String]| -> String {
+        words.iter().map(|w| format!("^{}^{}", pos + 1, w)).collect::<Vec<_>>().join(" ")
+    };
+
+    let mut lines = Vec::new();
+    if config.fuzzy > 0 {
+        lines.push(format!("#--typos {} --typos-replace --typos-insert --typos-delete", config.fuzzy));
+    }
+    // Palavras permutadas: as de `words` menos as que as posicoes conhecidas levam
+    let mut free: Vec<(usize, &String)> = config.words.iter().enumerate()
+        .filter(|(_, w)| w.as_str() != WILDCARD)
+        .collect();
+    let mut take = |word: &str| {
+        let at = free.iter().position(|(i, w)| w.as_str() == word && !config.alternatives.iter().any(|(a, _)| a == i));
+        at.map(|at| free.remove(at)).with_context(|| format!("'{}' is not one of the given words", word))
+    };
+    let mut placed: Vec<(usize, Vec<String>)> = Vec::new();
+    for (pos, word) in &config.fixed {
+        take(word)?;
+        placed.push((*pos, vec![spelled(word)?]));
+    }
+    for (pos, alts) in &config.choices {
+        take(alts.first().context("A position without candidates")?)?;
+        placed.push((*pos, alts.iter().map(|w| spelled(w)).collect::<Result<_>>()?));
+    }
+    let every: Vec<String> = wordlist.iter().map(|w| w.to_string()).collect();
+    placed.extend(config.wildcard_positions().into_iter().map(|pos| (pos, every.clone())));
+    placed.sort_by_key(|(pos, _)| *pos);
+    lines.extend(placed.iter().map(|(pos, words)| anchored(*pos, words)));
+
+    for (i, word) in free {
+        let tokens = match config.alternatives.iter().find(|(a, _)| *a == i) {
+            Some((_, alts)) => alts.iter().map(|w| spelled(w)).collect::<Result<Vec<_>>>()?,
+            None => match find_word(word, config.language).filter(|_| !config.prefix_mode) {
+                Some(idx) => vec![wordlist[idx as usize].to_string()],
+                // Abreviada: as palavras que comecam com ela; sem nenhuma, o typo do --fuzzy
+                None => match prefix_matches(word, wordlist, config.prefix_mode) {
+                    alts if !alts.is_empty() => alts.iter().map(|&i| wordlist[i as usize].to_string()).collect(),
+                    _ if config.fuzzy > 0    => vec![word.clone()],
+                    _ => anyhow::bail!("'{}' is not in the wordlist and no word starts with it", word),
+                },
+            },
+        };
+        lines.push(tokens.join(" "));
+    }
+    Ok(lines.into_iter().map(|l| l + "\n").collect())
+}
+
+// seedrecover.py com a tokenlist; alvos como --addrs ou a xpub da conta como --mpk
+fn seedrecover(config: &SearchConfig, dropped: &mut Vec<&'static str>) -> Result<String> {
+    if config.history.is_some() || config.multisig.is_some() || config.uncompressed {
+        anyhow::bail!("seedrecover needs target addresses or an account xpub (no history check, multisig or uncompressed keys)");
+    }
+    let wallet = match config.coin {
+        Coin::Bitcoin     => "bip39",
+        Coin::Litecoin    => "litecoin",
+        Coin::Dogecoin    => "dogecoin",
+        Coin::BitcoinCash => "bch",
+        Coin::Testnet | Coin::Regtest => anyhow::bail!("seedrecover checks mainnet addresses; {} searches are not exported", config.coin.name()),
+        Coin::Cardano     => anyhow::bail!("Cardano searches are not exported"),
+    };
+    let mut args = vec![
+        "python3 seedrecover.py".to_string(),
+        format!("--wallet-type {}", wallet),
+        format!("--tokenlist {}", TOKENLIST_FILE),
+        format!("--mnemonic-length {}", config.words.len()),
+        format!("--language {}", language_code(config.language)),
+    ];
+    match &config.xpub {
+        Some(x) if x.depth == 3 => args.push(format!("--mpk {}", x)),
+        Some(_) => anyhow::bail!("seedrecover takes an account-level xpub (depth 3), not a master key"),
+        None => {
+            let addrs = config.targets.iter()
+                .map(|t| config.coin.encode_address(t).context("A target script has no address form for seedrecover"))
+                .collect::<Result<Vec<_>>>()?;
+            if addrs.is_empty() {
+                anyhow::bail!("seedrecover needs a target address or an account xpub");
+            }
+            args.push(format!("--addrs {}", addrs.join(" ")));
+        }
+    }
+
+    // Caminho da cadeia (sem o indice do endereco) e quantos enderecos olhar nela
+    let mut chains: Vec<String> = Vec::new();
+    let mut limit = 0;
+    for path in config.derivation_paths()? {
+        let (parent, last) = path.as_ref().split_at(path.len() - 1);
+        match last[0] {
+            ChildNumber::Normal { index } => limit = limit.max(index + 1),
+            ChildNumber::Hardened { .. }  => {
+                dropped.push("hardened address indices (--path-variants)");
+                continue;
+            }
+        }
+        let parent = format!("m/{}", parent.iter().map(|c| c.to_string()).collect::<Vec<_>>().join("/"));
+        if !chains.contains(&parent) {
+            chains.push(parent);
+        }
+    }
+    if config.xpub.is_none() {
+        args.push(format!("--addr-limit {}", limit));
+        args.push(format!("--bip32-path {}", chains.iter().map(|c| format!("\"{}\"", c)).collect::<Vec<_>>().join(" ")));
+    }
+    if !config.passphrase.is_empty() {
+        args.push(format!("--passphrase-arg '{}'", config.passphrase.replace('\'', "'\\''")));
+    }
+    if !config.passphrases.is_empty() || config.passphrase_mask.is_some() {
+        dropped.push("passphrase lists and masks");
+    }
+    dropped.dedup();
+    Ok(args.join(" ") + "\n")
+}
+
+// Codigos de lingua do btcrecover
+fn language_code(language: Language) -> &'static str {
+    match language {
+        Language::English            => "en",
+        Language::Portuguese         => "pt",
+        Language::Spanish            => "es",
+        Language::French             => "fr",
+        Language::Italian            => "it",
+        Language::Czech              => "cs",
+        Language::Korean             => "ko",
+        Language::Japanese           => "ja",
+        Language::SimplifiedChinese  => "zh-hans",
+        Language::TraditionalChinese => "zh-hant",
+    }
+}
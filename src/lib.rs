@@ -0,0 +1,49 @@
+//! Recovery engine for BIP-39 mnemonics whose word order is unknown.
+//!
+//! ```no_run
+//! use brute_force_mnemonics::{search, AddressType, Coin, SearchConfig};
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let target = Coin::Bitcoin.parse_address("18vMtZKaAModY5k3dtQJyQ1VSdeorcomrz")?;
+//! let words = "pond bleak bicycle crystal pigeon boy liberty hungry ride emotion fluid ignore"
+//!     .split(' ').map(String::from).collect();
+//!
+//! let config = SearchConfig::new(vec![target], words, bip39::Language::English, AddressType::Bip44);
+//! if let Some(found) = search(config)?.next() {
+//!     println!("{}", found?.phrase);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod audit;
+pub mod bloom;
+pub mod bench;
+pub mod bip85;
+pub mod cardano;
+pub mod cashaddr;
+pub mod checkpoint;
+pub mod coin;
+pub mod derivation;
+pub mod descriptor;
+pub mod ed25519;
+pub mod funded;
+pub mod gpu;
+pub mod history;
+pub mod job;
+pub mod mnemonic;
+pub mod monero;
+pub mod multisig;
+pub mod passphrase;
+pub mod pbkdf2;
+pub mod permutation;
+pub mod scan;
+pub mod search;
+pub mod slip39;
+pub mod target;
+pub mod tokenlist;
+pub mod utxo;
+
+pub use coin::Coin;
+pub use derivation::AddressType;
+pub use search::{emit, phrase_search, search, Calibration, Emitted, Match, Search, SearchConfig};
@@ -0,0 +1,76 @@
+// ── Registro em arquivo (--log-file) ─────────────────────────────────────────
+//
+// Implementacao minima de `log::Log`: uma linha por registro, com horario UTC
+// (RFC 3339) e nivel, acrescentada ao arquivo (uma retomada continua o mesmo
+// registro). So entram registros deste crate — o ureq, por exemplo, tambem
+// usa `log` e encheria o arquivo com detalhes de HTTP.
+
+use anyhow::{Context, Result};
+use log::{LevelFilter, Log, Metadata, Record};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const CRATE: &str = "brute_force_mnemonics";
+
+struct FileLogger {
+    file: Mutex<File>,
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        let target = metadata.target();
+        target == CRATE || target.starts_with("brute_force_mnemonics::")
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        // "brute_force_mnemonics::history::electrum" → "history::electrum"
+        let module = record.target().strip_prefix(CRATE).unwrap_or("").trim_start_matches("::");
+        let module = if module.is_empty() { "main" } else { module };
+        let line = format!("{} {:<5} {}: {}\n", timestamp(), record.level(), module, record.args());
+        // Falha de escrita no registro nao deve derrubar a busca
+        let _ = self.file.lock().expect("log file lock").write_all(line.as_bytes());
+    }
+
+    fn flush(&self) {
+        let _ = self.file.lock().expect("log file lock").flush();
+    }
+}
+
+/// Appends timestamped records (debug and above) of this crate to `path`.
+pub fn init(path: &Path) -> Result<()> {
+    let file = OpenOptions::new().create(true).append(true).open(path)
+        .with_context(|| format!("Failed to open log file {}", path.display()))?;
+    log::set_boxed_logger(Box::new(FileLogger { file: Mutex::new(file) }))
+        .context("Logger already initialized")?;
+    log::set_max_level(LevelFilter::Debug);
+    Ok(())
+}
+
+// 2026-01-31T23:59:59.123Z
+fn timestamp() -> String {
+    let now  = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = now.as_secs();
+    let (y, m, d) = civil_from_days((secs / 86_400) as i64);
+    let s = secs % 86_400;
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        y, m, d, s / 3600, s % 3600 / 60, s % 60, now.subsec_millis())
+}
+
+// Dias desde 1970-01-01 → (ano, mes, dia) no calendario gregoriano (algoritmo de H. Hinnant)
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z   = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp  = (5 * doy + 2) / 153;
+    let d   = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m   = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    (yoe + era * 400 + i64::from(m <= 2), m, d)
+}
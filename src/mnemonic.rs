@@ -0,0 +1,369 @@
+// ── Palavras BIP-39: idiomas, wordlists e checksum ───────────────────────────
+
+use anyhow::{Context, Result};
+use bip39::{Language, Mnemonic};
+use bitcoin::hashes::{sha256, Hash};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Mnemonic lengths allowed by BIP-39.
+pub const WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+// ── Validacao rapida de checksum BIP-39 sem string nem parse ─────────────────
+//
+// Pipeline ANTIGO por permutacao:
+//   join(" ") → Mnemonic::parse (busca na wordlist + SHA256) → to_seed (PBKDF2) → BIP32
+//
+// Com esta funcao, PBKDF2 + BIP32 so executam quando o checksum passa —
+// o que ocorre em apenas 1/2^(n/3) das permutacoes: 1/16 com 12 palavras,
+// 1/256 com 24.
+//
+// Matematica BIP-39: cada palavra = 11 bits de indice na wordlist
+//   12 palavras → 132 bits = 128 bits entropia + 4 bits checksum
+//   15 palavras → 165 bits = 160 bits entropia + 5 bits checksum
+//   18 palavras → 198 bits = 192 bits entropia + 6 bits checksum
+//   21 palavras → 231 bits = 224 bits entropia + 7 bits checksum
+//   24 palavras → 264 bits = 256 bits entropia + 8 bits checksum
+//   Checksum = primeiros N bits de SHA256(entropy)
+pub fn checksum_valid(indices: &[u16]) -> bool {
+    let n = indices.len();
+    let entropy_bytes = n * 4 / 3; // 12→16, 15→20, ..., 24→32
+    let checksum_bits = n / 3;     // 12→4,  15→5,  ..., 24→8
+
+    let buf  = pack(indices);
+    let hash = sha256::Hash::hash(&buf[..entropy_bytes]);
+    let mask = 0xFFu8 << (8 - checksum_bits);
+    (buf[entropy_bytes] & mask) == (hash[0] & mask)
+}
+
+// ── Ultima palavra (a que carrega o checksum) ────────────────────────────────
+//
+// A ultima palavra tem 11 - n/3 bits de entropia e os n/3 bits de checksum:
+// dadas as outras, so 2^(11 - n/3) fecham a frase (128 com 12 palavras, 8 com
+// 24), calculadas direto, sem testar as 2048.
+
+/// Every last word (wordlist index) that completes `indices`, the first 11, 14, 17, 20 or
+/// 23 words of a mnemonic in order, to a valid checksum.
+///
+/// ```
+/// use bip39::Language;
+/// use brute_force_mnemonics::mnemonic::last_word_candidates;
+///
+/// let first = vec![0u16; 11]; // abandon x 11
+/// let last = last_word_candidates(&first);
+/// assert_eq!(last.len(), 128);
+/// assert!(last.contains(&Language::English.find_word("about").unwrap()));
+/// ```
+pub fn last_word_candidates(indices: &[u16]) -> Vec<u16> {
+    let n = indices.len() + 1;
+    debug_assert!(WORD_COUNTS.contains(&n), "espera 11, 14, 17, 20 ou 23 palavras");
+    let checksum_bits = n / 3;
+    let mut words = indices.to_vec();
+    words.push(0);
+    (0..1u16 << (11 - checksum_bits))
+        .map(|bits| {
+            words[n - 1] = bits << checksum_bits;
+            set_checksum(&mut words);
+            words[n - 1]
+        })
+        .collect()
+}
+
+/// Overwrites the checksum bits (the low n/3 bits of the last word) of `indices` with
+/// the checksum of the entropy the words carry, making the mnemonic valid.
+pub fn set_checksum(indices: &mut [u16]) {
+    let n = indices.len();
+    let checksum_bits = n / 3;
+    let hash = sha256::Hash::hash(&entropy(indices));
+    let last = &mut indices[n - 1];
+    *last = (*last & !((1u16 << checksum_bits) - 1)) | (hash[0] >> (8 - checksum_bits)) as u16;
+}
+
+/// Entropy encoded by a mnemonic (word indices), without the checksum bits.
+pub fn entropy(indices: &[u16]) -> Vec<u8> {
+    pack(indices)[..indices.len() * 4 / 3].to_vec()
+}
+
+// ── Entropia parcial (--entropy-pattern) ─────────────────────────────────────
+//
+// A entropia em hex com "?" nos nibbles ilegiveis: cada palavra cobre 11
+// bits, e as que cruzam um nibble desconhecido valem qualquer palavra com os
+// bits conhecidos no lugar. Os bits de checksum da ultima ficam livres (o
+// filtro de checksum da busca escolhe o certo).
+
+/// Wordlist indices each position of the mnemonic can take, given its entropy as hex with
+/// `?` for every unknown nibble (32 to 64 digits; spaces and a `0x` prefix are ignored).
+/// Known positions have a single candidate; the last word lists every checksum value.
+///
+/// ```
+/// use brute_force_mnemonics::mnemonic::entropy_pattern_words;
+///
+/// let words = entropy_pattern_words("0000000000000000000000000000000?").unwrap();
+/// assert_eq!(words.len(), 12);
+/// assert_eq!(words[0], vec![0]);     // abandon
+/// assert_eq!(words[11].len(), 256);  // 4 unknown bits x 16 checksums
+/// assert!(words[11].contains(&3));   // about
+/// ```
+pub fn entropy_pattern_words(pattern: &str) -> Result<Vec<Vec<u16>>> {
+    let digits: Vec<char> = pattern.trim().trim_start_matches("0x").chars().filter(|c| !c.is_whitespace()).collect();
+    if ![32, 40, 48, 56, 64].contains(&digits.len()) {
+        anyhow::bail!("Entropy pattern has {} hex digits; expected 32, 40, 48, 56 or 64 (128 to 256 bits)", digits.len());
+    }
+    // (valor, mascara dos bits conhecidos), um bit por posicao
+    let mut bits: Vec<(bool, bool)> = Vec::with_capacity(digits.len() * 4 + 8);
+    for c in &digits {
+        let nibble = match c {
+            '?' => None,
+            _   => Some(c.to_digit(16).with_context(|| format!("'{}' is not a hex digit or ?", c))?),
+        };
+        for shift in (0..4).rev() {
+            bits.push(nibble.map_or((false, false), |v| (v >> shift & 1 == 1, true)));
+        }
+    }
+    let n = bits.len() * 3 / 32;
+    bits.resize(n * 11, (false, false));
+
+    Ok(bits.chunks(11)
+        .map(|word| {
+            let (value, mask) = word.iter().fold((0u16, 0u16), |(v, m), &(bit, known)| {
+                ((v << 1) | bit as u16, (m << 1) | known as u16)
+            });
+            (0..2048u16).filter(|w| w & mask == value).collect()
+        })
+        .collect())
+}
+
+// Empacota os indices de 11 bits em bytes com um acumulador de bits
+// (um shift por palavra em vez de um teste por bit)
+fn pack(indices: &[u16]) -> [u8; 33] {
+    let mut buf = [0u8; 33]; // 32 bytes de entropia + 1 byte extra para os bits de checksum
+    let mut acc: u32 = 0;
+    let mut acc_bits = 0u32;
+    let mut out = 0usize;
+    for &idx in indices {
+        acc = (acc << 11) | (idx as u32 & 0x7FF);
+        acc_bits += 11;
+        while acc_bits >= 8 {
+            acc_bits -= 8;
+            buf[out] = (acc >> acc_bits) as u8;
+            out += 1;
+        }
+    }
+    if acc_bits > 0 {
+        buf[out] = (acc << (8 - acc_bits)) as u8;
+    }
+    buf
+}
+
+// ── Normalizacao Unicode (NFKD, exigida pelo BIP-39) ─────────────────────────
+//
+// As wordlists ja estao em NFKD; o que vem do teclado costuma estar em NFC
+// ("á" num so codigo), entao palavras e passphrases sao normalizadas antes de
+// qualquer comparacao. Em japones a frase se escreve com U+3000 (espaco
+// ideografico), que o NFKD transforma em espaco ASCII: a semente e a mesma.
+
+/// Text in NFKD, as BIP-39 compares words and stretches passphrases.
+///
+/// Official Japanese vectors (`test_JP_BIP39.json`): phrases joined by ideographic spaces,
+/// passphrase in NFC; the words are looked up, the entropy rebuilt and the seed stretched the
+/// way the search does it.
+///
+/// ```
+/// use bip39::Language;
+/// use bitcoin::hex::{DisplayHex, FromHex};
+/// use brute_force_mnemonics::mnemonic::{build_phrase, checksum_valid, display_phrase, entropy, normalize};
+/// use brute_force_mnemonics::pbkdf2;
+///
+/// let passphrase = "㍍ガバヴァぱばぐゞちぢ十人十色";
+/// // (entropy, mnemonic, seed)
+/// let vectors = [
+///     ("00000000000000000000000000000000",
+///      "あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あおぞら",
+///      "a262d6fb6122ecf45be09c50492b31f92e9beb7d9a845987a02cefda57a15f9c467a17872029a9e92299b5cbdf306e3a0ee620245cbd508959b6cb7ca637bd55"),
+///     ("7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+///      "そつう　れきだい　ほんやく　わかす　りくつ　ばいか　ろせん　やちん　そつう　れきだい　ほんやく　わかめ",
+///      "aee025cbe6ca256862f889e48110a6a382365142f7d16f2b9545285b3af64e542143a577e9c144e101a6bdca18f8d97ec3366ebf5b088b1c1af9bc31346e60d9"),
+///     ("77c2b00716cec7213839159e404db50d",
+///      "せまい　うちがわ　あずき　かろう　めずらしい　だんち　ますく　おさめる　ていぼう　あたる　すあな　えしゃく",
+///      "344cef9efc37d0cb36d89def03d09144dd51167923487eec42c487f7428908546fa31a3c26b7391a2b3afe7db81b9f8c5007336b58e269ea0bd10749a87e0193"),
+///     ("eaebabb2383351fd31d703840b32e9e2",
+///      "めいえん　さのう　めだつ　すてる　きぬごし　ろんぱ　はんこ　まける　たいおう　さかいし　ねんいり　はぶらし",
+///      "06e1d5289a97bcc95cb4a6360719131a786aba057d8efd603a547bd254261c2a97fcd3e8a4e766d5416437e956b388336d36c7ad2dba4ee6796f0249b10ee961"),
+///     ("b63a9c59a6e641f288ebc103017f1da9f8290b3da6bdef7b",
+///      "ぬすむ　ふっかつ　うどん　こうりつ　しつじ　りょうり　おたがい　せもたれ　あつめる　いちりゅう　はんしゃ　ごますり　そんけい　たいちょう　らしんばん　ぶんせき　やすみ　ほいく",
+///      "b14e7d35904cb8569af0d6a016cee7066335a21c1c67891b01b83033cadb3e8a034a726e3909139ecd8b2eb9e9b05245684558f329b38480e262c1d6bc20ecc4"),
+///     ("3e141609b97933b66a060dcddc71fad1d91677db872031e85f4c015c5e7e8982",
+///      "くのう　てぬぐい　そんかい　すろっと　ちきゅう　ほあん　とさか　はくしゅ　ひびく　みえる　そざい　てんすう　たんぴん　くしょう　すいようび　みけん　きさらぎ　げざん　ふくざつ　あつかう　はやい　くろう　おやゆび　こすう",
+///      "32e78dce2aff5db25aa7a4a32b493b5d10b4089923f3320c8b287a77e512455443298351beb3f7eb2390c4662a2e566eec5217e1a37467af43b46668d515e41b"),
+///     ("2c85efc7f24ee4573d2b81a6ec66cee209b2dcbd09d8eddc51e0215b0b68e416",
+///      "かほご　きうい　ゆたか　みすえる　もらう　がっこう　よそう　ずっと　ときどき　したうけ　にんか　はっこう　つみき　すうじつ　よけい　くげん　もくてき　まわり　せめる　げざい　にげる　にんたい　たんそく　ほそく",
+///      "713b7e70c9fbc18c831bfd1f03302422822c3727a93a5efb9659bec6ad8d6f2c1b5c8ed8b0b77775feaf606e9d1cc0a84ac416a85514ad59f5541ff5e0382481"),
+///     ("15da872c95a13dd738fbf50e427583ad61f18fd99f628c417a61cf8343c90419",
+///      "うちゅう　ふそく　ひしょ　がちょう　うけもつ　めいそう　みかん　そざい　いばる　うけとる　さんま　さこつ　おうさま　ぱんつ　しひょう　めした　たはつ　いちぶ　つうじょう　てさぎょう　きつね　みすえる　いりぐち　かめれおん",
+///      "346b7321d8c04f6f37b49fdf062a2fddc8e1bf8f1d33171b65074531ec546d1d3469974beccb1a09263440fc92e1042580a557fdce314e27ee4eabb25fa5e5fe"),
+/// ];
+/// for (hex, sentence, seed) in vectors {
+///     // U+3000 between the words: split_whitespace already splits on it
+///     let indices: Vec<u16> = sentence.split_whitespace()
+///         .map(|w| Language::Japanese.find_word(&normalize(w)).unwrap())
+///         .collect();
+///     assert!(checksum_valid(&indices));
+///     assert_eq!(entropy(&indices), Vec::<u8>::from_hex(hex)?);
+///
+///     let phrase = build_phrase(&indices, Language::Japanese.word_list());
+///     let shown = display_phrase(&phrase, Language::Japanese);
+///     assert_eq!(shown.split('\u{3000}').count(), indices.len());
+///     assert_eq!(normalize(&shown), normalize(sentence));
+///     assert_eq!(pbkdf2::seed(&phrase, &normalize(passphrase)).to_lower_hex_string(), seed);
+/// }
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn normalize(text: &str) -> String {
+    let mut cow = Cow::from(text);
+    Mnemonic::normalize_utf8_cow(&mut cow);
+    cow.into_owned()
+}
+
+/// Phrase as the language writes it: Japanese words are separated by U+3000.
+pub fn display_phrase(phrase: &str, language: Language) -> String {
+    match language {
+        Language::Japanese => phrase.replace(' ', "\u{3000}"),
+        _                  => phrase.to_string(),
+    }
+}
+
+// ── Palavras sem acento ("album" → "álbum") ──────────────────────────────────
+//
+// Quem anotou a frase em espanhol ou frances muitas vezes deixou os acentos
+// de fora. As wordlists foram feitas para que nenhuma palavra dependa so do
+// acento, entao a forma sem diacriticos ainda aponta para uma unica entrada.
+// So as marcas combinantes latinas (U+0300..U+036F) saem: o dakuten japones
+// (U+3099) e os jamo coreanos mudam a palavra e continuam.
+
+/// `text` in NFKD without Latin diacritics.
+pub fn fold_accents(text: &str) -> String {
+    normalize(text).chars().filter(|c| !('\u{300}'..='\u{36f}').contains(c)).collect()
+}
+
+/// Index of `word` in `language`, falling back to an accent-insensitive match when
+/// the exact spelling is not in the list. `None` if absent or ambiguous.
+pub fn find_word(word: &str, language: Language) -> Option<u16> {
+    let word = normalize(word);
+    language.find_word(&word).or_else(|| {
+        let folded = fold_accents(&word);
+        let mut hits = language.word_list().iter().enumerate()
+            .filter(|(_, w)| fold_accents(w) == folded);
+        match (hits.next(), hits.next()) {
+            (Some((i, _)), None) => Some(i as u16),
+            _                    => None,
+        }
+    })
+}
+
+// ── Tabela palavra → indice (uma vez por execucao) ───────────────────────────
+pub fn word_index_table(wordlist: &[&'static str]) -> HashMap<&'static str, u16> {
+    wordlist.iter().enumerate().map(|(i, &w)| (w, i as u16)).collect()
+}
+
+pub fn parse_language(lang: &str) -> Result<Language> {
+    match lang.to_lowercase().as_str() {
+        "english"              => Ok(Language::English),
+        "portuguese"           => Ok(Language::Portuguese),
+        "spanish"              => Ok(Language::Spanish),
+        "french"               => Ok(Language::French),
+        "italian"              => Ok(Language::Italian),
+        "czech"                => Ok(Language::Czech),
+        "korean"               => Ok(Language::Korean),
+        "japanese"             => Ok(Language::Japanese),
+        "chinese-simplified"   => Ok(Language::SimplifiedChinese),
+        "chinese-traditional"  => Ok(Language::TraditionalChinese),
+        _ => anyhow::bail!("Unknown language: {}", lang),
+    }
+}
+
+pub fn language_name(lang: Language) -> &'static str {
+    match lang {
+        Language::English            => "english",
+        Language::Portuguese         => "portuguese",
+        Language::Spanish            => "spanish",
+        Language::French             => "french",
+        Language::Italian            => "italian",
+        Language::Czech              => "czech",
+        Language::Korean             => "korean",
+        Language::Japanese           => "japanese",
+        Language::SimplifiedChinese  => "chinese-simplified",
+        Language::TraditionalChinese => "chinese-traditional",
+    }
+}
+
+/// Every BIP-39 wordlist, in detection order.
+pub const LANGUAGES: [Language; 10] = [
+    Language::English, Language::Portuguese, Language::Spanish,
+    Language::French,  Language::Italian,    Language::Czech,
+    Language::Korean,  Language::Japanese,   Language::SimplifiedChinese,
+    Language::TraditionalChinese,
+];
+
+/// Wordlist with the most of `words`; `None` if no word is in any list.
+pub fn detect_language(words: &[String]) -> Option<Language> {
+    let (best, count) = LANGUAGES.iter().copied()
+        .map(|lang| (lang, words.iter().filter(|w| find_word(w, lang).is_some()).count()))
+        .max_by_key(|&(_, count)| count)?;
+    (count > 0).then_some(best)
+}
+
+/// Wordlists that contain `word`, accents aside (English and French, for instance, share some words).
+pub fn word_languages(word: &str) -> Vec<Language> {
+    LANGUAGES.iter().copied().filter(|&lang| find_word(word, lang).is_some()).collect()
+}
+
+// ── Frase a partir dos indices (so para candidatos checksum-validos) ─────────
+pub fn build_phrase(perm: &[u16], wordlist: &[&str]) -> String {
+    let mut phrase = String::with_capacity(perm.len() * 9);
+    for (i, &w) in perm.iter().enumerate() {
+        if i > 0 { phrase.push(' '); }
+        phrase.push_str(wordlist[w as usize]);
+    }
+    phrase
+}
+
+// ── Correcao de palavras com erro de grafia (--fuzzy) ────────────────────────
+// Distancia de Levenshtein por caractere (as wordlists nao-latinas sao UTF-8)
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diag = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let next = (row[j + 1] + 1).min(row[j] + 1).min(diag + usize::from(ca != cb));
+            diag = row[j + 1];
+            row[j + 1] = next;
+        }
+    }
+    row[b.len()]
+}
+
+/// Indices das palavras da wordlist a no maximo `max_distance` edicoes de `word`,
+/// das mais proximas para as mais distantes (acentos nao contam como edicao).
+pub fn fuzzy_matches(word: &str, wordlist: &[&str], max_distance: usize) -> Vec<u16> {
+    let word = fold_accents(word);
+    let mut found: Vec<(usize, u16)> = wordlist.iter().enumerate()
+        .map(|(i, w)| (edit_distance(&word, &fold_accents(w)), i as u16))
+        .filter(|&(d, _)| d <= max_distance)
+        .collect();
+    found.sort_unstable();
+    found.into_iter().map(|(_, i)| i).collect()
+}
+
+// ── Prefixos (as 4 primeiras letras identificam a palavra) ───────────────────
+/// Indices das palavras que comecam com `word`; com `first_four`, so as 4
+/// primeiras letras de `word` contam (palavras anotadas abreviadas). Acentos sao ignorados.
+pub fn prefix_matches(word: &str, wordlist: &[&str], first_four: bool) -> Vec<u16> {
+    let word = fold_accents(word);
+    let prefix: String = if first_four { word.chars().take(4).collect() } else { word };
+    wordlist.iter().enumerate()
+        .filter(|(_, w)| fold_accents(w).starts_with(&prefix))
+        .map(|(i, _)| i as u16)
+        .collect()
+}
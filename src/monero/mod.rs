@@ -0,0 +1,177 @@
+// ── Monero: seed de 25 palavras ──────────────────────────────────────────────
+//
+// 24 palavras de dados (cada 3 palavras = 32 bits, base 1626) + 1 de checksum:
+// o crc32 dos 3 primeiros caracteres das 24 palavras, modulo 24, aponta qual
+// delas se repete no fim. A seed decodificada (32 bytes) reduzida mod l e a
+// chave privada de gasto; a de visualizacao e keccak256(gasto) mod l.
+//
+// Endereco padrao: base58 em blocos de 8 bytes de
+//   rede (18) | gasto publico | visualizacao publica | keccak256[..4]
+// A busca compara so a chave publica de gasto: a de visualizacao vem dela.
+
+pub mod keccak;
+
+use crate::ed25519;
+use crate::permutation::{order_at_index, order_count};
+use anyhow::{Context, Result};
+use rayon::prelude::*;
+use std::sync::OnceLock;
+
+const WORDLIST: &str = include_str!("english.txt");
+
+/// Words in a Monero seed (24 data words + 1 checksum word).
+pub const SEED_WORDS: usize = 25;
+const DATA_WORDS: usize = 24;
+// Palavras sao unicas pelos 3 primeiros caracteres
+const PREFIX_LEN: usize = 3;
+
+// Prefixos de rede mainnet: padrao, integrado (com payment id) e subendereco
+const NETWORK_STANDARD: u8 = 18;
+const NETWORK_INTEGRATED: u8 = 19;
+const NETWORK_SUBADDRESS: u8 = 42;
+
+/// Monero English wordlist (1626 words, sorted).
+pub fn wordlist() -> &'static [&'static str] {
+    static LIST: OnceLock<Vec<&'static str>> = OnceLock::new();
+    LIST.get_or_init(|| WORDLIST.split_whitespace().collect())
+}
+
+pub fn word_index(word: &str) -> Option<u16> {
+    wordlist().binary_search(&word).ok().map(|i| i as u16)
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    static TABLE: OnceLock<[u32; 256]> = OnceLock::new();
+    let table = TABLE.get_or_init(|| {
+        std::array::from_fn(|i| (0..8).fold(i as u32, |c, _| if c & 1 != 0 { 0xedb8_8320 ^ (c >> 1) } else { c >> 1 }))
+    });
+    !data.iter().fold(!0u32, |c, &b| table[((c ^ b as u32) & 0xff) as usize] ^ (c >> 8))
+}
+
+/// Whether the last of 25 words (indices) is the checksum word of the first 24.
+pub fn checksum_valid(indices: &[u16]) -> bool {
+    let words = wordlist();
+    let mut prefixes = [0u8; DATA_WORDS * PREFIX_LEN];
+    let mut len = 0;
+    for &i in &indices[..DATA_WORDS] {
+        let word = words[i as usize].as_bytes();
+        let prefix = &word[..word.len().min(PREFIX_LEN)];
+        prefixes[len..len + prefix.len()].copy_from_slice(prefix);
+        len += prefix.len();
+    }
+    // Indices iguais ⇔ mesmo prefixo de 3 letras
+    indices[DATA_WORDS] == indices[crc32(&prefixes[..len]) as usize % DATA_WORDS]
+}
+
+/// Private spend key of a seed (word indices, checksum word ignored); `None` if a
+/// 3-word group does not fit in 32 bits.
+pub fn spend_key(indices: &[u16]) -> Option<[u8; 32]> {
+    let n = wordlist().len() as u64;
+    let mut seed = [0u8; 32];
+    for (chunk, group) in seed.chunks_mut(4).zip(indices[..DATA_WORDS].chunks(3)) {
+        let [w1, w2, w3] = [group[0], group[1], group[2]].map(|w| w as u64);
+        let value = w1 + n * ((n - w1 + w2) % n) + n * n * ((n - w2 + w3) % n);
+        let value = u32::try_from(value).ok()?;
+        chunk.copy_from_slice(&value.to_le_bytes());
+    }
+    Some(ed25519::reduce32(&seed))
+}
+
+fn view_key(spend: &[u8; 32]) -> [u8; 32] {
+    ed25519::reduce32(&keccak::keccak256(spend))
+}
+
+// ── Base58 do Monero: blocos de 8 bytes → 11 caracteres ──────────────────────
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+// Caracteres de um bloco de 0..=8 bytes
+const ENCODED_BLOCK: [usize; 9] = [0, 2, 3, 5, 6, 7, 9, 10, 11];
+
+fn base58_encode(data: &[u8]) -> String {
+    let mut out = String::new();
+    for block in data.chunks(8) {
+        let mut n = block.iter().fold(0u64, |acc, &b| (acc << 8) | b as u64);
+        let mut chars = vec![ALPHABET[0]; ENCODED_BLOCK[block.len()]];
+        for c in chars.iter_mut().rev() {
+            *c = ALPHABET[(n % 58) as usize];
+            n /= 58;
+        }
+        out.extend(chars.into_iter().map(char::from));
+    }
+    out
+}
+
+fn base58_decode(s: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    for block in s.as_bytes().chunks(11) {
+        let size = ENCODED_BLOCK.iter().position(|&l| l == block.len())?;
+        let mut n: u128 = 0;
+        for c in block {
+            n = n * 58 + ALPHABET.iter().position(|a| a == c)? as u128;
+        }
+        if n >> (8 * size) != 0 {
+            return None;
+        }
+        out.extend_from_slice(&n.to_be_bytes()[16 - size..]);
+    }
+    Some(out)
+}
+
+/// Standard mainnet address of a private spend key.
+pub fn address(spend: &[u8; 32]) -> String {
+    let mut data = vec![NETWORK_STANDARD];
+    data.extend_from_slice(&ed25519::public_key(spend));
+    data.extend_from_slice(&ed25519::public_key(&view_key(spend)));
+    let checksum = keccak::keccak256(&data);
+    data.extend_from_slice(&checksum[..4]);
+    base58_encode(&data)
+}
+
+/// Public spend key of a standard (4...) or integrated mainnet address.
+pub fn parse_address(s: &str) -> Result<[u8; 32]> {
+    let data = base58_decode(s).with_context(|| format!("Invalid Monero address: {}", s))?;
+    let (body, checksum) = data.split_at(data.len().saturating_sub(4));
+    if checksum.len() != 4 || keccak::keccak256(body)[..4] != *checksum {
+        anyhow::bail!("Invalid Monero address checksum: {}", s);
+    }
+    match (data[0], data.len()) {
+        (NETWORK_STANDARD, 69) | (NETWORK_INTEGRATED, 77) => {
+            Ok(data[1..33].try_into().expect("32-byte key"))
+        }
+        (NETWORK_SUBADDRESS, _) => {
+            anyhow::bail!("{} is a subaddress; use the wallet's primary address (4...)", s)
+        }
+        _ => anyhow::bail!("{} is not a mainnet Monero address", s),
+    }
+}
+
+/// A seed order whose public spend key matches the target.
+#[derive(Debug, Clone)]
+pub struct MoneroMatch {
+    pub seed:    String,
+    pub index:   u64,
+    pub address: String,
+}
+
+/// First order of the 25 `words` (indices, given order first) whose public spend key is `target`.
+pub fn search(
+    words: &[u16],
+    target: &[u8; 32],
+    max_swaps: Option<usize>,
+    max_permutations: Option<u64>,
+) -> Option<MoneroMatch> {
+    let total = order_count(words.len(), max_swaps).min(u64::MAX as u128) as u64;
+    let end   = max_permutations.unwrap_or(u64::MAX).min(total);
+    (0..end).into_par_iter().find_map_first(|i| {
+        let perm = order_at_index(words, max_swaps, i as u128);
+        // ~1/24 passa na palavra de checksum antes do ed25519
+        if !checksum_valid(&perm) {
+            return None;
+        }
+        let spend = spend_key(&perm)?;
+        (ed25519::public_key(&spend) == *target).then(|| MoneroMatch {
+            seed:    perm.iter().map(|&w| wordlist()[w as usize]).collect::<Vec<_>>().join(" "),
+            index:   i,
+            address: address(&spend),
+        })
+    })
+}
@@ -0,0 +1,225 @@
+// ── Multisig: sortedmulti com as xpubs dos outros cosignatarios ──────────────
+//
+// O endereco multisig sai de M-de-N chaves: a da frase procurada e as dos
+// outros cosignatarios, todas no mesmo /cadeia/indice abaixo da conta de cada
+// um. As chaves sao ordenadas (BIP67, como o sortedmulti dos descriptors) e o
+// script e embrulhado como P2SH, P2WSH ou P2SH-P2WSH. Os filhos das xpubs dos
+// cosignatarios nao dependem do candidato: sao derivados uma vez por caminho.
+// Contas padrao: BIP48 (m/48'/coin'/0'/1' ou 2') e BIP45 (m/45') no P2SH.
+//
+// Com --witness-script o script vem de um modelo no formato dos descriptors,
+// com @ no lugar da chave da frase: pk(@) (uma chave so, <chave> CHECKSIG),
+// sortedmulti(M,...) ou multi(M,...), este com as chaves na ordem dada.
+
+use crate::derivation::parse_extended_pubkey;
+use anyhow::{Context, Result};
+use bitcoin::base58;
+use bitcoin::bip32::{DerivationPath, Xpub};
+use bitcoin::opcodes::all::{OP_CHECKMULTISIG, OP_CHECKSIG};
+use bitcoin::script::Builder;
+use bitcoin::secp256k1::{PublicKey, Secp256k1};
+use bitcoin::ScriptBuf;
+
+/// Most keys a standard P2SH multisig can hold (520-byte redeem script).
+pub const MAX_KEYS: usize = 15;
+
+// Versoes SLIP-132 das xpubs de conta multisig (Ypub: P2SH-P2WSH, Zpub: P2WSH)
+const XPUB_VERSION: [u8; 4] = [0x04, 0x88, 0xB2, 0x1E];
+const YPUB_MULTISIG: [u8; 4] = [0x02, 0x95, 0xB4, 0x3F];
+const ZPUB_MULTISIG: [u8; 4] = [0x02, 0xAA, 0x7E, 0xD3];
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultisigType {
+    /// Legacy P2SH (3..., BIP45 m/45')
+    P2sh,
+    /// Nested SegWit P2SH-P2WSH (3..., BIP48 m/48'/coin'/0'/1')
+    P2shP2wsh,
+    /// Native SegWit P2WSH (bc1q..., BIP48 m/48'/coin'/0'/2')
+    P2wsh,
+}
+
+impl MultisigType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MultisigType::P2sh      => "P2SH",
+            MultisigType::P2shP2wsh => "P2SH-P2WSH",
+            MultisigType::P2wsh     => "P2WSH",
+        }
+    }
+
+    /// Conta padrao do tipo (BIP48 nos SegWit, BIP45 no P2SH legado).
+    pub fn account_path(&self, coin_type: u32) -> String {
+        match self {
+            MultisigType::P2sh      => "m/45'".to_string(),
+            MultisigType::P2shP2wsh => format!("m/48'/{}'/0'/1'", coin_type),
+            MultisigType::P2wsh     => format!("m/48'/{}'/0'/2'", coin_type),
+        }
+    }
+
+    /// `inner` (o sortedmulti) dentro do descriptor do tipo.
+    pub fn wrap_descriptor(&self, inner: &str) -> String {
+        match self {
+            MultisigType::P2sh      => format!("sh({})", inner),
+            MultisigType::P2shP2wsh => format!("sh(wsh({}))", inner),
+            MultisigType::P2wsh     => format!("wsh({})", inner),
+        }
+    }
+}
+
+/// Placeholder of the mnemonic's key in a `--witness-script` template.
+pub const MY_KEY: &str = "@";
+
+/// How the keys make the script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Template {
+    /// `sortedmulti(M,...)`: keys sorted as BIP67
+    SortedMulti,
+    /// `multi(M,...)`: keys in the given order, the mnemonic's at `mine`
+    Multi { mine: usize },
+    /// `pk(@)`: the mnemonic's key alone, `<key> OP_CHECKSIG`
+    Pk,
+}
+
+impl Template {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Template::SortedMulti => "sortedmulti",
+            Template::Multi { .. } => "multi",
+            Template::Pk           => "pk",
+        }
+    }
+}
+
+/// An M-of-N wallet: the searched mnemonic holds one key, `cosigners` the others.
+#[derive(Debug, Clone)]
+pub struct Multisig {
+    pub script_type: MultisigType,
+    /// Signatures required (the M of M-of-N)
+    pub quorum:      usize,
+    /// Account-level xpubs of the other cosigners (N - 1)
+    pub cosigners:   Vec<Xpub>,
+    pub template:    Template,
+}
+
+impl Multisig {
+    pub fn new(script_type: MultisigType, quorum: usize, cosigners: Vec<Xpub>) -> Result<Self> {
+        let n = cosigners.len() + 1;
+        if cosigners.is_empty() {
+            anyhow::bail!("A multisig wallet needs at least one cosigner xpub");
+        }
+        if n > MAX_KEYS {
+            anyhow::bail!("At most {} keys fit a standard multisig script, got {}", MAX_KEYS, n);
+        }
+        if quorum == 0 || quorum > n {
+            anyhow::bail!("Quorum {} is not possible with {} keys (expected 1..={})", quorum, n, n);
+        }
+        Ok(Self { script_type, quorum, cosigners, template: Template::SortedMulti })
+    }
+
+    /// Wallet from a script template: `pk(@)`, `sortedmulti(M,...)` or `multi(M,...)` with
+    /// the cosigners' account xpubs and `@` for the mnemonic's key.
+    ///
+    /// ```
+    /// use brute_force_mnemonics::multisig::{Multisig, MultisigType, Template};
+    ///
+    /// let single = Multisig::parse_template(MultisigType::P2wsh, "pk(@)")?;
+    /// assert_eq!((single.template, single.key_count()), (Template::Pk, 1));
+    ///
+    /// let xpub = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+    /// let multi = Multisig::parse_template(MultisigType::P2wsh, &format!("multi(1,{},@)", xpub))?;
+    /// assert_eq!((multi.template, multi.quorum, multi.key_count()), (Template::Multi { mine: 1 }, 1, 2));
+    /// # Ok::<(), anyhow::Error>(())
+    /// ```
+    pub fn parse_template(script_type: MultisigType, text: &str) -> Result<Self> {
+        let text: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+        let (name, args) = text.strip_suffix(')').and_then(|t| t.split_once('('))
+            .with_context(|| format!("Expected pk(@), sortedmulti(M,...) or multi(M,...), got '{}'", text))?;
+        let args: Vec<&str> = args.split(',').collect();
+        if args.iter().filter(|a| **a == MY_KEY).count() != 1 {
+            anyhow::bail!("The template must hold the mnemonic's key ({}) exactly once", MY_KEY);
+        }
+        if name == "pk" {
+            if args.len() != 1 {
+                anyhow::bail!("pk() takes one key, the mnemonic's: pk({})", MY_KEY);
+            }
+            return Ok(Self { script_type, quorum: 1, cosigners: Vec::new(), template: Template::Pk });
+        }
+        let template = match name {
+            "sortedmulti" => Template::SortedMulti,
+            "multi"       => Template::Multi { mine: args[1..].iter().position(|a| *a == MY_KEY).unwrap_or_default() },
+            _ => anyhow::bail!("Unsupported script '{}' (expected pk, sortedmulti or multi)", name),
+        };
+        let quorum = args[0].parse::<usize>()
+            .with_context(|| format!("Expected the quorum first in {}(M,...), got '{}'", name, args[0]))?;
+        let cosigners = args[1..].iter()
+            .filter(|a| **a != MY_KEY)
+            .map(|a| parse_cosigner(a))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { template, ..Self::new(script_type, quorum, cosigners)? })
+    }
+
+    /// N, the mnemonic's key included.
+    pub fn key_count(&self) -> usize {
+        self.cosigners.len() + 1
+    }
+
+    /// Chaves dos cosignatarios para `path`: os dois ultimos passos (cadeia/indice)
+    /// aplicados a cada xpub de conta.
+    pub fn cosigner_keys(&self, path: &DerivationPath) -> Result<Vec<PublicKey>> {
+        if self.cosigners.is_empty() {
+            return Ok(Vec::new());
+        }
+        let tail = &path[path.len().saturating_sub(2)..];
+        if tail.len() < 2 || tail.iter().any(|c| c.is_hardened()) {
+            anyhow::bail!("Multisig path m/{} must end in two unhardened steps (chain/index)", path);
+        }
+        let secp = Secp256k1::verification_only();
+        self.cosigners.iter()
+            .map(|x| Ok(x.derive_pub(&secp, &tail)?.public_key))
+            .collect::<Result<_>>()
+            .with_context(|| format!("Failed to derive the cosigner keys at m/{}", path))
+    }
+
+    /// scriptPubKey com a chave do candidato (`mine`) e as dos cosignatarios ja derivadas.
+    pub fn script(&self, mine: PublicKey, cosigners: &[PublicKey]) -> ScriptBuf {
+        let mut keys: Vec<[u8; 33]> = cosigners.iter().map(|k| k.serialize()).collect();
+        match self.template {
+            Template::Multi { mine: at } => keys.insert(at, mine.serialize()),
+            _ => {
+                keys.push(mine.serialize());
+                keys.sort_unstable();
+            }
+        }
+        let multi = if self.template == Template::Pk {
+            Builder::new().push_slice(mine.serialize()).push_opcode(OP_CHECKSIG).into_script()
+        } else {
+            let mut builder = Builder::new().push_int(self.quorum as i64);
+            for key in &keys {
+                builder = builder.push_slice(key);
+            }
+            builder.push_int(keys.len() as i64).push_opcode(OP_CHECKMULTISIG).into_script()
+        };
+        match self.script_type {
+            MultisigType::P2sh      => ScriptBuf::new_p2sh(&multi.script_hash()),
+            MultisigType::P2wsh     => ScriptBuf::new_p2wsh(&multi.wscript_hash()),
+            MultisigType::P2shP2wsh => {
+                let witness = ScriptBuf::new_p2wsh(&multi.wscript_hash());
+                ScriptBuf::new_p2sh(&witness.script_hash())
+            }
+        }
+    }
+}
+
+/// Decodifica a xpub de conta de um cosignatario: xpub/ypub/zpub ou as versoes
+/// multisig SLIP-132 (Ypub, Zpub).
+pub fn parse_cosigner(s: &str) -> Result<Xpub> {
+    if !s.starts_with('Y') && !s.starts_with('Z') {
+        return Ok(parse_extended_pubkey(s)?.0);
+    }
+    let mut data = base58::decode_check(s).context("Invalid cosigner xpub (base58check)")?;
+    if data.len() != 78 || ![YPUB_MULTISIG, ZPUB_MULTISIG].contains(&[data[0], data[1], data[2], data[3]]) {
+        anyhow::bail!("Unsupported cosigner key {} (expected xpub, ypub, zpub, Ypub or Zpub)", s);
+    }
+    data[..4].copy_from_slice(&XPUB_VERSION);
+    Xpub::decode(&data).context("Invalid cosigner xpub")
+}
@@ -0,0 +1,144 @@
+// ── Passphrases por mascara (--passphrase-mask, estilo hashcat) ──────────────
+//
+// Cada posicao da mascara e um caractere fixo ou um conjunto: ?l (a-z), ?u
+// (A-Z), ?d (0-9), ?s (especiais ASCII, espaco incluso), ?a (todos esses) e
+// ?1..?4 (conjuntos do usuario, que tambem aceitam ?l etc.); ?? e um "?". As
+// passphrases nao sao guardadas: o indice e decomposto em base mista, com a
+// ultima posicao variando mais rapido (aaa, aab, ...).
+
+use crate::mnemonic::normalize;
+use anyhow::Result;
+
+const LOWER: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPER: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+const SPECIAL: &str = " !\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+
+/// Most user-defined charsets (`?1` to `?4`).
+pub const MAX_CUSTOM_CHARSETS: usize = 4;
+
+/// Every passphrase a hashcat-style mask describes, in index order.
+///
+/// ```
+/// use brute_force_mnemonics::passphrase::PassphraseMask;
+///
+/// let mask = PassphraseMask::parse("?u?d!", &[])?;
+/// assert_eq!(mask.count(), 260);
+/// assert_eq!(mask.at(0), "A0!");
+/// assert_eq!(mask.at(259), "Z9!");
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PassphraseMask {
+    mask:      String,
+    positions: Vec<Vec<char>>,
+    count:     u64,
+}
+
+impl PassphraseMask {
+    /// Parses `mask`; `custom[i]` is the charset of `?{i + 1}`.
+    pub fn parse(mask: &str, custom: &[String]) -> Result<Self> {
+        if custom.len() > MAX_CUSTOM_CHARSETS {
+            anyhow::bail!("At most {} custom charsets (?1 to ?{})", MAX_CUSTOM_CHARSETS, MAX_CUSTOM_CHARSETS);
+        }
+        let custom = custom.iter().map(|c| charset(c)).collect::<Result<Vec<_>>>()?;
+        let positions = expand(mask, &custom)?;
+        if positions.is_empty() {
+            anyhow::bail!("Empty passphrase mask");
+        }
+        if let Some(i) = positions.iter().position(|p| p.is_empty()) {
+            anyhow::bail!("Position {} of the passphrase mask has an empty charset", i + 1);
+        }
+        let count = positions.iter()
+            .try_fold(1u64, |n, p| n.checked_mul(p.len() as u64))
+            .ok_or_else(|| anyhow::anyhow!("Passphrase mask '{}' describes more than 2^64 passphrases", mask))?;
+        Ok(Self { mask: mask.to_string(), positions, count })
+    }
+
+    /// The mask as given.
+    pub fn as_str(&self) -> &str {
+        &self.mask
+    }
+
+    /// Number of passphrases.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Passphrase number `index` (`index < count()`), NFKD-normalized.
+    pub fn at(&self, mut index: u64) -> String {
+        let mut chars = vec![' '; self.positions.len()];
+        for (c, set) in chars.iter_mut().zip(&self.positions).rev() {
+            let radix = set.len() as u64;
+            *c = set[(index % radix) as usize];
+            index /= radix;
+        }
+        normalize(&chars.into_iter().collect::<String>())
+    }
+}
+
+// Uma posicao da mascara por elemento: o conjunto de caracteres dela
+fn expand(mask: &str, custom: &[Vec<char>]) -> Result<Vec<Vec<char>>> {
+    let mut positions = Vec::new();
+    let mut chars = mask.chars();
+    while let Some(c) = chars.next() {
+        if c != '?' {
+            positions.push(vec![c]);
+            continue;
+        }
+        let set = match chars.next() {
+            Some('l') => LOWER.chars().collect(),
+            Some('u') => UPPER.chars().collect(),
+            Some('d') => DIGITS.chars().collect(),
+            Some('s') => SPECIAL.chars().collect(),
+            Some('a') => [LOWER, UPPER, DIGITS, SPECIAL].concat().chars().collect(),
+            Some('?') => vec!['?'],
+            Some(n @ '1'..='4') => {
+                let i = n as usize - '1' as usize;
+                custom.get(i).cloned()
+                    .ok_or_else(|| anyhow::anyhow!("?{} used but no custom charset {} given (--custom-charset)", n, n))?
+            }
+            Some(other) => anyhow::bail!("Unknown charset ?{} in the passphrase mask (expected ?l ?u ?d ?s ?a ?1-?4 or ??)", other),
+            None => anyhow::bail!("Passphrase mask ends with a lone ? (use ?? for a literal ?)"),
+        };
+        positions.push(set);
+    }
+    Ok(positions)
+}
+
+// Conjunto do usuario: caracteres e ?l/?u/?d/?s/?a, sem repeticoes
+fn charset(spec: &str) -> Result<Vec<char>> {
+    let mut set: Vec<char> = expand(spec, &[])?.into_iter().flatten().collect();
+    let mut seen = std::collections::HashSet::new();
+    set.retain(|c| seen.insert(*c));
+    Ok(set)
+}
+
+/// The passphrases a search tries with every candidate: a list or a mask.
+#[derive(Debug, Clone)]
+pub enum Passphrases {
+    /// Already NFKD-normalized
+    List(Vec<String>),
+    Mask(PassphraseMask),
+}
+
+impl Passphrases {
+    pub fn len(&self) -> u64 {
+        match self {
+            Passphrases::List(list) => list.len() as u64,
+            Passphrases::Mask(mask) => mask.count(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Passphrase number `index`, normalized.
+    pub fn get(&self, index: u64) -> String {
+        match self {
+            Passphrases::List(list) => list[index as usize].clone(),
+            Passphrases::Mask(mask) => mask.at(index),
+        }
+    }
+}
@@ -0,0 +1,285 @@
+// ── PBKDF2-HMAC-SHA512 da seed BIP-39 (2048 rodadas) ─────────────────────────
+//
+// E o gargalo da busca na CPU: 4096 compressoes SHA-512 por seed. Cada rodada
+// e HMAC(frase, U) com U de 64 bytes, entao os dois blocos (interno e externo)
+// tem formato fixo e partem de estados ipad/opad calculados uma vez so.
+//
+// Com AVX2 (detectado em runtime) as seeds saem de 4 em 4: cada registro de
+// 256 bits leva a mesma palavra de 4 frases diferentes (multi-buffer). SHA-NI
+// so cobre SHA-1/SHA-256, nao ajuda aqui. Sem AVX2 (ou nas sobras de um lote)
+// vai o SHA-512 em assembly do ring, que escolhe AVX/SSSE3 ou as extensoes
+// SHA512 do ARMv8 sozinho.
+
+use std::num::NonZeroU32;
+
+/// PBKDF2 iterations of a BIP-39 seed.
+pub const ROUNDS: u32 = 2048;
+
+/// Seeds computed together by the SIMD path.
+pub const LANES: usize = 4;
+
+const IV: [u64; 8] = [
+    0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+    0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+];
+
+const K: [u64; 80] = [
+    0x428a2f98d728ae22, 0x7137449123ef65cd, 0xb5c0fbcfec4d3b2f, 0xe9b5dba58189dbbc,
+    0x3956c25bf348b538, 0x59f111f1b605d019, 0x923f82a4af194f9b, 0xab1c5ed5da6d8118,
+    0xd807aa98a3030242, 0x12835b0145706fbe, 0x243185be4ee4b28c, 0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f, 0x80deb1fe3b1696b1, 0x9bdc06a725c71235, 0xc19bf174cf692694,
+    0xe49b69c19ef14ad2, 0xefbe4786384f25e3, 0x0fc19dc68b8cd5b5, 0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275, 0x4a7484aa6ea6e483, 0x5cb0a9dcbd41fbd4, 0x76f988da831153b5,
+    0x983e5152ee66dfab, 0xa831c66d2db43210, 0xb00327c898fb213f, 0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2, 0xd5a79147930aa725, 0x06ca6351e003826f, 0x142929670a0e6e70,
+    0x27b70a8546d22ffc, 0x2e1b21385c26c926, 0x4d2c6dfc5ac42aed, 0x53380d139d95b3df,
+    0x650a73548baf63de, 0x766a0abb3c77b2a8, 0x81c2c92e47edaee6, 0x92722c851482353b,
+    0xa2bfe8a14cf10364, 0xa81a664bbc423001, 0xc24b8b70d0f89791, 0xc76c51a30654be30,
+    0xd192e819d6ef5218, 0xd69906245565a910, 0xf40e35855771202a, 0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8, 0x1e376c085141ab53, 0x2748774cdf8eeb99, 0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63, 0x4ed8aa4ae3418acb, 0x5b9cca4f7763e373, 0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc, 0x78a5636f43172f60, 0x84c87814a1f0ab72, 0x8cc702081a6439ec,
+    0x90befffa23631e28, 0xa4506cebde82bde9, 0xbef9a3f7b2c67915, 0xc67178f2e372532b,
+    0xca273eceea26619c, 0xd186b8c721c0c207, 0xeada7dd6cde0eb1e, 0xf57d4f7fee6ed178,
+    0x06f067aa72176fba, 0x0a637dc5a2c898a6, 0x113f9804bef90dae, 0x1b710b35131c471b,
+    0x28db77f523047d84, 0x32caab7b40c72493, 0x3c9ebe0a15c9bebc, 0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6, 0x597f299cfc657e2a, 0x5fcb6fab3ad6faec, 0x6c44198c4a475817,
+];
+
+// Bits das mensagens de uma rodada: bloco da chave (128 bytes) + U ou o hash interno (64)
+const ROUND_BITS: u64 = (128 + 64) * 8;
+
+/// BIP-39 seed of a normalized phrase (words joined by single spaces, as
+/// `build_phrase` writes them) and a normalized passphrase: the same bytes as
+/// `Mnemonic::to_seed_normalized`.
+///
+/// ```
+/// use bip39::{Language, Mnemonic};
+/// use brute_force_mnemonics::pbkdf2;
+///
+/// let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+/// let mnemonic = Mnemonic::parse_in_normalized(Language::English, phrase).unwrap();
+/// assert_eq!(pbkdf2::seed(phrase, "TREZOR"), mnemonic.to_seed_normalized("TREZOR"));
+///
+/// // Em lote (4 por vez com AVX2): frases acima de 128 bytes e passphrases longas tambem
+/// let long = ["zoo"; 23].join(" ") + " wrong";
+/// let pass = "x".repeat(200);
+/// let inputs = [(phrase, ""), (long.as_str(), "TREZOR"), (phrase, pass.as_str()), (long.as_str(), ""), (phrase, "a")];
+/// let seeds = pbkdf2::seeds(&inputs);
+/// for (&(p, s), seed) in inputs.iter().zip(&seeds) {
+///     assert_eq!(*seed, pbkdf2::seed(p, s));
+/// }
+/// ```
+pub fn seed(phrase: &str, passphrase: &str) -> [u8; 64] {
+    let mut salt = Vec::with_capacity(8 + passphrase.len());
+    salt.extend_from_slice(b"mnemonic");
+    salt.extend_from_slice(passphrase.as_bytes());
+    let mut out = [0u8; 64];
+    ring::pbkdf2::derive(
+        ring::pbkdf2::PBKDF2_HMAC_SHA512,
+        NonZeroU32::new(ROUNDS).expect("nonzero rounds"),
+        &salt,
+        phrase.as_bytes(),
+        &mut out,
+    );
+    out
+}
+
+/// Seeds of `(phrase, passphrase)` pairs, in order; `LANES` at a time on AVX2.
+pub fn seeds(inputs: &[(&str, &str)]) -> Vec<[u8; 64]> {
+    let mut out = Vec::with_capacity(inputs.len());
+    let lanes = if simd() { inputs.len() / LANES * LANES } else { 0 };
+    #[cfg(target_arch = "x86_64")]
+    for group in inputs[..lanes].chunks_exact(LANES) {
+        let states = group.iter().map(|&(p, s)| Lane::new(p, s)).collect::<Vec<_>>();
+        // SAFETY: simd() confirmou AVX2 nesta CPU
+        out.extend(unsafe { avx2::rounds(&states) });
+    }
+    out.extend(inputs[lanes..].iter().map(|&(p, s)| seed(p, s)));
+    out
+}
+
+/// Whether `seeds` runs on AVX2 on this CPU.
+pub fn simd() -> bool {
+    #[cfg(target_arch = "x86_64")]
+    {
+        std::arch::is_x86_feature_detected!("avx2")
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        false
+    }
+}
+
+// Estado de uma frase no lote: meio-estados ipad/opad e U1 (a primeira rodada,
+// cujo sal tem tamanho livre, e feita aqui em escalar)
+#[cfg_attr(not(target_arch = "x86_64"), allow(dead_code))]
+struct Lane {
+    ipad: [u64; 8],
+    opad: [u64; 8],
+    u1:   [u64; 8],
+}
+
+#[cfg_attr(not(target_arch = "x86_64"), allow(dead_code))]
+impl Lane {
+    fn new(phrase: &str, passphrase: &str) -> Self {
+        // Chave acima de um bloco vira o proprio hash (RFC 2104)
+        let mut key = [0u8; 128];
+        if phrase.len() > 128 {
+            key[..64].copy_from_slice(&words_to_bytes(&hash(IV, phrase.as_bytes(), 0)));
+        } else {
+            key[..phrase.len()].copy_from_slice(phrase.as_bytes());
+        }
+        let ipad = midstate(&key, 0x36);
+        let opad = midstate(&key, 0x5c);
+        let mut salt = Vec::with_capacity(12 + passphrase.len());
+        salt.extend_from_slice(b"mnemonic");
+        salt.extend_from_slice(passphrase.as_bytes());
+        salt.extend_from_slice(&1u32.to_be_bytes());
+        let inner = hash(ipad, &salt, 128);
+        let u1 = hash(opad, &words_to_bytes(&inner), 128);
+        Self { ipad, opad, u1 }
+    }
+}
+
+fn midstate(key: &[u8; 128], pad: u8) -> [u64; 8] {
+    let mut block = [0u8; 128];
+    for (b, k) in block.iter_mut().zip(key) {
+        *b = k ^ pad;
+    }
+    let mut state = IV;
+    compress(&mut state, &block);
+    state
+}
+
+// SHA-512 de `data` a partir de `state`, com `prefix` bytes ja comprimidos antes
+fn hash(mut state: [u64; 8], data: &[u8], prefix: usize) -> [u64; 8] {
+    let mut tail = data.to_vec();
+    let bits = ((prefix + data.len()) as u128) * 8;
+    tail.push(0x80);
+    while tail.len() % 128 != 112 {
+        tail.push(0);
+    }
+    tail.extend_from_slice(&bits.to_be_bytes());
+    for block in tail.chunks_exact(128) {
+        compress(&mut state, block.try_into().expect("128-byte block"));
+    }
+    state
+}
+
+fn words_to_bytes(words: &[u64; 8]) -> [u8; 64] {
+    let mut out = [0u8; 64];
+    for (chunk, w) in out.chunks_exact_mut(8).zip(words) {
+        chunk.copy_from_slice(&w.to_be_bytes());
+    }
+    out
+}
+
+fn compress(state: &mut [u64; 8], block: &[u8; 128]) {
+    let mut w = [0u64; 80];
+    for (w, chunk) in w.iter_mut().zip(block.chunks_exact(8)) {
+        *w = u64::from_be_bytes(chunk.try_into().expect("8-byte word"));
+    }
+    for i in 16..80 {
+        let s0 = w[i - 15].rotate_right(1) ^ w[i - 15].rotate_right(8) ^ (w[i - 15] >> 7);
+        let s1 = w[i - 2].rotate_right(19) ^ w[i - 2].rotate_right(61) ^ (w[i - 2] >> 6);
+        w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+    }
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for i in 0..80 {
+        let s1 = e.rotate_right(14) ^ e.rotate_right(18) ^ e.rotate_right(41);
+        let ch = (e & f) ^ (!e & g);
+        let t1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(K[i]).wrapping_add(w[i]);
+        let s0 = a.rotate_right(28) ^ a.rotate_right(34) ^ a.rotate_right(39);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(maj);
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+    }
+    for (s, v) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *s = s.wrapping_add(v);
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod avx2 {
+    use super::{words_to_bytes, Lane, K, LANES, ROUNDS, ROUND_BITS};
+    use std::arch::x86_64::*;
+
+    // Sem rotacao de 64 bits no AVX2: dois shifts e um or
+    macro_rules! rotr {
+        ($x:expr, $n:literal) => {
+            _mm256_or_si256(_mm256_srli_epi64::<$n>($x), _mm256_slli_epi64::<{ 64 - $n }>($x))
+        };
+    }
+
+    type State = [__m256i; 8];
+
+    // Palavra `i` de cada uma das 4 frases num registro so
+    #[target_feature(enable = "avx2")]
+    unsafe fn gather(words: [&[u64; 8]; LANES]) -> State {
+        std::array::from_fn(|i| {
+            _mm256_set_epi64x(words[3][i] as i64, words[2][i] as i64, words[1][i] as i64, words[0][i] as i64)
+        })
+    }
+
+    // Uma compressao de bloco unico com formato fixo: 64 bytes de dados + padding
+    #[target_feature(enable = "avx2")]
+    unsafe fn compress(state: &State, data: &State) -> State {
+        let mut w = [_mm256_setzero_si256(); 80];
+        w[..8].copy_from_slice(data);
+        w[8]  = _mm256_set1_epi64x(i64::MIN); // 0x80 seguido de zeros
+        w[15] = _mm256_set1_epi64x(ROUND_BITS as i64);
+        for i in 16..80 {
+            let s0 = _mm256_xor_si256(_mm256_xor_si256(rotr!(w[i - 15], 1), rotr!(w[i - 15], 8)), _mm256_srli_epi64::<7>(w[i - 15]));
+            let s1 = _mm256_xor_si256(_mm256_xor_si256(rotr!(w[i - 2], 19), rotr!(w[i - 2], 61)), _mm256_srli_epi64::<6>(w[i - 2]));
+            w[i] = _mm256_add_epi64(_mm256_add_epi64(w[i - 16], s0), _mm256_add_epi64(w[i - 7], s1));
+        }
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+        for (i, &k) in K.iter().enumerate() {
+            let s1  = _mm256_xor_si256(_mm256_xor_si256(rotr!(e, 14), rotr!(e, 18)), rotr!(e, 41));
+            let ch  = _mm256_xor_si256(_mm256_and_si256(e, f), _mm256_andnot_si256(e, g));
+            let t1  = _mm256_add_epi64(_mm256_add_epi64(h, s1), _mm256_add_epi64(ch, _mm256_add_epi64(_mm256_set1_epi64x(k as i64), w[i])));
+            let s0  = _mm256_xor_si256(_mm256_xor_si256(rotr!(a, 28), rotr!(a, 34)), rotr!(a, 39));
+            let maj = _mm256_or_si256(_mm256_and_si256(a, b), _mm256_and_si256(c, _mm256_or_si256(a, b)));
+            let t2  = _mm256_add_epi64(s0, maj);
+            h = g;
+            g = f;
+            f = e;
+            e = _mm256_add_epi64(d, t1);
+            d = c;
+            c = b;
+            b = a;
+            a = _mm256_add_epi64(t1, t2);
+        }
+        let out = [a, b, c, d, e, f, g, h];
+        std::array::from_fn(|i| _mm256_add_epi64(state[i], out[i]))
+    }
+
+    // Rodadas 2..=2048 das 4 frases; devolve as seeds
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn rounds(lanes: &[Lane]) -> Vec<[u8; 64]> {
+        let lanes: [&Lane; LANES] = std::array::from_fn(|i| &lanes[i]);
+        let ipad = gather(lanes.map(|l| &l.ipad));
+        let opad = gather(lanes.map(|l| &l.opad));
+        let mut u = gather(lanes.map(|l| &l.u1));
+        let mut t = u;
+        for _ in 1..ROUNDS {
+            u = compress(&opad, &compress(&ipad, &u));
+            for (t, u) in t.iter_mut().zip(&u) {
+                *t = _mm256_xor_si256(*t, *u);
+            }
+        }
+        let mut words = [[0u64; LANES]; 8];
+        for (w, t) in words.iter_mut().zip(&t) {
+            _mm256_storeu_si256(w.as_mut_ptr() as *mut __m256i, *t);
+        }
+        (0..LANES).map(|l| words_to_bytes(&std::array::from_fn(|i| words[i][l]))).collect()
+    }
+}
@@ -0,0 +1,806 @@
+// ── Geracao de permutacoes por indice (sistema fatoradico) ───────────────────
+//
+// Cada indice em [0, n!) corresponde a exatamente uma ordem das palavras.
+// Isso permite paralelizar (Rayon sobre faixas de indices), retomar de um
+// checkpoint e dividir o trabalho sem gerar as permutacoes anteriores.
+
+use crate::mnemonic::set_checksum;
+
+// ── Fatorial (u128 para suportar 24! sem overflow) ───────────────────────────
+// Tabelado ate 34! (o maior que cabe em u128): o laco quente consulta um
+// fatorial por posicao de cada candidato
+const FACTORIALS: [u128; 35] = {
+    let mut table = [1u128; 35];
+    let mut i = 1;
+    while i < table.len() {
+        table[i] = table[i - 1] * i as u128;
+        i += 1;
+    }
+    table
+};
+
+pub fn factorial(n: u128) -> u128 {
+    if n < FACTORIALS.len() as u128 {
+        FACTORIALS[n as usize]
+    } else {
+        (1..=n).product::<u128>().max(1)
+    }
+}
+
+// ── Permutação por índice com bitmask (sem Vec::remove) ──────────────────────
+//
+// A abordagem anterior usava Vec::remove(pos) — O(n) por passo porque desloca
+// todos os elementos à direita. Com um bitmask u64 (suporta n<=64),
+// encontramos o k-ésimo elemento livre com um loop simples sem alocar memória.
+//
+// O parâmetro index chega como u64 (seguro para o Rayon) e é convertido para
+// u128 aqui onde o fatorádico precisa de precisão total.
+pub fn permutation_at_index(items: &[u16], index: u128) -> Vec<u16> {
+    let mut result = Vec::with_capacity(items.len());
+    permutation_at_index_into(items, index, &mut result);
+    result
+}
+
+/// `permutation_at_index` escrita em `out` (esvaziado antes): quem percorre muitos
+/// indices reaproveita um buffer em vez de alocar uma ordem por indice.
+pub fn permutation_at_index_into(items: &[u16], mut index: u128, out: &mut Vec<u16>) {
+    let n = items.len();
+    debug_assert!(n <= 64, "bitmask suporta no maximo 64 itens");
+
+    out.clear();
+    let mut used: u64 = 0; // bit i = 1 → items[i] ja foi usado
+
+    for i in (1..=n as u128).rev() {
+        let f  = factorial(i - 1);
+        let k  = (index / f) as usize; // queremos o k-esimo item ainda livre
+        index %= f;
+
+        // Encontra a posicao do k-esimo bit NAO-setado em `used`
+        let mut count = 0usize;
+        let mut pos   = 0usize;
+        loop {
+            if used & (1 << pos) == 0 {
+                if count == k { break; }
+                count += 1;
+            }
+            pos += 1;
+        }
+
+        used |= 1 << pos;
+        out.push(items[pos]);
+    }
+}
+
+// ── Indice de uma permutacao (inverso de permutation_at_index) ───────────────
+//
+// Cada palavra contribui (quantas livres ha antes dela) * (restantes)!. Com
+// palavras repetidas varios indices dao a mesma ordem; vale o menor.
+
+/// Indice da ordem `perm` de `items` no fatoradico, ou `None` quando `perm` nao e uma
+/// ordem de `items`.
+///
+/// ```
+/// use brute_force_mnemonics::permutation::{permutation_at_index, permutation_rank};
+///
+/// let items = [10, 20, 30, 40];
+/// assert_eq!(permutation_at_index(&items, 17), vec![30, 40, 20, 10]);
+/// assert_eq!(permutation_rank(&items, &[30, 40, 20, 10]), Some(17));
+/// assert_eq!(permutation_rank(&items, &[30, 40, 20, 20]), None);
+/// ```
+pub fn permutation_rank(items: &[u16], perm: &[u16]) -> Option<u128> {
+    let n = items.len();
+    debug_assert!(n <= 64, "bitmask suporta no maximo 64 itens");
+    if perm.len() != n {
+        return None;
+    }
+
+    let mut used: u64 = 0;
+    let mut index: u128 = 0;
+    for (i, &word) in perm.iter().enumerate() {
+        // Primeira posicao livre com esta palavra
+        let pos = (0..n).find(|&p| used & (1 << p) == 0 && items[p] == word)?;
+        let before = pos as u32 - (used & ((1 << pos) - 1)).count_ones();
+        index += before as u128 * factorial((n - 1 - i) as u128);
+        used |= 1 << pos;
+    }
+    Some(index)
+}
+
+// ── Ordem de busca (--order) ─────────────────────────────────────────────────
+//
+// original-first: fatoradico sobre a ordem informada (indice 0 = a propria
+//                 ordem; os primeiros indices so mexem nas ultimas posicoes)
+// lexicographic:  fatoradico sobre as palavras ordenadas pela wordlist
+// random:         indices embaralhados por uma bijecao fixa (Shuffle)
+// heap:           algoritmo de Heap — cada indice difere do anterior por uma troca
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Order {
+    #[default]
+    OriginalFirst,
+    Lexicographic,
+    Random,
+    Heap,
+}
+
+impl Order {
+    /// Permutacao `index` de `items` nesta ordem.
+    pub fn permutation(&self, items: &[u16], index: u128) -> Vec<u16> {
+        let mut result = Vec::with_capacity(items.len());
+        self.permutation_into(items, index, &mut result);
+        result
+    }
+
+    /// `permutation` escrita em `out`.
+    pub fn permutation_into(&self, items: &[u16], index: u128, out: &mut Vec<u16>) {
+        match self {
+            Order::Heap => heap_permutation_at_index_into(items, index, out),
+            _           => permutation_at_index_into(items, index, out),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Order::OriginalFirst => "original-first",
+            Order::Lexicographic => "lexicographic",
+            Order::Random        => "random",
+            Order::Heap          => "heap",
+        }
+    }
+}
+
+// ── Algoritmo de Heap por indice ─────────────────────────────────────────────
+//
+// generate(k) = k blocos de generate(k-1), separados por uma troca (k par:
+// posicao i com k-1; k impar: 0 com k-1). O efeito de um generate(m) completo
+// nas posicoes nao depende do conteudo, entao o estado no inicio de cada bloco
+// e um mapa de posicoes pre-calculado: heap_blocks()[k][b] (novo[i] = velho[mapa[i]]).
+// Para desfazer o indice basta aplicar um mapa por nivel, de n ate 2.
+const HEAP_MAX: usize = 32;
+
+type PosMap = Vec<u8>;
+
+// a e depois b
+fn compose(a: &[u8], b: &[u8]) -> PosMap {
+    b.iter().map(|&i| a[i as usize]).collect()
+}
+
+fn heap_blocks() -> &'static Vec<Vec<PosMap>> {
+    static TABLE: std::sync::OnceLock<Vec<Vec<PosMap>>> = std::sync::OnceLock::new();
+    TABLE.get_or_init(|| {
+        let identity = |n: usize| (0..n as u8).collect::<PosMap>();
+        let swap = |n: usize, i: usize, j: usize| {
+            let mut m = identity(n);
+            m.swap(i, j);
+            m
+        };
+        let mut table = vec![Vec::new(), vec![identity(1)]];
+        let mut full = identity(1); // efeito de generate(k-1) completo
+        for k in 2..=HEAP_MAX {
+            let mut inner = full.clone();
+            inner.push((k - 1) as u8);
+            let mut blocks = vec![identity(k)];
+            for b in 1..=k {
+                let t = if k % 2 == 0 { b - 1 } else { 0 };
+                let after = compose(&blocks[b - 1], &inner);
+                if b == k {
+                    full = after; // ultimo bloco termina sem troca
+                } else {
+                    blocks.push(compose(&after, &swap(k, t, k - 1)));
+                }
+            }
+            table.push(blocks);
+        }
+        table
+    })
+}
+
+/// Permutacao `index` na ordem do algoritmo de Heap.
+pub fn heap_permutation_at_index(items: &[u16], index: u128) -> Vec<u16> {
+    let mut result = Vec::with_capacity(items.len());
+    heap_permutation_at_index_into(items, index, &mut result);
+    result
+}
+
+/// `heap_permutation_at_index` escrita em `out`.
+pub fn heap_permutation_at_index_into(items: &[u16], mut index: u128, out: &mut Vec<u16>) {
+    let n = items.len();
+    debug_assert!(n <= HEAP_MAX, "tabela suporta no maximo 32 itens");
+
+    let table = heap_blocks();
+    out.clear();
+    out.extend_from_slice(items);
+    let result = out;
+    let mut prefix = [0u16; HEAP_MAX];
+    for k in (2..=n).rev() {
+        let f = factorial(k as u128 - 1);
+        let b = (index / f) as usize;
+        index %= f;
+        if b == 0 { continue; }
+        for (p, &i) in prefix.iter_mut().zip(&table[k][b]) {
+            *p = result[i as usize];
+        }
+        result[..k].copy_from_slice(&prefix[..k]);
+    }
+}
+
+// ── Permutacoes a ate K trocas da ordem informada (--max-swaps) ──────────────
+//
+// O minimo de transposicoes ate uma permutacao π e m - ciclos(π). Quantas
+// permutacoes de m itens tem c ciclos e o numero de Stirling de primeira
+// especie s(m, c) = s(m-1, c-1) + (m-1)·s(m-1, c): o ultimo item e um ciclo
+// sozinho ou entra logo apos um dos m-1 outros, num dos c ciclos. Essa
+// recorrencia desfaz o indice; os blocos vao de 0 trocas (a ordem informada)
+// ate K, em distancia crescente. 33 cobre as shares SLIP-39 de 256 bits.
+const STIRLING_MAX: usize = 33;
+
+fn stirling1() -> &'static [[u128; STIRLING_MAX + 1]; STIRLING_MAX + 1] {
+    static TABLE: std::sync::OnceLock<[[u128; STIRLING_MAX + 1]; STIRLING_MAX + 1]> = std::sync::OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut s = [[0u128; STIRLING_MAX + 1]; STIRLING_MAX + 1];
+        s[0][0] = 1;
+        for m in 1..=STIRLING_MAX {
+            for c in 1..=m {
+                s[m][c] = s[m - 1][c - 1].saturating_add((m as u128 - 1).saturating_mul(s[m - 1][c]));
+            }
+        }
+        s
+    })
+}
+
+/// Permutacoes de `m` itens a no maximo `max_swaps` transposicoes da identidade.
+pub fn swap_permutation_count(m: usize, max_swaps: usize) -> u128 {
+    let s = stirling1();
+    (0..=max_swaps.min(m.saturating_sub(1)))
+        .fold(0u128, |acc, d| acc.saturating_add(s[m][m - d]))
+}
+
+/// Permutacao `index` entre as que estao a no maximo `max_swaps` trocas de `items`.
+pub fn swap_permutation_at_index(items: &[u16], max_swaps: usize, index: u128) -> Vec<u16> {
+    let mut result = Vec::with_capacity(items.len());
+    swap_permutation_at_index_into(items, max_swaps, index, &mut result);
+    result
+}
+
+/// `swap_permutation_at_index` escrita em `out`.
+pub fn swap_permutation_at_index_into(items: &[u16], max_swaps: usize, mut index: u128, out: &mut Vec<u16>) {
+    let m = items.len();
+    debug_assert!(m <= STIRLING_MAX, "tabela suporta no maximo 33 itens");
+    let s = stirling1();
+
+    // Bloco de distancia d (m - d ciclos)
+    let mut cycles = m;
+    for d in 0..=max_swaps.min(m.saturating_sub(1)) {
+        cycles = m - d;
+        if index < s[m][cycles] { break; }
+        index -= s[m][cycles];
+    }
+
+    // Decisoes do maior item para o menor: None = ciclo proprio, Some(j) = apos j
+    let mut decisions = [None; STIRLING_MAX];
+    for (decision, size) in decisions.iter_mut().zip((1..=m).rev()) {
+        let alone = s[size - 1][cycles - 1];
+        if index < alone {
+            cycles -= 1;
+        } else {
+            index -= alone;
+            let block = s[size - 1][cycles];
+            *decision = Some((index / block) as usize);
+            index %= block;
+        }
+    }
+
+    // Reconstroi π do menor item para o maior; posicao i recebe items[π(i)]
+    let mut pi = [0usize; STIRLING_MAX];
+    for (e, decision) in decisions[..m].iter().rev().enumerate() {
+        pi[e] = e;
+        if let Some(j) = *decision {
+            pi.swap(e, j);
+        }
+    }
+    out.clear();
+    out.extend(pi[..m].iter().map(|&i| items[i]));
+}
+
+/// Ordens de `n` itens: todas (n!) ou so as a no maximo `max_swaps` trocas.
+pub fn order_count(n: usize, max_swaps: Option<usize>) -> u128 {
+    match max_swaps {
+        Some(k) => swap_permutation_count(n, k),
+        None    => factorial(n as u128),
+    }
+}
+
+/// Ordem `index` entre as de `order_count`; o indice 0 e a propria ordem de `items`.
+pub fn order_at_index(items: &[u16], max_swaps: Option<usize>, index: u128) -> Vec<u16> {
+    match max_swaps {
+        Some(k) => swap_permutation_at_index(items, k, index),
+        None    => permutation_at_index(items, index),
+    }
+}
+
+// ── Ordem relativa conhecida (--before) ──────────────────────────────────────
+//
+// "a vem antes de b" entre as palavras permutadas. O indice so desfaz as
+// permutacoes que respeitam as restricoes: em cada posicao, um item restrito
+// so entra depois de todos os seus predecessores, e cada ramo e contado sem
+// ser gerado. Com r itens restantes, dos quais o conjunto R e restrito, as
+// completacoes sao r!/|R|! · e(R), onde e(R) e o numero de ordens de R que
+// respeitam as restricoes (extensoes lineares), tabelado por subconjunto.
+// Como no fatoradico, os itens sao tentados na ordem informada: o indice 0 e
+// a propria ordem quando ela respeita as restricoes.
+pub const PRECEDENCE_MAX: usize = 20;
+// Palavras permutadas cobertas pela tabela de r!/j! (as 24 de uma frase + folga)
+const RATIO_MAX: usize = 32;
+
+#[derive(Debug, Clone)]
+pub struct Precedence {
+    /// Indice (em `items`) de cada posicao restrita das palavras permutadas
+    rank:       Vec<Option<usize>>,
+    /// Predecessores de cada item restrito (bits sobre os itens restritos)
+    preds:      Vec<u32>,
+    /// e(R) para cada subconjunto R dos itens restritos
+    extensions: Vec<u128>,
+    /// ratio[r][j] = r!/j!
+    ratio:      Vec<Vec<u128>>,
+}
+
+impl Precedence {
+    /// `pairs` (a, b): entre as palavras permutadas, a da posicao a vem antes da de b.
+    /// Falha num ciclo ou com mais de `PRECEDENCE_MAX` palavras envolvidas.
+    pub fn new(pairs: &[(usize, usize)]) -> anyhow::Result<Self> {
+        let mut items: Vec<usize> = pairs.iter().flat_map(|&(a, b)| [a, b]).collect();
+        items.sort_unstable();
+        items.dedup();
+        if items.len() > PRECEDENCE_MAX {
+            anyhow::bail!("At most {} words can take part in --before, got {}", PRECEDENCE_MAX, items.len());
+        }
+        let at = |p: usize| items.iter().position(|&i| i == p).expect("item de um par");
+        let mut preds = vec![0u32; items.len()];
+        for &(a, b) in pairs {
+            preds[at(b)] |= 1 << at(a);
+        }
+
+        // e(R) = soma de e(R - x) sobre os x de R sem predecessor em R
+        let k = items.len();
+        let mut extensions = vec![0u128; 1 << k];
+        extensions[0] = 1;
+        for set in 1..1usize << k {
+            extensions[set] = (0..k)
+                .filter(|&x| set & (1 << x) != 0 && preds[x] & set as u32 == 0)
+                .fold(0u128, |acc, x| acc.saturating_add(extensions[set & !(1 << x)]));
+        }
+        if extensions[(1 << k) - 1] == 0 {
+            anyhow::bail!("The --before constraints contradict each other (a word would come before itself)");
+        }
+
+        let mut rank = vec![None; items.iter().max().map_or(0, |&m| m + 1)];
+        for (j, &p) in items.iter().enumerate() {
+            rank[p] = Some(j);
+        }
+        let falling = |r: usize, j: usize| (j as u128 + 1..=r as u128).fold(1u128, |a, x| a.saturating_mul(x));
+        let ratio = (0..=RATIO_MAX)
+            .map(|r| (0..=RATIO_MAX).map(|j| if j > r { 0 } else { falling(r, j) }).collect())
+            .collect();
+        Ok(Self { rank, preds, extensions, ratio })
+    }
+
+    fn restricted(&self) -> usize {
+        self.preds.len()
+    }
+
+    // Completacoes com `r` itens restantes, dos quais `set` sao os restritos
+    fn completions(&self, r: usize, set: usize) -> u128 {
+        self.ratio[r][set.count_ones() as usize].saturating_mul(self.extensions[set])
+    }
+
+    /// Ordens das `m` palavras permutadas que respeitam as restricoes.
+    pub fn count(&self, m: usize) -> u128 {
+        self.completions(m, (1 << self.restricted()) - 1)
+    }
+
+    /// Ordem `index` (abaixo de `count`) de `items` entre as que respeitam as restricoes.
+    pub fn permutation_at_index(&self, items: &[u16], index: u128) -> Vec<u16> {
+        let mut result = Vec::with_capacity(items.len());
+        self.permutation_at_index_into(items, index, &mut result);
+        result
+    }
+
+    /// `permutation_at_index` escrita em `out` (esvaziado antes).
+    pub fn permutation_at_index_into(&self, items: &[u16], mut index: u128, out: &mut Vec<u16>) {
+        let m = items.len();
+        debug_assert!(m <= RATIO_MAX, "tabela suporta no maximo 32 itens");
+
+        out.clear();
+        let mut used: u64 = 0;
+        let mut left = (1usize << self.restricted()) - 1; // restritos ainda nao colocados
+        for r in (1..=m).rev() {
+            for (pos, &item) in items.iter().enumerate() {
+                if used & (1 << pos) != 0 {
+                    continue;
+                }
+                let rest = match self.rank.get(pos).copied().flatten() {
+                    // Restrito: so com todos os predecessores ja colocados
+                    Some(j) if self.preds[j] & left as u32 != 0 => continue,
+                    Some(j) => left & !(1 << j),
+                    None    => left,
+                };
+                let branch = self.completions(r - 1, rest);
+                if index < branch {
+                    used |= 1 << pos;
+                    left = rest;
+                    out.push(item);
+                    break;
+                }
+                index -= branch;
+            }
+        }
+    }
+}
+
+// ── Palavras repetidas (multiconjunto) ───────────────────────────────────────
+//
+// Com palavras repetidas o fatoradico gera a mesma ordem varias vezes (duas
+// iguais: metade dos indices e repeticao). Aqui so as ordens distintas sao
+// numeradas, m!/∏c! para c copias de cada palavra. Como no fatoradico, cada
+// posicao tenta os itens na ordem informada, mas so a primeira copia ainda
+// livre de cada palavra abre um ramo: escolher uma palavra com c copias entre
+// r restantes deixa T·c/r das T completacoes. O indice 0 e a ordem informada.
+#[derive(Debug, Clone)]
+pub struct Multiset {
+    /// Posicoes (bits) com a mesma palavra de cada posicao, ela inclusive
+    same:  Vec<u64>,
+    count: u128,
+}
+
+impl Multiset {
+    /// `None` quando todos os itens sao distintos: o fatoradico ja nao repete.
+    ///
+    /// ```
+    /// use brute_force_mnemonics::permutation::Multiset;
+    ///
+    /// let items = [7, 3, 7];
+    /// let orders = Multiset::new(&items).unwrap();
+    /// assert_eq!(orders.count(), 3);
+    /// assert_eq!(orders.permutation_at_index(&items, 0), vec![7, 3, 7]);
+    /// assert_eq!(orders.permutation_at_index(&items, 1), vec![7, 7, 3]);
+    /// assert_eq!(orders.permutation_at_index(&items, 2), vec![3, 7, 7]);
+    /// assert_eq!(orders.rank(&items, &[7, 7, 3]), Some(1));
+    /// assert!(Multiset::new(&[1, 2, 3]).is_none());
+    /// ```
+    pub fn new(items: &[u16]) -> Option<Self> {
+        let n = items.len();
+        debug_assert!(n <= 64, "bitmask suporta no maximo 64 itens");
+        let same: Vec<u64> = items.iter()
+            .map(|&w| (0..n).filter(|&p| items[p] == w).fold(0u64, |m, p| m | 1 << p))
+            .collect();
+        if same.iter().all(|m| m.count_ones() == 1) {
+            return None;
+        }
+        // Cada palavra com c copias divide por c!, contada uma vez (na primeira copia)
+        let count = same.iter().enumerate()
+            .filter(|&(p, m)| m.trailing_zeros() as usize == p)
+            .fold(factorial(n as u128), |acc, (_, m)| acc / factorial(m.count_ones() as u128));
+        Some(Self { same, count })
+    }
+
+    /// Ordens distintas dos itens.
+    pub fn count(&self) -> u128 {
+        self.count
+    }
+
+    // Ramo de `pos` com `used` ja colocados: `Some(T·c/r)` se for a primeira copia livre
+    fn branch(&self, pos: usize, used: u64, total: u128, r: usize) -> Option<u128> {
+        let left = self.same[pos] & !used;
+        if used & (1 << pos) != 0 || left & ((1 << pos) - 1) != 0 {
+            return None;
+        }
+        Some(total * left.count_ones() as u128 / r as u128)
+    }
+
+    /// Ordem distinta `index` (abaixo de `count`) de `items`, os mesmos de `new`.
+    pub fn permutation_at_index(&self, items: &[u16], index: u128) -> Vec<u16> {
+        let mut result = Vec::with_capacity(items.len());
+        self.permutation_at_index_into(items, index, &mut result);
+        result
+    }
+
+    /// `permutation_at_index` escrita em `out` (esvaziado antes).
+    pub fn permutation_at_index_into(&self, items: &[u16], mut index: u128, out: &mut Vec<u16>) {
+        out.clear();
+        let mut used: u64 = 0;
+        let mut total = self.count;
+        for r in (1..=items.len()).rev() {
+            for (pos, &item) in items.iter().enumerate() {
+                let Some(branch) = self.branch(pos, used, total, r) else { continue };
+                if index < branch {
+                    used |= 1 << pos;
+                    total = branch;
+                    out.push(item);
+                    break;
+                }
+                index -= branch;
+            }
+        }
+    }
+
+    /// Indice da ordem `perm` de `items`, ou `None` quando `perm` nao e uma ordem de `items`.
+    pub fn rank(&self, items: &[u16], perm: &[u16]) -> Option<u128> {
+        if perm.len() != items.len() {
+            return None;
+        }
+        let mut used: u64 = 0;
+        let mut total = self.count;
+        let mut index: u128 = 0;
+        for (r, &word) in (1..=items.len()).rev().zip(perm) {
+            let at = (0..items.len()).find(|&p| used & (1 << p) == 0 && items[p] == word)?;
+            for pos in 0..at {
+                index += self.branch(pos, used, total, r).unwrap_or(0);
+            }
+            total = self.branch(at, used, total, r)?;
+            used |= 1 << at;
+        }
+        Some(index)
+    }
+}
+
+// ── Embaralhamento bijetor de [0, domain) ────────────────────────────────────
+//
+// Rede de Feistel balanceada sobre o menor numero par de bits que cobre o
+// dominio, com "cycle walking": reaplica ate cair dentro de [0, domain). Cada
+// indice continua mapeando para exatamente um candidato, entao checkpoint e
+// --shard valem como na ordem normal.
+#[derive(Debug, Clone, Copy)]
+pub struct Shuffle {
+    domain: u128,
+    half:   u32,
+}
+
+const SHUFFLE_ROUNDS: u64 = 4;
+
+// SplitMix64
+fn mix(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+impl Shuffle {
+    pub fn new(domain: u128) -> Self {
+        let bits = 128 - domain.saturating_sub(1).leading_zeros();
+        Self { domain, half: bits.div_ceil(2).max(1) }
+    }
+
+    fn round_trip(&self, x: u128) -> u128 {
+        let mask = (1u128 << self.half) - 1;
+        let (mut l, mut r) = ((x >> self.half) as u64, (x & mask) as u64);
+        for round in 0..SHUFFLE_ROUNDS {
+            let f = mix(r ^ mix(round)) & mask as u64;
+            (l, r) = (r, l ^ f);
+        }
+        ((l as u128) << self.half) | r as u128
+    }
+
+    pub fn apply(&self, index: u128) -> u128 {
+        if self.domain <= 1 {
+            return index;
+        }
+        let mut x = self.round_trip(index);
+        while x >= self.domain {
+            x = self.round_trip(x);
+        }
+        x
+    }
+}
+
+// ── Posicoes fixas e palavras faltando ("?") ─────────────────────────────────
+//
+// Cada posicao da frase e livre (recebe uma das palavras permutadas), fixa
+// (--fixed POS=WORD) ou uma lacuna "?" (testa as 2048 palavras). Com m
+// palavras livres e k lacunas, o indice combina a ordem das livres e o
+// preenchimento das lacunas:  idx = perm * 2048^k + preenchimento
+// O preenchimento varia mais rapido, entao os primeiros 2048^k indices testam
+// a ordem informada — o caso comum de quem sabe a ordem mas perdeu palavras.
+// Uma lacuna na ultima posicao pode ser `Checksum`: so os 11 - n/3 bits de
+// entropia variam e o checksum e calculado, nao testado (128 em vez de 2048).
+pub const WILDCARD: &str = "?";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slot {
+    Free,
+    Fixed(u16),
+    Missing,
+    /// Ultima posicao faltando, preenchida so com palavras de checksum valido
+    Checksum,
+}
+
+/// Candidatos para `free` palavras permutadas e `missing` lacunas.
+pub fn candidate_count(free: usize, missing: usize) -> u128 {
+    (0..missing).fold(factorial(free as u128), |acc, _| acc.saturating_mul(2048))
+}
+
+// Valores que a lacuna assume: 2048, ou 2^(11 - n/3) na ultima com checksum
+fn slot_radix(slot: Slot, n: usize) -> u128 {
+    match slot {
+        Slot::Missing  => 2048,
+        Slot::Checksum => 1 << (11 - n / 3),
+        _              => 1,
+    }
+}
+
+/// Preenchimentos das lacunas de `slots` (o produto dos valores de cada uma).
+pub fn fill_count(slots: &[Slot]) -> u128 {
+    slots.iter().fold(1u128, |acc, &s| acc.saturating_mul(slot_radix(s, slots.len())))
+}
+
+/// Frase (como indices) do candidato `index`; `free` sao as palavras permutadas
+/// nas posicoes `Slot::Free` de `slots`, na ordem dada por `permute` (ex.:
+/// `permutation_at_index`, ou `Order::permutation`).
+pub fn candidate_at_index(
+    free: &[u16],
+    slots: &[Slot],
+    index: u128,
+    permute: impl Fn(&[u16], u128) -> Vec<u16>,
+) -> Vec<u16> {
+    let mut phrase = Vec::with_capacity(slots.len());
+    candidate_at_index_into(free, slots, index, |f, i, out| *out = permute(f, i), &mut phrase);
+    phrase
+}
+
+/// `candidate_at_index` escrita em `out`, com `permute` tambem escrevendo no
+/// buffer (ex.: `permutation_at_index_into`): sem alocacao por candidato.
+pub fn candidate_at_index_into(
+    free: &[u16],
+    slots: &[Slot],
+    mut index: u128,
+    permute: impl Fn(&[u16], u128, &mut Vec<u16>),
+    out: &mut Vec<u16>,
+) {
+    if free.len() == slots.len() {
+        return permute(free, index, out);
+    }
+
+    // Digitos do preenchimento (ultima lacuna = digito menos significativo)
+    let n = slots.len();
+    let mut fill = [0u16; 32];
+    for (i, slot) in slots.iter().enumerate().rev() {
+        let radix = slot_radix(*slot, n);
+        if radix > 1 {
+            fill[i] = (index % radix) as u16;
+            index /= radix;
+        }
+    }
+
+    // As livres ordenadas ocupam o inicio de `out`; espalhadas de tras para
+    // frente, cada uma so vai para a propria posicao ou adiante dela
+    permute(free, index, out);
+    let mut ordered = out.len();
+    out.resize(n, 0);
+    for (i, slot) in slots.iter().enumerate().rev() {
+        out[i] = match *slot {
+            Slot::Free if ordered > 0 => { ordered -= 1; out[ordered] }
+            Slot::Free     => 0,
+            Slot::Fixed(w) => w,
+            Slot::Missing  => fill[i],
+            Slot::Checksum => fill[i] << (n / 3),
+        };
+    }
+    if slots.last() == Some(&Slot::Checksum) {
+        set_checksum(out);
+    }
+}
+
+// ── Espaco de candidatos ─────────────────────────────────────────────────────
+//
+// Palavras livres com varias grafias possiveis (--fuzzy, prefixos) e posicoes
+// com varias palavras candidatas (--tokens) entram como uma escolha entre
+// alternativas, que varia entre o preenchimento das lacunas e a permutacao:
+//   idx = (perm * C + escolha) * 2048^k + preenchimento
+// onde C e o produto do numero de alternativas de cada escolha.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Choice {
+    /// Palavra permutada (indice em `free`)
+    Free(usize),
+    /// Posicao conhecida da frase (Slot::Fixed)
+    Position(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct Candidates {
+    /// Palavras permutadas (para as incertas, a primeira alternativa)
+    pub free:      Vec<u16>,
+    pub slots:     Vec<Slot>,
+    pub order:     Order,
+    /// Alvo e alternativas de cada escolha
+    pub choices:   Vec<(Choice, Vec<u16>)>,
+    /// So permutacoes a ate K trocas da ordem informada (substitui `order`)
+    pub max_swaps: Option<usize>,
+    /// So permutacoes com a ordem relativa conhecida (substitui `order`)
+    pub precedence: Option<Precedence>,
+    /// So as ordens distintas de palavras repetidas (substitui `order`)
+    pub multiset:   Option<Multiset>,
+}
+
+impl Candidates {
+    /// Combinacoes de grafias (C).
+    pub fn choice_count(&self) -> u128 {
+        self.choices.iter().fold(1u128, |acc, (_, alts)| acc.saturating_mul(alts.len() as u128))
+    }
+
+    /// Total de candidatos: m! (ou as permutacoes a ate K trocas, as que
+    /// respeitam a ordem relativa, ou as distintas) · C · 2048^k (a ultima
+    /// com checksum conta 2^(11 - n/3)).
+    pub fn count(&self) -> u128 {
+        let m = self.free.len();
+        let perms = match (&self.precedence, self.max_swaps, &self.multiset) {
+            (Some(p), _, _)         => p.count(m),
+            (None, Some(k), _)      => swap_permutation_count(m, k),
+            (None, None, Some(set)) => set.count(),
+            (None, None, None)      => factorial(m as u128),
+        };
+        fill_count(&self.slots).saturating_mul(perms).saturating_mul(self.choice_count())
+    }
+
+    fn permute(&self, items: &[u16], index: u128, out: &mut Vec<u16>) {
+        match (&self.precedence, self.max_swaps, &self.multiset) {
+            (Some(p), _, _)         => p.permutation_at_index_into(items, index, out),
+            (None, Some(k), _)      => swap_permutation_at_index_into(items, k, index, out),
+            (None, None, Some(set)) => set.permutation_at_index_into(items, index, out),
+            (None, None, None)      => self.order.permutation_into(items, index, out),
+        }
+    }
+
+    /// Ordena as palavras livres pela wordlist (--order lexicographic).
+    pub fn sort_free(&mut self) {
+        let mut order: Vec<usize> = (0..self.free.len()).collect();
+        order.sort_by_key(|&i| self.free[i]);
+        self.free = order.iter().map(|&i| self.free[i]).collect();
+        for (choice, _) in &mut self.choices {
+            if let Choice::Free(at) = choice {
+                *at = order.iter().position(|&i| i == *at).unwrap_or(*at);
+            }
+        }
+    }
+
+    /// Frase (como indices) do candidato `index`.
+    pub fn at(&self, index: u128) -> Vec<u16> {
+        let mut phrase = Vec::with_capacity(self.slots.len());
+        self.at_into(index, &mut phrase);
+        phrase
+    }
+
+    /// `at` escrita em `out`, para o laco da busca reaproveitar um buffer por thread.
+    pub fn at_into(&self, index: u128, out: &mut Vec<u16>) {
+        let permute = |f: &[u16], i: u128, out: &mut Vec<u16>| self.permute(f, i, out);
+        if self.choices.is_empty() {
+            return candidate_at_index_into(&self.free, &self.slots, index, permute, out);
+        }
+        let fill_radix = fill_count(&self.slots);
+        let (rest, fill) = (index / fill_radix, index % fill_radix);
+        let c = self.choice_count();
+        let (perm, mut choice) = (rest / c, rest % c);
+
+        // Digitos da escolha (ultima escolha = digito menos significativo); as
+        // palavras livres sao trocadas antes de permutar, as posicoes depois
+        let mut picks = [0u16; 32];
+        let picks = &mut picks[..self.choices.len()];
+        for (pick, (_, alts)) in picks.iter_mut().zip(&self.choices).rev() {
+            let n = alts.len() as u128;
+            *pick = alts[(choice % n) as usize];
+            choice /= n;
+        }
+        let mut free = [0u16; 32];
+        let free = &mut free[..self.free.len()];
+        free.copy_from_slice(&self.free);
+        for (&pick, (target, _)) in picks.iter().zip(&self.choices) {
+            if let Choice::Free(at) = *target {
+                free[at] = pick;
+            }
+        }
+        candidate_at_index_into(free, &self.slots, perm * fill_radix + fill, permute, out);
+        for (&pick, (target, _)) in picks.iter().zip(&self.choices) {
+            if let Choice::Position(pos) = *target {
+                out[pos] = pick;
+            }
+        }
+    }
+}
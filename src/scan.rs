@@ -0,0 +1,86 @@
+// ── Varredura de caminhos de uma chave mestra (scan-xprv, path-scan) ─────────
+//
+// Sem frase para achar: a chave mestra e conhecida (ou sai de uma frase
+// conhecida) e o que falta e o caminho: purpose, coin type, conta, cadeia e
+// indice. O tipo do script vem do alvo, mas o purpose varia: carteiras
+// quebradas puseram SegWit em m/44' e tambem ha caminhos sem purpose
+// (m/conta'/cadeia/indice). Cada tipo de script vira um Matcher com todos os
+// caminhos da grade: os indices de uma cadeia reaproveitam a chave do pai.
+
+use crate::derivation::AddressType;
+use crate::target::Matcher;
+use anyhow::{Context, Result};
+use bitcoin::bip32::{DerivationPath, Xpriv};
+use bitcoin::ScriptBuf;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// The grid of paths tried below a known master key.
+#[derive(Debug, Clone)]
+pub struct PathScan {
+    /// Script types derived (from the target prefixes)
+    pub address_types: Vec<AddressType>,
+    /// Purpose levels tried, `None` for paths without one (m/account'/chain/index)
+    pub purposes:      Vec<Option<u32>>,
+    pub coin_types:    Vec<u32>,
+    pub accounts:      Range<u32>,
+    pub chains:        Vec<u32>,
+    pub indices:       Range<u32>,
+}
+
+impl PathScan {
+    /// Every path of the grid for `address_type`, its own purpose first. Without a
+    /// purpose there is no coin type level either.
+    pub fn paths(&self, address_type: AddressType) -> Result<Vec<DerivationPath>> {
+        let mut purposes = self.purposes.clone();
+        purposes.sort_by_key(|p| *p != Some(address_type.purpose()));
+        let mut accounts = Vec::new();
+        for purpose in purposes {
+            match purpose {
+                Some(p) => accounts.extend(self.coin_types.iter().flat_map(|c| {
+                    self.accounts.clone().map(move |a| format!("m/{}'/{}'/{}'", p, c, a))
+                })),
+                None => accounts.extend(self.accounts.clone().map(|a| format!("m/{}'", a))),
+            }
+        }
+        let mut paths = Vec::with_capacity(self.paths_per_type());
+        for account in &accounts {
+            for &chain in &self.chains {
+                for index in self.indices.clone() {
+                    let path = format!("{}/{}/{}", account, chain, index);
+                    paths.push(DerivationPath::from_str(&path)
+                        .with_context(|| format!("Invalid derivation path '{}'", path))?);
+                }
+            }
+        }
+        Ok(paths)
+    }
+
+    fn paths_per_type(&self) -> usize {
+        let purposes = self.purposes.iter().map(|p| if p.is_some() { self.coin_types.len() } else { 1 }).sum::<usize>();
+        purposes * self.accounts.len() * self.chains.len() * self.indices.len()
+    }
+
+    /// Addresses derived in a full scan.
+    pub fn total(&self) -> usize {
+        self.address_types.len() * self.paths_per_type()
+    }
+}
+
+/// First path of `scan` (address types in order) whose script is one of `targets`.
+pub fn scan_master(
+    master: &Xpriv,
+    targets: &[ScriptBuf],
+    scan: &PathScan,
+) -> Result<Option<(AddressType, ScriptBuf, DerivationPath)>> {
+    if scan.paths_per_type() == 0 {
+        anyhow::bail!("Nothing to scan: the purposes, coin types, accounts, chains and indices must not be empty");
+    }
+    for &address_type in &scan.address_types {
+        let matcher = Matcher::new(targets.to_vec(), None, address_type, scan.paths(address_type)?);
+        if let Some((script, path)) = matcher.check_master(master) {
+            return Ok(Some((address_type, script, path)));
+        }
+    }
+    Ok(None)
+}
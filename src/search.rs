@@ -0,0 +1,1445 @@
+// ── Motor de busca ───────────────────────────────────────────────────────────
+//
+// Por permutacao:
+//   [1] Candidates::at_into  — u16 + bitmask num buffer por thread, sem alocacao
+//                              (lacunas "?" preenchidas com as 2048 palavras)
+//   [2] checksum_valid       — empacota bits + SHA256 (~1µs) — rejeita a maioria
+//   [3] reconstroi phrase    — so para os ~1/16 ou ~1/256 que passaram
+//   [4] PBKDF2               — gargalo real (~1ms), mas chamado raramente
+//   [5] BIP32 + endereco     — comparado com os alvos
+//
+// Os indices vao em janelas, e cada janela passa por tres estagios em
+// pipeline ([1]..[3], [4], [5]; ver `scan`). A derivacao termina as janelas
+// em ordem: todo indice < `next` foi testado, o que torna o checkpoint exato.
+// A janela e dimensionada para que ~batch frases passem no checksum. Dentro
+// dela os indices vao em blocos pequenos (~CHUNK_VALID frases validas cada)
+// que os threads ociosos roubam da fila do Rayon: a densidade de frases
+// validas varia entre regioes, e blocos grandes deixariam threads parados no
+// fim de cada janela esperando o mais lento.
+
+use crate::audit::{AuditLog, AuditRow};
+use crate::bip85::{self, Child, Children};
+use crate::checkpoint::{self, Checkpoint, CheckpointInterval};
+use crate::coin::Coin;
+use crate::derivation::{derive_pubkey, master_fingerprint, AddressType};
+use crate::gpu::{self, SeedBackend};
+use crate::history::HistoryBackend;
+use crate::mnemonic::{
+    build_phrase, checksum_valid, display_phrase, find_word, fuzzy_matches, language_name, normalize,
+    prefix_matches, word_index_table, WORD_COUNTS,
+};
+use crate::bloom::BloomTargets;
+use crate::multisig::{Multisig, Template};
+use crate::passphrase::{PassphraseMask, Passphrases};
+use crate::pbkdf2;
+use crate::permutation::{Candidates, Choice, Multiset, Order, Precedence, Shuffle, Slot, WILDCARD};
+use crate::target::Matcher;
+use anyhow::{Context, Result};
+use bip39::Language;
+use bitcoin::bip32::{ChildNumber, DerivationPath, Xpub};
+use bitcoin::hex::DisplayHex;
+use bitcoin::secp256k1::PublicKey;
+use bitcoin::ScriptBuf;
+use rayon::prelude::*;
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, BufRead, Write};
+use std::ops::Range;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Marcador do indice no --path customizado
+pub const INDEX_PLACEHOLDER: &str = "{index}";
+
+// Intervalo padrao entre gravacoes do checkpoint (--checkpoint-interval)
+pub const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(30);
+
+// Frases checksum-validas por thread (CPU) em cada janela
+const CPU_BATCH_PER_THREAD: u64 = 1024;
+
+// Janelas prontas esperando entre dois estagios do pipeline
+const PIPELINE_DEPTH: usize = 1;
+
+// Frases checksum-validas esperadas por bloco roubavel (~0.1s de PBKDF2)
+const CHUNK_VALID: u64 = 64;
+
+/// Parameters of a permutation search.
+#[derive(Debug, Clone)]
+pub struct SearchConfig {
+    /// scriptPubKeys of the addresses the correct mnemonic may derive to (a hit on any of them is a match)
+    pub targets: Vec<ScriptBuf>,
+    /// Master or account-level extended public key (m or m/purpose'/coin'/0') to match instead of addresses
+    pub xpub: Option<Xpub>,
+    /// Known words, in any order (12, 15, 18, 21 or 24); `?` marks a missing word at that position
+    pub words: Vec<String>,
+    /// Wordlist the words belong to
+    pub language: Language,
+    /// Derivation preset (BIP44/49/84/86)
+    pub address_type: AddressType,
+    /// Coin: sets the coin type of the preset paths and how addresses are encoded
+    pub coin: Coin,
+    /// Coin type of the preset paths in place of the coin's SLIP-44 one (e.g. Bitcoin under `145'`)
+    pub coin_type: Option<u32>,
+    /// BIP-39 passphrase ("25th word"); empty for none
+    pub passphrase: String,
+    /// Candidate passphrases, each tried with every mnemonic (overrides `passphrase` when non-empty)
+    pub passphrases: Vec<String>,
+    /// Hashcat-style mask generating the candidate passphrases (overrides `passphrases`)
+    pub passphrase_mask: Option<PassphraseMask>,
+    /// Custom derivation path overriding the preset (e.g. `m/0'/0/{index}`);
+    /// `{index}` is replaced by each of `derivation_indices`
+    pub path: Option<String>,
+    /// Chains checked for every seed: 0 = receive (external), 1 = change (internal)
+    pub chains: Vec<u32>,
+    /// Address indices (last path component) checked on every chain
+    pub derivation_indices: Range<u32>,
+    /// First permutation index to test
+    pub start_index: u64,
+    /// Words pinned to a position (0-based); each is taken out of `words` and not permuted
+    pub fixed: Vec<(usize, String)>,
+    /// Positions (0-based) holding one of several candidate words, all combinations tried;
+    /// like `fixed`, each takes its first candidate out of `words`
+    pub choices: Vec<(usize, Vec<String>)>,
+    /// Permuted words (index into `words`) standing for one of several words, each tried
+    /// in every order; the word at the index must be the first of them
+    pub alternatives: Vec<(usize, Vec<String>)>,
+    /// Order in which the permutations are visited
+    pub order: Order,
+    /// Only try orders at most this many word swaps away from `words`, nearest first
+    pub max_swaps: Option<usize>,
+    /// Chains of words known to appear in this relative order (e.g. `["hotel", "cabin"]`:
+    /// hotel somewhere before cabin); orders breaking them are never generated
+    pub before: Vec<Vec<String>>,
+    /// Words not in the wordlist are replaced by every word within this edit distance (0 = off)
+    pub fuzzy: usize,
+    /// Treat every word as an abbreviation: only its first 4 letters are matched
+    pub prefix_mode: bool,
+    /// A `?` in the last position takes only the 2^(11 - n/3) words completing the checksum,
+    /// computed rather than tested (128 instead of 2048 with 12 words)
+    pub checksum_last: bool,
+    /// P2PKH addresses from uncompressed public keys, as very old wallets used (BIP44 only)
+    pub uncompressed: bool,
+    /// Also derive the preset paths the way broken wallets did: all levels hardened,
+    /// none hardened, and without the account level
+    pub path_variants: bool,
+    /// The targets are multisig addresses holding the derived key and these cosigners' keys;
+    /// the preset account becomes the multisig one (BIP48, or BIP45 for P2SH)
+    pub multisig: Option<Multisig>,
+    /// Only test permutation indices below this bound (default: all)
+    pub max_permutations: Option<u64>,
+    /// `(k, n)`: only test the indices `i` with `i % n == k` (split a job across machines)
+    pub shard: Option<(u64, u64)>,
+    /// Keep searching after a match instead of stopping at the first one
+    pub find_all: bool,
+    /// Derive every address type the coin supports (BIP44/49/84/86) from each seed,
+    /// sharing the PBKDF2 cost, instead of only `address_type`
+    pub all_types: bool,
+    /// Also count as a match any derived address with on-chain history (the targets may then be empty)
+    pub history: Option<Arc<dyn HistoryBackend>>,
+    /// More targets, too many for `targets`: a Bloom filter over a file (the targets may then be empty)
+    pub bloom: Option<Arc<BloomTargets>>,
+    /// Also check the BIP85 child mnemonics of every seed (their own seeds, without a passphrase,
+    /// on the same paths) when the seed itself does not match
+    pub bip85: Option<Children>,
+}
+
+impl SearchConfig {
+    pub fn new(
+        targets: Vec<ScriptBuf>,
+        words: Vec<String>,
+        language: Language,
+        address_type: AddressType,
+    ) -> Self {
+        Self {
+            targets,
+            xpub:               None,
+            words,
+            language,
+            address_type,
+            coin:               Coin::Bitcoin,
+            coin_type:          None,
+            passphrase:         String::new(),
+            passphrases:        Vec::new(),
+            passphrase_mask:    None,
+            path:               None,
+            chains:             vec![0],
+            derivation_indices: 0..1,
+            start_index:        0,
+            fixed:              Vec::new(),
+            choices:            Vec::new(),
+            alternatives:       Vec::new(),
+            order:              Order::OriginalFirst,
+            max_swaps:          None,
+            before:             Vec::new(),
+            fuzzy:              0,
+            prefix_mode:        false,
+            checksum_last:      false,
+            uncompressed:       false,
+            path_variants:      false,
+            multisig:           None,
+            max_permutations:   None,
+            shard:              None,
+            find_all:           false,
+            all_types:          false,
+            history:            None,
+            bloom:              None,
+            bip85:              None,
+        }
+    }
+
+    /// Coin type dos caminhos preset: o de --coin-type, ou o da moeda.
+    pub fn coin_type(&self) -> u32 {
+        self.coin_type.unwrap_or_else(|| self.coin.coin_type())
+    }
+
+    /// Conta do preset para a moeda (m/purpose'/coin'/0', ou a do multisig).
+    pub fn account_path(&self) -> String {
+        match &self.multisig {
+            Some(m) => m.script_type.account_path(self.coin_type()),
+            None    => self.address_type.account_path(self.coin_type()),
+        }
+    }
+
+    /// Tipos derivados por seed: so `address_type`, ou todos os da moeda com `all_types`.
+    pub fn address_types(&self) -> Vec<AddressType> {
+        if !self.all_types {
+            return vec![self.address_type];
+        }
+        AddressType::ALL.into_iter().filter(|t| self.coin.supports(*t)).collect()
+    }
+
+    /// Caminho de derivacao; com varias cadeias/indices, "m/.../{0,1}/A..B".
+    pub fn derivation_path_string(&self) -> String {
+        let r = &self.derivation_indices;
+        let indices = if r.len() == 1 { r.start.to_string() } else { format!("{}..{}", r.start, r.end) };
+        if let Some(template) = &self.path {
+            return template.replace(INDEX_PLACEHOLDER, &indices);
+        }
+        let chains = match self.chains.as_slice() {
+            [c] => c.to_string(),
+            cs  => format!("{{{}}}", cs.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(",")),
+        };
+        let account = match self.address_types().as_slice() {
+            [_] => self.account_path(),
+            ts  => {
+                let purposes: Vec<String> = ts.iter().map(|t| t.purpose().to_string()).collect();
+                format!("m/{{{}}}'/{}'/0'", purposes.join(","), self.coin_type())
+            }
+        };
+        format!("{}/{}/{}", account, chains, indices)
+    }
+
+    /// Todos os caminhos checados por seed, agrupados pelo pai (cadeia, depois indice).
+    pub fn derivation_paths(&self) -> Result<Vec<DerivationPath>> {
+        self.derivation_paths_for(self.address_type)
+    }
+
+    /// Como `derivation_paths`, com o preset de `address_type`.
+    pub fn derivation_paths_for(&self, address_type: AddressType) -> Result<Vec<DerivationPath>> {
+        let account = match &self.multisig {
+            Some(m) => m.script_type.account_path(self.coin_type()),
+            None    => address_type.account_path(self.coin_type()),
+        };
+        let parse = |p: &str| DerivationPath::from_str(p)
+            .with_context(|| format!("Invalid derivation path '{}'", p));
+        match &self.path {
+            Some(template) if template.contains(INDEX_PLACEHOLDER) => self.derivation_indices.clone()
+                .map(|i| parse(&template.replace(INDEX_PLACEHOLDER, &i.to_string())))
+                .collect(),
+            Some(template) => Ok(vec![parse(template)?]),
+            None => self.preset_accounts(&account).iter()
+                .flat_map(|(account, h)| self.chains.iter()
+                    .flat_map(|&c| self.derivation_indices.clone().map(move |i| (c, i)))
+                    .map(move |(c, i)| format!("{}/{}{}/{}{}", account, c, h, i, h)))
+                .map(|p| parse(&p))
+                .collect(),
+        }
+    }
+
+    /// Posicoes marcadas com `?`.
+    pub fn wildcard_positions(&self) -> Vec<usize> {
+        self.words.iter().enumerate()
+            .filter(|(_, w)| w.as_str() == WILDCARD)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    // (conta, marca de cadeia/indice) do preset e, com path_variants, das variantes
+    // malformadas: tudo endurecido, nada endurecido e sem o nivel da conta
+    fn preset_accounts(&self, account: &str) -> Vec<(String, &'static str)> {
+        let mut accounts = vec![(account.to_string(), "")];
+        if self.path_variants {
+            accounts.push((account.to_string(), "'"));
+            accounts.push((account.replace('\'', ""), ""));
+            if let Some((parent, _)) = account.rsplit_once('/') {
+                accounts.push((parent.to_string(), ""));
+            }
+        }
+        accounts
+    }
+
+    // Alguma palavra conhecida aparece mais de uma vez
+    fn repeated_words(&self) -> bool {
+        let known: Vec<&String> = self.words.iter().filter(|w| w.as_str() != WILDCARD).collect();
+        known.iter().enumerate().any(|(i, w)| known[..i].contains(w))
+    }
+
+    /// Digest gravado no checkpoint: muda sempre que o espaco de busca muda.
+    pub fn checkpoint_digest(&self) -> String {
+        checkpoint::config_digest(&[
+            &self.targets.iter().map(|t| t.as_bytes().to_lower_hex_string()).collect::<Vec<_>>().join(","),
+            &self.xpub.map(|x| x.to_string()).unwrap_or_default(),
+            &self.words.join(" "),
+            &self.fixed.iter().map(|(p, w)| format!("{}={}", p, w)).collect::<Vec<_>>().join(" "),
+            &self.choices.iter().map(|(p, ws)| format!("{}={}", p, ws.join(","))).collect::<Vec<_>>().join(" "),
+            &self.alternatives.iter().map(|(i, ws)| format!("{}~{}", i, ws.join(","))).collect::<Vec<_>>().join(" "),
+            language_name(self.language),
+            &self.passphrase,
+            &self.passphrases.join("\n"),
+            self.passphrase_mask.as_ref().map_or("", |m| m.as_str()),
+            &self.derivation_path_string(),
+            self.address_type.name(),
+            self.coin.name(),
+            &self.shard.map(|(k, n)| format!("{}/{}", k, n)).unwrap_or_default(),
+            self.order.name(),
+            &self.max_swaps.map(|k| k.to_string()).unwrap_or_default(),
+            &self.before.iter().map(|c| c.join(":")).collect::<Vec<_>>().join(" "),
+            &self.fuzzy.to_string(),
+            if self.prefix_mode { "prefix" } else { "" },
+            if self.checksum_last { "checksum-last" } else { "" },
+            if self.uncompressed { "uncompressed" } else { "" },
+            if self.path_variants { "path-variants" } else { "" },
+            &self.multisig.as_ref().map(|m| format!("{} {}-of-{} {}{}", m.script_type.name(), m.quorum, m.key_count(),
+                m.cosigners.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(","),
+                // sortedmulti fica de fora, como antes do --witness-script
+                Some(m.template).filter(|t| *t != Template::SortedMulti).map(|t| format!(" {:?}", t)).unwrap_or_default()))
+                .unwrap_or_default(),
+            if self.all_types { "all-types" } else { "" },
+            &self.history.as_ref().map(|h| h.name()).unwrap_or_default(),
+            &self.bloom.as_ref().map(|b| format!("bloom {} {}", b.path().display(), b.len())).unwrap_or_default(),
+            &self.bip85.as_ref().map(|c| format!("bip85 {}..{} {:?} {}", c.indices.start, c.indices.end, c.word_counts,
+                language_name(c.language))).unwrap_or_default(),
+            // Palavras repetidas mudam a numeracao (so ordens distintas)
+            if self.repeated_words() { "distinct-orders" } else { "" },
+        ])
+    }
+}
+
+/// A mnemonic whose derived address (or account xpub) matches the target.
+#[derive(Debug, Clone)]
+pub struct Match {
+    pub phrase:     String,
+    pub passphrase: String,
+    pub index:      u64,
+    pub address:    String,
+    pub path:       DerivationPath,
+    /// BIP85 child of the mnemonic the address was derived from (`path` is then in the child's seed)
+    pub bip85:      Option<Child>,
+}
+
+/// Throughput measured by [`Search::calibrate`].
+#[derive(Debug, Clone, Copy)]
+pub struct Calibration {
+    /// Indices tested during the burst
+    pub tested:  u64,
+    pub elapsed: Duration,
+    /// The burst ran into a match (the search will find it again)
+    pub matched: bool,
+}
+
+impl Calibration {
+    /// Indices per second.
+    pub fn rate(&self) -> f64 {
+        self.tested as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// Running search; iterate it to obtain the matches.
+pub struct Search {
+    candidates:      Candidates,
+    corrections:     Vec<(String, Vec<&'static str>)>,
+    total:           u128,
+    wordlist:        &'static [&'static str; 2048],
+    language:        Language,
+    coin:            Coin,
+    // Um por tipo de endereco (varios so com all_types)
+    matchers:        Vec<Matcher>,
+    passphrases:     Passphrases,
+    filter_bits:     usize,
+    shuffle:         Option<Shuffle>,
+    // Frases vindas de fora (phrase_search), no lugar dos candidatos
+    lines:           Option<Mutex<Lines>>,
+    shard:           (u64, u64),
+    find_all:        bool,
+    bip85:           Option<Children>,
+
+    next:   u64,
+    end:    u64,
+    window: u64,
+
+    backend:    Option<Box<dyn SeedBackend>>,
+    checkpoint: Option<Checkpoint>,
+    save_every: CheckpointInterval,
+    last_saved: (Instant, u64),
+    audit:      Option<AuditLog>,
+
+    counter:  Arc<AtomicU64>,
+    // Candidatos checksum-validos (seeds derivadas)
+    valid:    Arc<AtomicU64>,
+    current:  Arc<AtomicU64>,
+    stop:     Arc<AtomicBool>,
+    pending:  VecDeque<Match>,
+    matched:  bool,
+    finished: bool,
+}
+
+/// Validates the configuration and prepares the search (nothing runs until iterated).
+pub fn search(config: SearchConfig) -> Result<Search> {
+    build(config, None)
+}
+
+/// Like [`search`], but tests the phrases read from `reader`, one mnemonic per line in
+/// `config.language`, instead of arranging `config.words` (ignored, as are the options
+/// that shape them). Line `i` (from 0) holds the indices `i·P .. i·P + P` for `P`
+/// passphrases; lines that are not a valid mnemonic are counted and skipped, and the end
+/// of the input ends the search. [`Search::calibrate`] and [`Search::examples`] do not
+/// apply: the lines they would read are not given back.
+pub fn phrase_search(config: SearchConfig, reader: impl BufRead + Send + 'static) -> Result<Search> {
+    build(config, Some(Box::new(reader)))
+}
+
+/// What [`emit`] wrote.
+#[derive(Debug, Clone, Copy)]
+pub struct Emitted {
+    /// Checksum-valid phrases written.
+    pub phrases: u64,
+    /// First index not covered (`end` once every index was).
+    pub next:    u64,
+    /// Last index to cover, exclusive.
+    pub end:     u64,
+}
+
+/// Writes the checksum-valid phrases [`search`] would derive for `config` to `out`, one per
+/// line in index order from `config.start_index` (in `config.shard`, up to
+/// `config.max_permutations`), and derives nothing: targets, paths and passphrases are not
+/// used. Stops after the current window when `stop` is set, or when `out` is closed (a
+/// broken pipe), with [`Emitted::next`] where to pick up.
+pub fn emit(mut config: SearchConfig, out: &mut dyn Write, stop: &AtomicBool) -> Result<Emitted> {
+    let n = config.words.len();
+    if !WORD_COUNTS.contains(&n) {
+        anyhow::bail!("Expected 12, 15, 18, 21 or 24 words, got {}", n);
+    }
+    let (k, shards) = config.shard.unwrap_or((0, 1));
+    if k >= shards {
+        anyhow::bail!("Invalid shard {}/{}: expected K/N with 0 <= K < N", k, shards);
+    }
+    let Arranged { candidates, filter_bits, shuffle, .. } = arrange(&mut config)?;
+    let end = config.max_permutations.unwrap_or(u64::MAX).min(candidates.count().min(u64::MAX as u128) as u64);
+    if config.start_index >= end && end > 0 {
+        anyhow::bail!("Start index {} is past the last index to test ({})", config.start_index, end - 1);
+    }
+
+    // Janelas como as da busca; cada uma escrita inteira, em ordem
+    let batch    = rayon::current_num_threads() as u64 * CPU_BATCH_PER_THREAD;
+    let window   = (batch << filter_bits).saturating_mul(shards);
+    let chunk    = (CHUNK_VALID << filter_bits).max(1) as usize;
+    let wordlist = config.language.word_list();
+    let mut emitted = Emitted { phrases: 0, next: config.start_index, end };
+    while emitted.next < end && !stop.load(Ordering::Relaxed) {
+        let start = emitted.next;
+        let upto  = start.saturating_add(window).min(end);
+        let phrases: Vec<String> = shard_indices((k, shards), start, upto).with_max_len(chunk)
+            .map_init(Vec::new, |perm, idx| {
+                candidates.at_into(split_index(idx, 1, shuffle).0, perm);
+                checksum_valid(perm).then(|| display_phrase(&build_phrase(perm, wordlist), config.language))
+            })
+            .flatten_iter()
+            .collect();
+        let mut text = phrases.join("\n");
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        // Quem le fechou a saida (`| head`): fim normal, a janela nao conta
+        match out.write_all(text.as_bytes()).and_then(|_| out.flush()) {
+            Err(e) if e.kind() == io::ErrorKind::BrokenPipe => break,
+            result => result.context("Could not write the candidate phrases")?,
+        }
+        emitted.phrases += phrases.len() as u64;
+        emitted.next     = upto;
+    }
+    Ok(emitted)
+}
+
+fn build(mut config: SearchConfig, reader: Option<Box<dyn BufRead + Send>>) -> Result<Search> {
+    let n = config.words.len();
+    if reader.is_none() && !WORD_COUNTS.contains(&n) {
+        anyhow::bail!("Expected 12, 15, 18, 21 or 24 words, got {}", n);
+    }
+    match (&config.xpub, config.targets.is_empty()) {
+        (None, true) if config.history.is_none() && config.bloom.is_none() => anyhow::bail!("No target address given"),
+        (Some(_), _) if config.history.is_some() => {
+            anyhow::bail!("An xpub target cannot be combined with an address history check")
+        }
+        (Some(_), false) => anyhow::bail!("Use either target addresses or an xpub, not both"),
+        (Some(x), true) if x.depth != 0 && (x.depth != 3 || x.child_number != ChildNumber::Hardened { index: 0 }) => {
+            anyhow::bail!("Expected a master key or an account-level xpub for m/purpose'/coin'/0' (depth 3, account 0')")
+        }
+        _ => {}
+    }
+    if !config.all_types && !config.coin.supports(config.address_type) {
+        anyhow::bail!("{} is not supported for {}", config.address_type.name(), config.coin.name());
+    }
+    if config.all_types && (config.xpub.is_some() || config.path.is_some()) {
+        anyhow::bail!("All address types cannot be combined with an xpub or a custom --path");
+    }
+    if config.uncompressed && (config.all_types || config.address_type != AddressType::Bip44) {
+        anyhow::bail!("Uncompressed public keys only exist with legacy P2PKH (BIP44) addresses");
+    }
+    if config.multisig.is_some() && (config.xpub.is_some() || config.all_types || config.uncompressed) {
+        anyhow::bail!("A multisig target cannot be combined with an xpub, all address types or uncompressed keys");
+    }
+    if config.bloom.is_some() && (config.xpub.is_some() || config.multisig.is_some()) {
+        anyhow::bail!("A Bloom filter of addresses cannot be combined with an xpub or a multisig target");
+    }
+    if config.path_variants && (config.xpub.is_some() || config.path.is_some() || config.multisig.is_some()) {
+        anyhow::bail!("Path variants apply to the single-key preset paths, not to an xpub, a custom --path or a multisig");
+    }
+    if let Some(children) = &config.bip85 {
+        if children.is_empty() || children.word_counts.iter().any(|w| !bip85::WORD_COUNTS.contains(w)) {
+            anyhow::bail!("BIP85 children need an index range and word counts among 12, 18 and 24");
+        }
+        if bip85::language_code(children.language).is_none() {
+            anyhow::bail!("BIP85 defines no code for {} child mnemonics", language_name(children.language));
+        }
+    }
+
+    // Candidatos: arranjos das palavras dadas, ou as linhas do leitor
+    let arranged = match &reader {
+        None    => arrange(&mut config)?,
+        Some(_) => Arranged {
+            candidates:  Candidates {
+                free: Vec::new(), slots: Vec::new(), order: config.order, choices: Vec::new(),
+                max_swaps: None, precedence: None, multiset: None,
+            },
+            corrections: Vec::new(),
+            filter_bits: 0,
+            shuffle:     None,
+        },
+    };
+
+    // Passphrases em NFKD (BIP-39), normalizadas uma vez para CPU e GPU
+    let passphrases = match (&config.passphrase_mask, config.passphrases.is_empty()) {
+        (Some(mask), _) => Passphrases::Mask(mask.clone()),
+        (None, true)    => Passphrases::List(vec![normalize(&config.passphrase)]),
+        (None, false)   => Passphrases::List(config.passphrases.iter().map(|p| normalize(p)).collect()),
+    };
+
+    if config.derivation_indices.is_empty() {
+        anyhow::bail!("Empty derivation index range {:?}", config.derivation_indices);
+    }
+    if config.chains.is_empty() {
+        anyhow::bail!("No derivation chain selected");
+    }
+    if let Some(template) = &config.path {
+        if config.chains != [0] {
+            anyhow::bail!("--chain does not apply to a custom --path");
+        }
+        if config.derivation_indices.len() > 1 && !template.contains(INDEX_PLACEHOLDER) {
+            anyhow::bail!("An index range needs the {} placeholder in the custom path", INDEX_PLACEHOLDER);
+        }
+    }
+    if let Some((k, n)) = config.shard {
+        if k >= n {
+            anyhow::bail!("Invalid shard {}/{}: expected K/N with 0 <= K < N", k, n);
+        }
+    }
+    let paths = config.derivation_paths()?;
+    if paths.iter().any(|p| p.is_empty()) {
+        anyhow::bail!("Derivation path must have at least one component after m/");
+    }
+    // Mesma seed (um PBKDF2) para todos os tipos: so a derivacao se repete
+    let matchers = config.address_types().into_iter()
+        .map(|t| {
+            let mut m = Matcher::new(config.targets.clone(), config.xpub.as_ref(), t, config.derivation_paths_for(t)?);
+            if config.uncompressed {
+                m = m.with_uncompressed_keys();
+            }
+            if let Some(multisig) = &config.multisig {
+                m = m.with_multisig(multisig.clone())?;
+            }
+            if let Some(bloom) = &config.bloom {
+                m = m.with_bloom(Arc::clone(bloom));
+            }
+            Ok(match &config.history {
+                Some(h) => m.with_history(Arc::clone(h)),
+                None    => m,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Rayon usa usize internamente; em 64-bit usize::MAX = u64::MAX ≈ 1.8e19.
+    // 24! ≈ 6.2e23 transbordaria usize. Usamos u64 no iterador do Rayon
+    // e convertemos para u128 somente dentro de permutation_at_index.
+    // m! · C · 2048^k · P: palavras livres, grafias, lacunas e passphrases
+    // Linhas: o total so se conhece no fim da entrada
+    let total = match reader {
+        None    => arranged.candidates.count().saturating_mul(passphrases.len() as u128),
+        Some(_) => u64::MAX as u128,
+    };
+    let end   = config.max_permutations.unwrap_or(u64::MAX).min(total.min(u64::MAX as u128) as u64);
+    if config.start_index >= end && end > 0 {
+        anyhow::bail!("Start index {} is past the last index to test ({})", config.start_index, end - 1);
+    }
+
+    let wordlist = config.language.word_list();
+    let mut s = Search {
+        candidates: arranged.candidates,
+        corrections: arranged.corrections,
+        total,
+        wordlist,
+        language: config.language,
+        coin: config.coin,
+        passphrases,
+        filter_bits: arranged.filter_bits,
+        shuffle: arranged.shuffle,
+        lines: reader.map(|reader| Mutex::new(Lines {
+            reader,
+            lookup: word_index_table(wordlist),
+            read:   0,
+            eof:    false,
+            error:  None,
+        })),
+        shard: config.shard.unwrap_or((0, 1)),
+        matchers,
+        find_all: config.find_all,
+        bip85: config.bip85,
+        next: config.start_index,
+        end,
+        window: 0,
+        backend: None,
+        checkpoint: None,
+        save_every: CheckpointInterval::Time(CHECKPOINT_INTERVAL),
+        last_saved: (Instant::now(), config.start_index),
+        audit: None,
+        counter: Arc::new(AtomicU64::new(0)),
+        valid: Arc::new(AtomicU64::new(0)),
+        current: Arc::new(AtomicU64::new(config.start_index)),
+        stop: Arc::new(AtomicBool::new(false)),
+        pending: VecDeque::new(),
+        matched: false,
+        finished: false,
+    };
+    s.window = s.window_for(rayon::current_num_threads() as u64 * CPU_BATCH_PER_THREAD);
+    Ok(s)
+}
+
+// Candidatos montados das palavras dadas
+struct Arranged {
+    candidates:  Candidates,
+    corrections: Vec<(String, Vec<&'static str>)>,
+    filter_bits: usize,
+    shuffle:     Option<Shuffle>,
+}
+
+fn arrange(config: &mut SearchConfig) -> Result<Arranged> {
+    let n = config.words.len();
+    // Palavras em NFKD, como nas wordlists (entrada em NFC nao acharia "ábaco")
+    for word in config.words.iter_mut()
+        .chain(config.fixed.iter_mut().map(|(_, w)| w))
+        .chain(config.choices.iter_mut().flat_map(|(_, alts)| alts.iter_mut()))
+        .chain(config.alternatives.iter_mut().flat_map(|(_, alts)| alts.iter_mut()))
+        .chain(config.before.iter_mut().flatten())
+    {
+        *word = normalize(word);
+    }
+
+    // Converte palavras para indices BIP-39 (feito uma vez, antes do loop).
+    // O loop paralelo opera sobre [u16], sem nunca alocar Strings por permutacao.
+    let wordlist = config.language.word_list(); // &'static [&'static str] — Send + Sync
+    let lookup   = word_index_table(wordlist);
+    let index_of = |w: &str| -> Result<u16> {
+        lookup.get(w)
+            .copied()
+            .or_else(|| find_word(w, config.language))
+            .with_context(|| format!(
+                "'{}' nao esta na wordlist BIP-39 ({})", w, language_name(config.language)))
+    };
+
+    // Palavra fora da wordlist: vira as palavras que comecam com ela (prefixo)
+    // ou, com --fuzzy, as mais proximas; cada alternativa e tentada em todas
+    // as permutacoes. Com --prefix-mode toda palavra vale pelas 4 primeiras letras.
+    if let Some((i, _)) = config.alternatives.iter().find(|(i, _)| *i >= n) {
+        anyhow::bail!("Alternatives for word {} are out of range (1..={})", i + 1, n);
+    }
+    let mut free: Vec<(u16, Vec<u16>)> = Vec::new();
+    let mut corrections = Vec::new();
+    for (i, word) in config.words.iter().enumerate().filter(|(_, w)| w.as_str() != WILDCARD) {
+        // Tokens exclusivos (--tokenlist): uma das palavras, em qualquer posicao
+        if let Some((_, alts)) = config.alternatives.iter().find(|(at, _)| *at == i) {
+            if alts.first() != Some(word) {
+                anyhow::bail!("Word {} ('{}') must be the first of its alternatives", i + 1, word);
+            }
+            let alts = alts.iter().map(|w| index_of(w)).collect::<Result<Vec<u16>>>()?;
+            free.push((alts[0], if alts.len() > 1 { alts } else { Vec::new() }));
+            continue;
+        }
+        let exact = lookup.get(word.as_str()).filter(|_| !config.prefix_mode);
+        if let Some(&idx) = exact {
+            free.push((idx, Vec::new()));
+            continue;
+        }
+        // Sem acento ("album" → "álbum"): a mesma palavra, nao uma alternativa
+        if let Some(idx) = find_word(word, config.language).filter(|_| !config.prefix_mode) {
+            corrections.push((word.clone(), vec![wordlist[idx as usize]]));
+            free.push((idx, Vec::new()));
+            continue;
+        }
+        let mut alts = prefix_matches(word, wordlist, config.prefix_mode);
+        if alts.is_empty() && config.fuzzy > 0 {
+            alts = fuzzy_matches(word, wordlist, config.fuzzy);
+        }
+        if alts.is_empty() {
+            index_of(word)?;
+            anyhow::bail!("'{}' is not in the wordlist and no word is within edit distance {}", word, config.fuzzy);
+        }
+        if alts.len() > 1 || wordlist[alts[0] as usize] != word {
+            corrections.push((word.clone(), alts.iter().map(|&i| wordlist[i as usize]).collect()));
+        }
+        free.push((alts[0], if alts.len() > 1 { alts } else { Vec::new() }));
+    }
+
+    // Layout das posicoes: "?" viram lacunas; cada --fixed sai do conjunto permutado
+    let mut slots = vec![Slot::Free; n];
+    for pos in config.wildcard_positions() {
+        slots[pos] = if config.checksum_last && pos == n - 1 { Slot::Checksum } else { Slot::Missing };
+    }
+    for (pos, word) in &config.fixed {
+        if *pos >= n {
+            anyhow::bail!("Fixed position {} is out of range (1..={})", pos + 1, n);
+        }
+        if slots[*pos] != Slot::Free {
+            anyhow::bail!("Position {} is fixed twice or also marked with ?", pos + 1);
+        }
+        let idx = index_of(word)?;
+        let at = free.iter().position(|(w, alts)| alts.is_empty() && *w == idx)
+            .with_context(|| format!("Fixed word '{}' is not one of the given words", word))?;
+        free.remove(at);
+        slots[*pos] = Slot::Fixed(idx);
+    }
+
+    // Posicoes com varias candidatas: fixas na primeira, trocadas pela escolha
+    let mut position_choices = Vec::new();
+    for (pos, alts) in &config.choices {
+        if *pos >= n {
+            anyhow::bail!("Position {} is out of range (1..={})", pos + 1, n);
+        }
+        if slots[*pos] != Slot::Free {
+            anyhow::bail!("Position {} has candidates but is also fixed or marked with ?", pos + 1);
+        }
+        let alts = alts.iter().map(|w| index_of(w)).collect::<Result<Vec<u16>>>()?;
+        let first = *alts.first().with_context(|| format!("No candidates for position {}", pos + 1))?;
+        let at = free.iter().position(|(w, a)| a.is_empty() && *w == first)
+            .with_context(|| format!("Candidate '{}' is not one of the given words", wordlist[first as usize]))?;
+        free.remove(at);
+        slots[*pos] = Slot::Fixed(first);
+        position_choices.push((Choice::Position(*pos), alts));
+    }
+
+    let mut candidates = Candidates {
+        free:      free.iter().map(|(w, _)| *w).collect(),
+        slots,
+        order:     config.order,
+        choices:   free.into_iter().enumerate()
+            .filter(|(_, (_, alts))| !alts.is_empty())
+            .map(|(i, (_, alts))| (Choice::Free(i), alts))
+            .chain(position_choices)
+            .collect(),
+        max_swaps:  config.max_swaps,
+        precedence: None,
+        multiset:   None,
+    };
+    if config.max_swaps.is_some() && config.order != Order::OriginalFirst {
+        anyhow::bail!("--max-swaps already sets the order (nearest to the given order first)");
+    }
+    if !config.before.is_empty() && (config.max_swaps.is_some() || config.order == Order::Heap) {
+        anyhow::bail!("--before cannot be combined with --max-swaps or --order heap");
+    }
+
+    // Com todas as posicoes fixas (e sem escolhas) a frase e conhecida: o
+    // checksum precisa fechar e nao filtra mais nada (so as passphrases variam)
+    let known_phrase = candidates.slots.iter().all(|s| matches!(s, Slot::Fixed(_)))
+        && candidates.choice_count() == 1;
+    if known_phrase && !checksum_valid(&candidates.at(0)) {
+        anyhow::bail!("The given mnemonic has an invalid BIP-39 checksum");
+    }
+    // Ultima palavra calculada: todo candidato ja fecha o checksum
+    let checksum_filled = candidates.slots.contains(&Slot::Checksum);
+
+    // lexicographic: mesma enumeracao, sobre as palavras ordenadas pela wordlist;
+    // random: indice embaralhado sobre todos os candidatos
+    if config.order == Order::Lexicographic {
+        candidates.sort_free();
+    }
+
+    // --before: cada palavra vira sua posicao entre as permutadas (ja na ordem final)
+    if !config.before.is_empty() {
+        let position = |word: &str| -> Result<usize> {
+            let idx = index_of(word)?;
+            let uncertain = |i: usize| candidates.choices.iter().any(|(c, _)| *c == Choice::Free(i));
+            let at: Vec<usize> = (0..candidates.free.len())
+                .filter(|&i| candidates.free[i] == idx && !uncertain(i))
+                .collect();
+            match at[..] {
+                [i] => Ok(i),
+                []  => anyhow::bail!("'{}' in --before is not one of the permuted words (fixed words and ? do not move)", word),
+                _   => anyhow::bail!("'{}' in --before appears more than once among the words", word),
+            }
+        };
+        let mut pairs = Vec::new();
+        for chain in &config.before {
+            let at = chain.iter().map(|w| position(w)).collect::<Result<Vec<_>>>()?;
+            pairs.extend(at.windows(2).map(|w| (w[0], w[1])));
+        }
+        candidates.precedence = Some(Precedence::new(&pairs)?);
+    }
+
+    // Palavras repetidas: so as ordens distintas. Fica de fora quando as
+    // palavras mudam com a escolha (grafias) ou a ordem ja e restrita
+    let free_choices = candidates.choices.iter().any(|(c, _)| matches!(c, Choice::Free(_)));
+    if candidates.max_swaps.is_none() && candidates.precedence.is_none() && !free_choices {
+        candidates.multiset = Multiset::new(&candidates.free);
+    }
+    let shuffle = (config.order == Order::Random).then(|| Shuffle::new(candidates.count()));
+
+    Ok(Arranged {
+        candidates,
+        corrections,
+        filter_bits: if known_phrase || checksum_filled { 0 } else { n / 3 },
+        shuffle,
+    })
+}
+
+impl Search {
+    /// Offload PBKDF2 (and point multiplication, if supported) to a GPU backend.
+    pub fn with_backend(mut self, backend: Box<dyn SeedBackend>) -> Self {
+        self.window  = self.window_for(backend.batch_size() as u64);
+        self.backend = Some(backend);
+        self
+    }
+
+    /// Save the search position to `checkpoint` periodically and when the search ends.
+    pub fn with_checkpoint(mut self, checkpoint: Checkpoint) -> Self {
+        self.checkpoint = Some(checkpoint);
+        self
+    }
+
+    /// Save every `interval` instead of every 30s; checked after each window.
+    pub fn with_checkpoint_interval(mut self, interval: CheckpointInterval) -> Self {
+        self.save_every = interval;
+        self
+    }
+
+    /// Record every derived candidate (index, master fingerprint, first address) in `audit`.
+    pub fn with_audit(mut self, audit: AuditLog) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Stop when `stop` is set elsewhere, e.g. by a coordinator, instead of an own flag.
+    pub fn with_stop_handle(mut self, stop: Arc<AtomicBool>) -> Self {
+        self.stop = stop;
+        self
+    }
+
+    /// Permutations tested so far by this search (shared, for progress reporting). Only
+    /// windows that were finished count: a window cut short by a stop is not, as it is
+    /// not covered by `next_index()` either.
+    pub fn counter(&self) -> Arc<AtomicU64> {
+        Arc::clone(&self.counter)
+    }
+
+    /// Checksum-valid candidates so far, i.e. seeds derived (shared, for progress reporting).
+    pub fn valid_counter(&self) -> Arc<AtomicU64> {
+        Arc::clone(&self.valid)
+    }
+
+    /// Index of the latest checksum-valid candidate (shared, for live display).
+    pub fn current(&self) -> Arc<AtomicU64> {
+        Arc::clone(&self.current)
+    }
+
+    /// Setting this flag stops the search at the next opportunity.
+    pub fn stop_handle(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.stop)
+    }
+
+    /// First permutation index not yet fully tested.
+    pub fn next_index(&self) -> u64 {
+        self.next
+    }
+
+    /// Exclusive upper bound of the permutation indices to test.
+    pub fn end_index(&self) -> u64 {
+        self.end
+    }
+
+    /// Size of the search space: permutations × spellings × gap fillings × passphrases.
+    pub fn total_permutations(&self) -> u128 {
+        self.total
+    }
+
+    /// Whether repeated words are permuted as a multiset: each distinct order once.
+    pub fn distinct_orders(&self) -> bool {
+        self.candidates.multiset.is_some()
+    }
+
+    /// Abbreviated or misspelled words and the wordlist entries tried in their place.
+    pub fn corrections(&self) -> &[(String, Vec<&'static str>)] {
+        &self.corrections
+    }
+
+    /// Indices this process still has to test (its shard of `next_index()..end_index()`).
+    pub fn remaining(&self) -> u64 {
+        self.shard_count(self.next, self.end).1
+    }
+
+    /// First `count` checksum-valid candidates (phrase, passphrase) from `next_index()` in
+    /// this process's indices, looking at no more than `limit` of them; nothing is derived.
+    pub fn examples(&self, count: usize, limit: u64) -> Vec<(u64, String, String)> {
+        let (first, available) = self.shard_count(self.next, self.end);
+        let n_pass = self.passphrases.len();
+        (0..available.min(limit))
+            .map(|j| first + j * self.shard.1)
+            .filter_map(|idx| {
+                let (cand, p) = split_index(idx, n_pass, self.shuffle);
+                let perm = self.candidates.at(cand);
+                let phrase = || display_phrase(&build_phrase(&perm, self.wordlist), self.language);
+                checksum_valid(&perm).then(|| (idx, phrase(), self.passphrases.get(p)))
+            })
+            .take(count)
+            .collect()
+    }
+
+    /// Runs the pipeline (CPU or GPU: checksum filter, PBKDF2, derivation) for about
+    /// `duration`, then rewinds: the search still starts at the same index, with the counter
+    /// at zero. Only the in-memory targets are compared: history lookups, Bloom re-checks,
+    /// the audit log and the checkpoint are left out, as the search would repeat them.
+    /// `None`, with nothing run, when the search fits in one window and would take about
+    /// as long as the calibration itself.
+    pub fn calibrate(&mut self, duration: Duration) -> Result<Option<Calibration>> {
+        if self.end.saturating_sub(self.next) <= self.window {
+            return Ok(None);
+        }
+        let local      = self.matchers.iter().map(Matcher::local).collect();
+        let matchers   = std::mem::replace(&mut self.matchers, local);
+        let audit      = self.audit.take();
+        let checkpoint = self.checkpoint.take();
+        let save_every = std::mem::replace(&mut self.save_every, CheckpointInterval::Time(Duration::MAX));
+        let start_index = self.next;
+        let start       = Instant::now();
+        let (done_tx, done_rx) = mpsc::channel::<()>();
+        let stop = Arc::clone(&self.stop);
+
+        // O prazo interrompe a janela corrente pelo mesmo caminho do Ctrl-C
+        let scanned = thread::scope(|scope| {
+            scope.spawn(move || {
+                if let Err(RecvTimeoutError::Timeout) = done_rx.recv_timeout(duration) {
+                    stop.store(true, Ordering::Relaxed);
+                }
+            });
+            let mut result = Ok(());
+            while result.is_ok() && self.next < self.end && !self.stop.load(Ordering::Relaxed) && !self.matched {
+                result = self.scan();
+            }
+            drop(done_tx);
+            result
+        });
+
+        let calibration = Calibration {
+            tested:  self.counter.load(Ordering::Relaxed),
+            elapsed: start.elapsed(),
+            matched: self.matched,
+        };
+        self.next    = start_index;
+        self.matched = false;
+        self.pending.clear();
+        self.counter.store(0, Ordering::Relaxed);
+        self.valid.store(0, Ordering::Relaxed);
+        self.current.store(start_index, Ordering::Relaxed);
+        self.stop.store(false, Ordering::Relaxed);
+        self.matchers   = matchers;
+        self.audit      = audit;
+        self.checkpoint = checkpoint;
+        self.save_every = save_every;
+        scanned.map(|_| Some(calibration))
+    }
+
+    // Janela que produz ~batch frases checksum-validas (1/16 ou 1/256 passam,
+    // ou todas quando a frase e conhecida); com --shard so 1/n dela e nossa
+    fn window_for(&self, batch: u64) -> u64 {
+        (batch.max(1) << self.filter_bits).saturating_mul(self.shard.1)
+    }
+
+    // Primeiro indice do shard em start..end e quantos ha
+    fn shard_count(&self, start: u64, end: u64) -> (u64, u64) {
+        shard_span(self.shard, start, end)
+    }
+
+    // Maior bloco de `items` que um thread processa sem dividir: ~CHUNK_VALID
+    // frases validas, cada item com `per_item` passphrases
+    fn chunk_len(&self, per_item: u64) -> usize {
+        ((CHUNK_VALID << self.filter_bits) / per_item).max(1) as usize
+    }
+
+    // Tambem marca o progresso no registro (--log-file), com ou sem checkpoint
+    fn save_checkpoint(&mut self) -> Result<()> {
+        save_progress(self.checkpoint.as_ref(), self.next, self.counter.load(Ordering::Relaxed))?;
+        self.last_saved = (Instant::now(), self.next);
+        Ok(())
+    }
+
+    // ── Pipeline ─────────────────────────────────────────────────────────────
+    // Tres estagios ligados por canais limitados, cada um numa janela diferente:
+    //   geracao   (thread proprio, Rayon) — [1] permutacao, [2] checksum, [3] frase
+    //   PBKDF2    (thread proprio)        — [4] seeds na CPU (AVX2, Rayon) ou na GPU
+    //   derivacao (este thread, Rayon)    — [5] BIP32 + endereco + comparacao
+    // So a derivacao mexe no estado da busca, e as janelas chegam nela em ordem:
+    // o checkpoint continua exato. Ao parar, as janelas ja geradas e ainda nao
+    // derivadas sao descartadas (nao contam como testadas).
+    //
+    // Volta quando a busca acaba, e parada, acha um match ou, com find_all, ha
+    // matches para entregar.
+    fn scan(&mut self) -> Result<()> {
+        let n_pass      = self.passphrases.len();
+        let chunks      = (self.chunk_len(1), self.chunk_len(n_pass));
+        let window      = self.window;
+        let search_end  = self.end;
+        let shard       = self.shard;
+        let candidates  = &self.candidates;
+        let wordlist    = self.wordlist;
+        let language    = self.language;
+        let coin        = self.coin;
+        let matchers    = &self.matchers;
+        let passphrases = &self.passphrases;
+        let shuffle     = self.shuffle;
+        let lines       = &self.lines;
+        let current     = &self.current;
+        let stop        = &self.stop;
+        let find_all    = self.find_all;
+        let bip85       = &self.bip85;
+        let backend     = &mut self.backend;
+
+        // O primeiro match para todos os estagios (a menos que find_all)
+        let hit    = AtomicBool::new(false);
+        let ended  = AtomicBool::new(false);
+        let halted = || ended.load(Ordering::Relaxed)
+            || stop.load(Ordering::Relaxed)
+            || (!find_all && hit.load(Ordering::Relaxed))
+            || matchers.iter().any(|m| m.failed());
+
+        // [1]..[3] Frases checksum-validas da janela que comeca em `start`
+        let generate = |start: u64| -> Window {
+            let end    = start.saturating_add(window).min(search_end);
+            let tested = AtomicU64::new(0);
+            if let Some(lines) = lines {
+                let window = read_window(&mut lines.lock().expect("lines lock"), start, end, n_pass, shard, wordlist, language);
+                if let Some(&(idx, _, _)) = window.items.last() {
+                    current.store(idx, Ordering::Relaxed);
+                }
+                return window;
+            }
+            let items  = if n_pass > 1 {
+                // Por candidato: permutacao, checksum e frase uma vez, e so os
+                // validos passam pelas passphrases
+                let (k, n)  = shard;
+                let first   = start / n_pass;
+                let groups  = (end.div_ceil(n_pass) - first) as usize;
+                (0..groups).into_par_iter().with_max_len(chunks.1).map_init(Vec::new, |perm, c| {
+                    let c    = first + c as u64;
+                    let idxs = ((c * n_pass).max(start)..(c * n_pass + n_pass).min(end)).filter(|i| i % n == k);
+                    if halted() { return Vec::new(); }
+                    let idxs: Vec<u64> = idxs.collect();
+                    tested.fetch_add(idxs.len() as u64, Ordering::Relaxed);
+                    candidates.at_into(shuffle.map_or(c as u128, |s| s.apply(c as u128)), perm);
+                    if !checksum_valid(perm) { return Vec::new(); }
+                    let phrase = build_phrase(perm, wordlist);
+                    if let Some(&idx) = idxs.last() {
+                        current.store(idx, Ordering::Relaxed);
+                    }
+                    idxs.into_iter().map(|idx| (idx, phrase.clone(), idx % n_pass)).collect()
+                }).flatten_iter().collect()
+            } else {
+                shard_indices(shard, start, end).with_max_len(chunks.0).map_init(Vec::new, |perm, idx| {
+                    if halted() { return None; }
+                    tested.fetch_add(1, Ordering::Relaxed);
+                    let (cand, p) = split_index(idx, n_pass, shuffle);
+
+                    // [1] Permutacao como [u16] no buffer da thread — sem alocacao
+                    candidates.at_into(cand, perm);
+
+                    // [2] Checksum rapido — sem join/parse de string
+                    //     Rejeita ~93.75% (12 words) ate ~99.6% (24 words)
+                    if !checksum_valid(perm) { return None; }
+                    current.store(idx, Ordering::Relaxed);
+
+                    // [3] Reconstroi a frase somente para os raros checksum-validos
+                    Some((idx, build_phrase(perm, wordlist), p))
+                }).flatten_iter().collect()
+            };
+            Window { end, tested: tested.into_inner(), items, last: false }
+        };
+
+        // [4] PBKDF2 (2048x HMAC-SHA512) — gargalo. Na GPU, o lote inteiro numa
+        //     chamada e, com um so caminho comparado, tambem o k·G da derivacao
+        let stretch = |window: Window, backend: Option<&mut Box<dyn SeedBackend>>| -> Result<Stretched> {
+            let Some(backend) = backend else {
+                let seeds = window.items.par_chunks(CHUNK_VALID as usize).flat_map_iter(|batch| {
+                    // Parada: seeds em branco, a derivacao descarta a janela inteira
+                    if halted() { return vec![[0u8; 64]; batch.len()]; }
+                    let passes: Vec<String> = batch.iter().map(|&(_, _, p)| passphrases.get(p)).collect();
+                    let inputs: Vec<(&str, &str)> = batch.iter().zip(&passes)
+                        .map(|((_, phrase, _), pass)| (phrase.as_str(), pass.as_str()))
+                        .collect();
+                    pbkdf2::seeds(&inputs)
+                }).collect();
+                return Ok(Stretched { window, seeds, pubkeys: None });
+            };
+            let salts: Vec<Vec<u8>> = window.items.iter()
+                .map(|&(_, _, p)| format!("mnemonic{}", passphrases.get(p)).into_bytes())
+                .collect();
+            let phrases: Vec<String> = window.items.iter().map(|(_, phrase, _)| phrase.clone()).collect();
+            let salt_refs: Vec<&[u8]> = salts.iter().map(|s| s.as_slice()).collect();
+            let seeds = backend.seeds(&phrases, &salt_refs)?;
+            // [5'] BIP32 em lote: k·G na GPU, HMAC + tweak na CPU
+            let pubkeys = match matchers.as_slice() {
+                [m] if backend.supports_point_mul() => m.compared_path()
+                    .map(|path| gpu::derive_pubkeys(backend.as_mut(), &seeds, path))
+                    .transpose()?,
+                _ => None,
+            };
+            Ok(Stretched { window, seeds, pubkeys })
+        };
+
+        // [5''] Filhos BIP85 da seed que nao casou: um PBKDF2 (em lote) por filho
+        let check_children = |seed: &[u8]| -> Option<(ScriptBuf, DerivationPath, Option<Child>)> {
+            let children = bip85.as_ref()?.of_seed(seed)?;
+            let inputs: Vec<(&str, &str)> = children.iter().map(|c| (c.phrase.as_str(), "")).collect();
+            let seeds = pbkdf2::seeds(&inputs);
+            children.into_iter().zip(seeds).find_map(|(child, seed)| {
+                let (script, path) = matchers.iter().find_map(|m| m.check_seed(&seed))?;
+                Some((script, path, Some(child)))
+            })
+        };
+
+        let on_match = |phrase: String, p: u64, index: u64, (script, path, bip85): (ScriptBuf, DerivationPath, Option<Child>)| {
+            hit.store(true, Ordering::Relaxed);
+            let address = coin.encode_address(&script)
+                .unwrap_or_else(|| script.as_bytes().to_lower_hex_string());
+            let phrase = display_phrase(&phrase, language);
+            Some(Match { phrase, passphrase: passphrases.get(p), index, address, path, bip85 })
+        };
+
+        thread::scope(|scope| {
+            let (window_tx, window_rx) = mpsc::sync_channel::<Window>(PIPELINE_DEPTH);
+            let (seed_tx, seed_rx)     = mpsc::sync_channel::<Result<Stretched>>(PIPELINE_DEPTH);
+
+            let mut start = self.next;
+            scope.spawn(move || {
+                while start < search_end && !halted() {
+                    let window = generate(start);
+                    let last   = window.last;
+                    start = window.end;
+                    if window_tx.send(window).is_err() || last { break; }
+                }
+            });
+            scope.spawn(move || {
+                for window in window_rx {
+                    let stretched = stretch(window, backend.as_mut());
+                    let failed    = stretched.is_err();
+                    if seed_tx.send(stretched).is_err() || failed { break; }
+                }
+            });
+
+            // [5] Derivacao BIP32 + geracao de endereco (ou chave da conta, com xpub)
+            let result = (|| -> Result<()> {
+                for stretched in seed_rx.iter() {
+                    let Stretched { window, seeds, pubkeys } = stretched?;
+                    // Janela interrompida nao conta como coberta: o checkpoint fica no inicio dela
+                    if halted() { break; }
+
+                    let audit = self.audit.is_some().then(|| Mutex::new(Vec::new()));
+                    let record = |idx: u64, seed: &[u8]| if let Some(rows) = &audit {
+                        if let Some(row) = audit_row(idx, seed, &matchers[0], coin) {
+                            rows.lock().expect("audit lock").push(row);
+                        }
+                    };
+                    let checked = window.items.len() as u64;
+                    let last    = window.last;
+                    let matches: Vec<Match> = match (pubkeys, matchers.as_slice()) {
+                        (Some(pubkeys), [matcher]) => window.items.into_par_iter().zip(pubkeys).zip(seeds.par_iter())
+                            .filter_map(|(((idx, phrase, p), pubkey), seed)| {
+                                record(idx, seed);
+                                let addr = pubkey.and_then(|pubkey| matcher.check_pubkey(pubkey, seed))
+                                    .map(|(script, path)| (script, path, None))
+                                    .or_else(|| check_children(seed))?;
+                                on_match(phrase, p, idx, addr)
+                            })
+                            .collect(),
+                        _ => window.items.into_par_iter().zip(seeds.par_iter())
+                            .filter_map(|((idx, phrase, p), seed)| {
+                                record(idx, seed);
+                                let addr = matchers.iter().find_map(|m| m.check_seed(seed))
+                                    .map(|(script, path)| (script, path, None))
+                                    .or_else(|| check_children(seed))?;
+                                on_match(phrase, p, idx, addr)
+                            })
+                            .collect(),
+                    };
+
+                    if let Some(e) = matchers.iter().find_map(|m| m.take_error()) {
+                        return Err(e.context("Address history check failed"));
+                    }
+                    if let (Some(log), Some(rows)) = (self.audit.as_mut(), audit) {
+                        let mut rows = rows.into_inner().expect("audit lock");
+                        rows.sort_unstable_by_key(|r| r.index);
+                        log.write(&rows)?;
+                    }
+                    // So janelas que avancam `next` contam: testados = next - inicio
+                    if !halted() {
+                        self.counter.fetch_add(window.tested, Ordering::Relaxed);
+                        self.valid.fetch_add(checked, Ordering::Relaxed);
+                        self.next = window.end;
+                        // Fim da entrada: agora o tamanho da busca e conhecido
+                        if last {
+                            self.end   = self.next;
+                            self.total = self.next as u128;
+                            if let Some(e) = lines.as_ref().and_then(|l| l.lock().expect("lines lock").error.take()) {
+                                return Err(anyhow::Error::new(e).context("Could not read the candidate phrases"));
+                            }
+                        }
+                    }
+                    self.matched |= !matches.is_empty();
+                    self.pending.extend(matches);
+
+                    if checkpoint_due(self.save_every, self.last_saved, self.next) {
+                        save_progress(self.checkpoint.as_ref(), self.next, self.counter.load(Ordering::Relaxed))?;
+                        self.last_saved = (Instant::now(), self.next);
+                    }
+                    if halted() || self.next >= self.end || !self.pending.is_empty() {
+                        break;
+                    }
+                }
+                Ok(())
+            })();
+            // Geracao e PBKDF2 param na proxima checagem; os canais fechados os destravam
+            ended.store(true, Ordering::Relaxed);
+            drop(seed_rx);
+            result
+        })
+    }
+}
+
+// Janela gerada: frases checksum-validas (indice, frase, indice da passphrase)
+struct Window {
+    end:    u64,
+    // Indices do shard percorridos (todos, se a janela nao foi interrompida)
+    tested: u64,
+    items:  Vec<(u64, String, u64)>,
+    // Ultima janela: a entrada de phrase_search acabou
+    last:   bool,
+}
+
+// Leitor de phrase_search: a linha i guarda os indices i·P .. i·P + P
+struct Lines {
+    reader: Box<dyn BufRead + Send>,
+    lookup: HashMap<&'static str, u16>,
+    // Linhas ja consumidas
+    read:   u64,
+    eof:    bool,
+    error:  Option<io::Error>,
+}
+
+// Janela start..end de phrase_search: le as linhas dela e mantem as frases
+// validas. Linha que nao e frase da lingua (palavra fora da lista, tamanho ou
+// checksum errado) conta como testada e sai sem PBKDF2.
+#[allow(clippy::too_many_arguments)]
+fn read_window(
+    lines: &mut Lines,
+    start: u64,
+    end: u64,
+    n_pass: u64,
+    shard: (u64, u64),
+    wordlist: &'static [&'static str; 2048],
+    language: Language,
+) -> Window {
+    let first = start / n_pass;
+    let mut text = Vec::new();
+    let mut buf  = String::new();
+    while !lines.eof && lines.read < end.div_ceil(n_pass) {
+        buf.clear();
+        match lines.reader.read_line(&mut buf) {
+            Ok(0)  => lines.eof = true,
+            Ok(_)  => {
+                // Linhas antes do inicio (--start-index, checkpoint) sao puladas
+                if lines.read >= first { text.push(buf.trim().to_lowercase()); }
+                lines.read += 1;
+            }
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => {
+                lines.error = Some(e);
+                lines.eof   = true;
+            }
+        }
+    }
+    let end = if lines.eof { end.min(lines.read.saturating_mul(n_pass)).max(start) } else { end };
+    let lookup = &lines.lookup;
+    let items = text.into_par_iter().enumerate().flat_map_iter(|(i, line)| {
+        let c    = first + i as u64;
+        let perm = normalize(&line).split_whitespace()
+            .map(|w| lookup.get(w).copied().or_else(|| find_word(w, language)))
+            .collect::<Option<Vec<u16>>>()
+            .filter(|perm| WORD_COUNTS.contains(&perm.len()) && checksum_valid(perm));
+        let phrase = perm.map(|perm| build_phrase(&perm, wordlist));
+        ((c * n_pass).max(start)..(c * n_pass + n_pass).min(end))
+            .filter(move |idx| idx % shard.1 == shard.0)
+            .filter_map(move |idx| Some((idx, phrase.clone()?, idx % n_pass)))
+    }).collect();
+    Window { end, tested: shard_span(shard, start, end).1, items, last: lines.eof }
+}
+
+// ... com as seeds e, se a GPU fez o k·G, as chaves publicas do caminho comparado
+struct Stretched {
+    window:  Window,
+    seeds:   Vec<[u8; 64]>,
+    pubkeys: Option<Vec<Option<PublicKey>>>,
+}
+
+// Primeiro indice do shard (k, n) em start..end e quantos ha
+fn shard_span((k, n): (u64, u64), start: u64, end: u64) -> (u64, u64) {
+    let first = start + (k + n - start % n) % n;
+    (first, if first < end { (end - first).div_ceil(n) } else { 0 })
+}
+
+// Indices do shard em start..end, em ordem
+fn shard_indices(shard: (u64, u64), start: u64, end: u64) -> impl IndexedParallelIterator<Item = u64> {
+    let (first, count) = shard_span(shard, start, end);
+    // Range<usize> e indexada: da para limitar o tamanho dos blocos
+    (0..count as usize).into_par_iter().map(move |j| first + j as u64 * shard.1)
+}
+
+fn checkpoint_due(every: CheckpointInterval, (at, index): (Instant, u64), next: u64) -> bool {
+    match every {
+        CheckpointInterval::Time(t)         => at.elapsed() >= t,
+        CheckpointInterval::Permutations(n) => next.saturating_sub(index) >= n,
+    }
+}
+
+// Grava o checkpoint (se houver) e marca o progresso no registro (--log-file)
+fn save_progress(checkpoint: Option<&Checkpoint>, next: u64, tested: u64) -> Result<()> {
+    log::info!("progress: next_index={} tested={}", next, tested);
+    if let Some(ck) = checkpoint {
+        ck.save(next)?;
+        log::debug!("checkpoint saved to {}", ck.path().display());
+    }
+    Ok(())
+}
+
+// --audit-csv: fingerprint da mestra e o primeiro endereco checado da seed
+fn audit_row(index: u64, seed: &[u8], matcher: &Matcher, coin: Coin) -> Option<AuditRow> {
+    let path   = matcher.reported_path().clone();
+    let script = matcher.script(derive_pubkey(seed, &path)?);
+    Some(AuditRow {
+        index,
+        fingerprint: master_fingerprint(seed)?,
+        address:     coin.encode_address(&script).unwrap_or_else(|| script.as_bytes().to_lower_hex_string()),
+        path,
+    })
+}
+
+// Indice → (candidato, passphrase); a passphrase varia mais rapido
+fn split_index(idx: u64, n_pass: u64, shuffle: Option<Shuffle>) -> (u128, u64) {
+    let cand = (idx / n_pass) as u128;
+    (shuffle.map_or(cand, |s| s.apply(cand)), idx % n_pass)
+}
+
+impl Iterator for Search {
+    type Item = Result<Match>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(m) = self.pending.pop_front() {
+                return Some(Ok(m));
+            }
+            if self.finished {
+                return None;
+            }
+
+            let exhausted = self.next >= self.end
+                || self.stop.load(Ordering::Relaxed)
+                || (self.matched && !self.find_all);
+            if exhausted {
+                self.finished = true;
+                if let Err(e) = self.save_checkpoint() {
+                    return Some(Err(e));
+                }
+                continue;
+            }
+
+            if let Err(e) = self.scan() {
+                self.finished = true;
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Janelas de ~16 seeds, sem o custo do PBKDF2
+    struct Fake;
+
+    impl SeedBackend for Fake {
+        fn device_name(&self) -> String { "fake".into() }
+        fn batch_size(&self) -> usize { 16 }
+        fn seeds(&mut self, phrases: &[String], _: &[&[u8]]) -> Result<Vec<[u8; 64]>> {
+            Ok(vec![[7; 64]; phrases.len()])
+        }
+    }
+
+    // Para a busca de dentro da derivacao, no meio de uma janela posterior
+    struct StopAt {
+        stop:  Arc<AtomicBool>,
+        calls: AtomicU64,
+    }
+
+    impl HistoryBackend for StopAt {
+        fn name(&self) -> String { "stop".into() }
+        fn used(&self, scripts: &[ScriptBuf]) -> Result<Vec<bool>> {
+            if self.calls.fetch_add(1, Ordering::Relaxed) == 40 {
+                self.stop.store(true, Ordering::Relaxed);
+            }
+            Ok(vec![false; scripts.len()])
+        }
+    }
+
+    #[test]
+    fn an_interrupted_window_is_not_counted() {
+        let words = "? ? abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let mut config = SearchConfig::new(Vec::new(), words.split(' ').map(String::from).collect(),
+            Language::English, AddressType::Bip84);
+        let stop = Arc::new(AtomicBool::new(false));
+        config.history = Some(Arc::new(StopAt { stop: Arc::clone(&stop), calls: AtomicU64::new(0) }));
+        config.start_index = 7;
+
+        let mut search = search(config).unwrap().with_backend(Box::new(Fake)).with_stop_handle(stop);
+        assert!(search.next().is_none());
+        assert!(search.next_index() > 7 && search.next_index() < search.end_index());
+        assert_eq!(search.counter().load(Ordering::Relaxed), search.next_index() - 7);
+        assert!(search.valid_counter().load(Ordering::Relaxed) <= 40);
+    }
+}
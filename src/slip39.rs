@@ -0,0 +1,329 @@
+// ── SLIP-39: backup Shamir (Trezor) ──────────────────────────────────────────
+//
+// Cada share tem 20 (segredo de 128 bits) ou 33 palavras (256 bits) de uma
+// wordlist propria de 1024 palavras, 10 bits cada:
+//   id(15) ext(1) e(4) | grupo(4) limiar-1(4) grupos-1(4) | membro(4)
+//   limiar-1(4) | valor (padding zero a esquerda) | checksum RS1024 (30 bits)
+//
+// O checksum e por share: a ordem de cada share e recuperada isoladamente e
+// so depois as shares sao combinadas (interpolacao em GF(256)) e o segredo
+// decifrado (Feistel de 4 rodadas com PBKDF2-SHA256). O segredo mestre e usado
+// direto como seed BIP32.
+
+use crate::permutation::{order_at_index, order_count};
+use crate::target::Matcher;
+use bitcoin::bip32::DerivationPath;
+use bitcoin::hashes::hmac::{Hmac, HmacEngine};
+use bitcoin::hashes::{sha256, Hash, HashEngine};
+use bitcoin::ScriptBuf;
+use itertools::Itertools;
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+const WORDLIST: &str = include_str!("slip39_english.txt");
+
+/// Share lengths in words: 128-bit and 256-bit master secrets.
+pub const SHARE_LENGTHS: [usize; 2] = [20, 33];
+
+// Palavras de cabecalho (40 bits) e de checksum (30 bits)
+const HEADER_WORDS: usize = 4;
+const CHECKSUM_WORDS: usize = 3;
+
+// x reservados na interpolacao: segredo e digest
+const SECRET_INDEX: u8 = 255;
+const DIGEST_INDEX: u8 = 254;
+const DIGEST_LEN: usize = 4;
+
+// Feistel: 10000 iteracoes de PBKDF2 no total (× 2^e), divididas pelas rodadas
+const BASE_ITERATIONS: u32 = 10000;
+const ROUNDS: u8 = 4;
+
+/// SLIP-39 English wordlist (1024 words, sorted).
+pub fn wordlist() -> &'static [&'static str] {
+    static LIST: OnceLock<Vec<&'static str>> = OnceLock::new();
+    LIST.get_or_init(|| WORDLIST.split_whitespace().collect())
+}
+
+pub fn word_index(word: &str) -> Option<u16> {
+    wordlist().binary_search(&word).ok().map(|i| i as u16)
+}
+
+// ── Checksum RS1024 ──────────────────────────────────────────────────────────
+fn polymod(values: impl Iterator<Item = u32>) -> u32 {
+    const GEN: [u32; 10] = [
+        0x00e0_e040, 0x01c1_c080, 0x0383_8100, 0x0707_0200, 0x0e0e_0009,
+        0x1c0c_2412, 0x3808_6c24, 0x3090_fc48, 0x21b1_f890, 0x03f3_f120,
+    ];
+    let mut chk: u32 = 1;
+    for v in values {
+        let b = chk >> 20;
+        chk = ((chk & 0x000f_ffff) << 10) ^ v;
+        for (i, g) in GEN.iter().enumerate() {
+            if (b >> i) & 1 != 0 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+// Bit "extendable" (5o bit da 2a palavra) escolhe a customizacao do checksum
+fn extendable(indices: &[u16]) -> bool {
+    indices.get(1).is_some_and(|w| (w >> 4) & 1 == 1)
+}
+
+/// RS1024 checksum of a share given as word indices.
+pub fn checksum_valid(indices: &[u16]) -> bool {
+    let customization: &[u8] = if extendable(indices) { b"shamir_extendable" } else { b"shamir" };
+    let values = customization.iter().map(|&b| b as u32).chain(indices.iter().map(|&i| i as u32));
+    polymod(values) == 1
+}
+
+// ── Share ────────────────────────────────────────────────────────────────────
+
+/// A decoded SLIP-39 share.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Share {
+    pub identifier:         u16,
+    pub extendable:         bool,
+    pub iteration_exponent: u8,
+    pub group_index:        u8,
+    pub group_threshold:    u8,
+    pub group_count:        u8,
+    pub member_index:       u8,
+    pub member_threshold:   u8,
+    pub value:              Vec<u8>,
+}
+
+impl Share {
+    /// Decodes a share from its word indices; `None` if the length, checksum or padding is invalid.
+    pub fn from_indices(indices: &[u16]) -> Option<Share> {
+        if !SHARE_LENGTHS.contains(&indices.len()) || !checksum_valid(indices) {
+            return None;
+        }
+        let header = indices[..HEADER_WORDS].iter().fold(0u64, |acc, &w| (acc << 10) | w as u64);
+
+        // Valor: 10 bits por palavra; os bits excedentes a esquerda devem ser zero
+        let words   = &indices[HEADER_WORDS..indices.len() - CHECKSUM_WORDS];
+        let padding = words.len() * 10 % 16;
+        let mut value = Vec::with_capacity(words.len() * 10 / 8);
+        let mut acc  = 0u32;
+        let mut bits = 0usize;
+        for (i, &w) in words.iter().enumerate() {
+            acc   = (acc << 10) | w as u32;
+            bits += 10;
+            if i == 0 {
+                if acc >> (10 - padding) != 0 {
+                    return None;
+                }
+                bits -= padding;
+            }
+            while bits >= 8 {
+                bits -= 8;
+                value.push((acc >> bits) as u8);
+            }
+            acc &= (1 << bits) - 1;
+        }
+
+        let share = Share {
+            identifier:         (header >> 25) as u16,
+            extendable:         (header >> 24) & 1 == 1,
+            iteration_exponent: ((header >> 20) & 0xf) as u8,
+            group_index:        ((header >> 16) & 0xf) as u8,
+            group_threshold:    ((header >> 12) & 0xf) as u8 + 1,
+            group_count:        ((header >> 8) & 0xf) as u8 + 1,
+            member_index:       ((header >> 4) & 0xf) as u8,
+            member_threshold:   (header & 0xf) as u8 + 1,
+            value,
+        };
+        (share.group_threshold <= share.group_count).then_some(share)
+    }
+
+    // Parametros que todas as shares de um mesmo segredo compartilham
+    fn common(&self) -> (u16, bool, u8, u8, u8, usize) {
+        (self.identifier, self.extendable, self.iteration_exponent,
+         self.group_threshold, self.group_count, self.value.len())
+    }
+}
+
+// ── GF(256) e Shamir ─────────────────────────────────────────────────────────
+//
+// Polinomio x^8 + x^4 + x^3 + x + 1 (o do AES), gerador 3.
+fn gf_tables() -> &'static ([u8; 255], [u8; 256]) {
+    static TABLES: OnceLock<([u8; 255], [u8; 256])> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let mut exp = [0u8; 255];
+        let mut log = [0u8; 256];
+        let mut x: u16 = 1;
+        for (i, e) in exp.iter_mut().enumerate() {
+            *e = x as u8;
+            log[x as usize] = i as u8;
+            x ^= x << 1;
+            if x & 0x100 != 0 {
+                x ^= 0x11b;
+            }
+        }
+        (exp, log)
+    })
+}
+
+// Valor em `x` do polinomio que passa pelos pontos (xi, yi) — Lagrange byte a byte
+fn interpolate(points: &[(u8, &[u8])], x: u8) -> Vec<u8> {
+    if let Some((_, y)) = points.iter().find(|(xi, _)| *xi == x) {
+        return y.to_vec();
+    }
+    let (exp, log) = gf_tables();
+    let log = |v: u8| log[v as usize] as usize;
+    let log_prod: usize = points.iter().map(|&(xi, _)| log(xi ^ x)).sum();
+
+    let mut out = vec![0u8; points[0].1.len()];
+    for &(xi, y) in points {
+        let denominator: usize = points.iter().map(|&(xj, _)| log(xi ^ xj)).sum();
+        let basis = (log_prod + 255 * points.len() - log(xi ^ x) - denominator) % 255;
+        for (o, &v) in out.iter_mut().zip(y) {
+            if v != 0 {
+                *o ^= exp[(log(v) + basis) % 255];
+            }
+        }
+    }
+    out
+}
+
+fn hmac_sha256(key: &[u8], data: &[&[u8]]) -> [u8; 32] {
+    let mut engine = HmacEngine::<sha256::Hash>::new(key);
+    for d in data {
+        engine.input(d);
+    }
+    Hmac::<sha256::Hash>::from_engine(engine).to_byte_array()
+}
+
+// Segredo de `threshold` pontos; acima de 1 o digest em x=254 confirma a combinacao
+fn recover_secret(threshold: u8, points: &[(u8, &[u8])]) -> Option<Vec<u8>> {
+    if threshold == 1 {
+        return points.first().map(|(_, y)| y.to_vec());
+    }
+    if points.iter().map(|(x, _)| x).all_unique() {
+        let secret = interpolate(points, SECRET_INDEX);
+        let digest = interpolate(points, DIGEST_INDEX);
+        let mac = hmac_sha256(&digest[DIGEST_LEN..], &[&secret]);
+        return (mac[..DIGEST_LEN] == digest[..DIGEST_LEN]).then_some(secret);
+    }
+    None
+}
+
+// PBKDF2-HMAC-SHA256; as metades do Feistel (8 ou 16 bytes) cabem num bloco
+fn pbkdf2_sha256(password: &[u8], salt: &[u8], iterations: u32, len: usize) -> Vec<u8> {
+    let mut u   = hmac_sha256(password, &[salt, &1u32.to_be_bytes()]);
+    let mut out = u;
+    for _ in 1..iterations {
+        u = hmac_sha256(password, &[&u]);
+        out.iter_mut().zip(&u).for_each(|(o, b)| *o ^= b);
+    }
+    out[..len].to_vec()
+}
+
+fn decrypt(encrypted: &[u8], passphrase: &[u8], share: &Share) -> Vec<u8> {
+    let half = encrypted.len() / 2;
+    let (mut left, mut right) = (encrypted[..half].to_vec(), encrypted[half..].to_vec());
+    let mut salt = if share.extendable {
+        Vec::new()
+    } else {
+        [b"shamir".as_slice(), &share.identifier.to_be_bytes()].concat()
+    };
+    let salt_len   = salt.len();
+    let iterations = (BASE_ITERATIONS << share.iteration_exponent) / ROUNDS as u32;
+    for round in (0..ROUNDS).rev() {
+        salt.truncate(salt_len);
+        salt.extend_from_slice(&right);
+        let password = [&[round], passphrase].concat();
+        let f = pbkdf2_sha256(&password, &salt, iterations, right.len());
+        let next = left.iter().zip(&f).map(|(l, f)| l ^ f).collect();
+        left = std::mem::replace(&mut right, next);
+    }
+    [right, left].concat()
+}
+
+/// Master secret (the BIP32 seed) from a set of shares; `None` if the shares do not
+/// belong together or are not enough to reach the thresholds.
+pub fn combine(shares: &[Share], passphrase: &str) -> Option<Vec<u8>> {
+    let first = shares.first()?;
+    if shares.iter().any(|s| s.common() != first.common()) {
+        return None;
+    }
+
+    let mut groups: BTreeMap<u8, Vec<&Share>> = BTreeMap::new();
+    for share in shares {
+        groups.entry(share.group_index).or_default().push(share);
+    }
+
+    // Segredo de cada grupo completo; grupos abaixo do limiar sao ignorados
+    let mut group_secrets = Vec::new();
+    for (&index, members) in &groups {
+        let threshold = members[0].member_threshold;
+        let mut points: Vec<(u8, &[u8])> = members.iter()
+            .filter(|m| m.member_threshold == threshold)
+            .map(|m| (m.member_index, m.value.as_slice()))
+            .unique_by(|(x, _)| *x)
+            .collect();
+        if points.len() < threshold as usize {
+            continue;
+        }
+        points.truncate(threshold as usize);
+        group_secrets.push((index, recover_secret(threshold, &points)?));
+    }
+    if group_secrets.len() < first.group_threshold as usize {
+        return None;
+    }
+
+    let points: Vec<(u8, &[u8])> = group_secrets.iter()
+        .take(first.group_threshold as usize)
+        .map(|(x, y)| (*x, y.as_slice()))
+        .collect();
+    let encrypted = recover_secret(first.group_threshold, &points)?;
+    Some(decrypt(&encrypted, passphrase.as_bytes(), first))
+}
+
+// ── Busca ────────────────────────────────────────────────────────────────────
+
+/// Orders of one share's words (indices) that pass the SLIP-39 checksum, given order first.
+pub fn share_orders(words: &[u16], max_swaps: Option<usize>, max_permutations: Option<u64>) -> Vec<(Vec<u16>, Share)> {
+    let total = order_count(words.len(), max_swaps).min(u64::MAX as u128) as u64;
+    let end   = max_permutations.unwrap_or(u64::MAX).min(total);
+    let mut found: Vec<(Vec<u16>, Share)> = (0..end).into_par_iter()
+        .filter_map(|i| {
+            let perm = order_at_index(words, max_swaps, i as u128);
+            Share::from_indices(&perm).map(|share| (perm, share))
+        })
+        .collect();
+    // Palavras repetidas geram a mesma ordem mais de uma vez
+    let mut seen = std::collections::HashSet::new();
+    found.retain(|(perm, _)| seen.insert(perm.clone()));
+    found
+}
+
+/// A set of share orders whose master secret derives to a target.
+#[derive(Debug, Clone)]
+pub struct Slip39Match {
+    /// The shares, words in the recovered order
+    pub shares:        Vec<String>,
+    pub master_secret: Vec<u8>,
+    pub script:        ScriptBuf,
+    pub path:          DerivationPath,
+}
+
+/// Tries every combination of the candidate orders of each share (from `share_orders`).
+pub fn find(candidates: &[Vec<(Vec<u16>, Share)>], passphrase: &str, matcher: &Matcher) -> Option<Slip39Match> {
+    candidates.iter()
+        .map(|c| c.iter())
+        .multi_cartesian_product()
+        .find_map(|combo| {
+            let shares: Vec<Share> = combo.iter().map(|(_, s)| s.clone()).collect();
+            let secret = combine(&shares, passphrase)?;
+            let (script, path) = matcher.check_seed(&secret)?;
+            let phrases = combo.iter()
+                .map(|(perm, _)| perm.iter().map(|&i| wordlist()[i as usize]).join(" "))
+                .collect();
+            Some(Slip39Match { shares: phrases, master_secret: secret, script, path })
+        })
+}
@@ -0,0 +1,81 @@
+// ── Endpoint de status (--status-port) ───────────────────────────────────────
+//
+// Um thread aceita conexoes HTTP e responde cada uma num thread proprio, com
+// um JSON do progresso (lido dos mesmos contadores da barra): um cliente que
+// conecta e nao manda nada nao trava as outras consultas. Nada da frase vai no
+// JSON. Sem HOST, escuta so em 127.0.0.1: para uma maquina remota, use um
+// tunel (ssh -L) ou passe 0.0.0.0:PORTA explicitamente.
+
+use anyhow::{Context, Result};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Uma requisicao de monitoramento chega inteira de uma vez
+const READ_TIMEOUT: Duration = Duration::from_millis(500);
+// Linha da requisicao e cabecalhos; o resto e ignorado
+const MAX_REQUEST: u64 = 8 * 1024;
+
+/// Progress counters shared with the search.
+pub struct Status {
+    pub counter:     Arc<AtomicU64>,
+    pub done:        Arc<AtomicBool>,
+    pub to_test:     u64,
+    pub start_index: u64,
+    pub start:       Instant,
+}
+
+impl Status {
+    fn json(&self) -> String {
+        let tested  = self.counter.load(Ordering::Relaxed);
+        let elapsed = self.start.elapsed().as_secs_f64();
+        let rate    = if elapsed > 0.0 { tested as f64 / elapsed } else { 0.0 };
+        let eta = match self.to_test.checked_sub(tested) {
+            Some(left) if rate > 0.0 && !self.done.load(Ordering::Relaxed) => format!("{:.0}", left as f64 / rate),
+            _ => "null".to_string(),
+        };
+        let state = if self.done.load(Ordering::Relaxed) { "finished" } else { "running" };
+        format!(
+            "{{\"state\": \"{}\", \"start_index\": {}, \"tested\": {}, \"to_test\": {}, \
+             \"rate_per_sec\": {:.1}, \"elapsed_secs\": {:.0}, \"eta_secs\": {}}}\n",
+            state, self.start_index, tested, self.to_test, rate, elapsed, eta)
+    }
+}
+
+// "8999" → 127.0.0.1:8999; "0.0.0.0:8999" fica como esta
+pub fn bind_address(spec: &str) -> String {
+    if spec.contains(':') { spec.to_string() } else { format!("127.0.0.1:{}", spec) }
+}
+
+/// Binds now (so a busy port fails before the search) and serves in a background thread.
+pub fn serve(spec: &str, status: Status) -> Result<String> {
+    let addr = bind_address(spec);
+    let listener = TcpListener::bind(&addr)
+        .with_context(|| format!("Failed to listen on {} for --status-port", addr))?;
+    let status = Arc::new(status);
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let status = Arc::clone(&status);
+            // Cliente lento ou quebrado nao derruba o servidor nem atrasa os outros
+            thread::spawn(move || { let _ = respond(stream, &status); });
+        }
+    });
+    Ok(addr)
+}
+
+fn respond(mut stream: TcpStream, status: &Status) -> std::io::Result<()> {
+    // So a linha da requisicao e os cabecalhos sao lidos; o caminho nao importa
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?.take(MAX_REQUEST));
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 2 {
+        line.clear();
+    }
+    let body = status.json();
+    write!(stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(), body)
+}
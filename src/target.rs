@@ -0,0 +1,296 @@
+// ── Alvos: scriptPubKeys e/ou xpub de conta ──────────────────────────────────
+//
+// Com uma xpub de conta (m/purpose'/0'/0') como alvo, a comparacao e feita na
+// chave publica da conta: pula os dois ultimos passos de derivacao e a
+// codificacao do endereco por candidato. O endereco so e derivado no acerto.
+// Uma xpub (ou xprv) mestra compara ja a chave da seed, sem derivar nada.
+//
+// Com varios caminhos por seed (--chain, --index-range, --path com {index}),
+// caminhos consecutivos com o mesmo pai reaproveitam a chave do pai: cada
+// indice custa so o ultimo passo.
+//
+// Com --uncompressed, o P2PKH usa a chave publica nao comprimida (carteiras
+// anteriores a 2012): outro hash160, outro endereco para a mesma chave. Num
+// multisig, a chave derivada entra no script junto com as dos cosignatarios
+// (ver multisig.rs), ja derivadas para cada caminho.
+//
+// Sem multisig, a comparacao e no conteudo do script (hash160 ou chave de
+// saida Taproot, ver `Payload`), pre-calculado uma vez para os alvos: o
+// ScriptBuf do candidato so e montado no acerto ou para o historico. Alvos e
+// payloads ficam em conjuntos de hash: milhares de alvos (--targets-file)
+// custam por candidato o mesmo que um. Milhoes ficam num filtro de Bloom (ver
+// bloom.rs), consultado quando o conjunto nao tem o payload.
+//
+// Com um backend de historico (--electrum), os scripts que nao casaram com
+// nenhum alvo sao consultados de uma vez: o primeiro ja usado e o acerto. Um
+// erro de rede fica guardado e interrompe a busca (ver `take_error`).
+
+use crate::bloom::BloomTargets;
+use crate::derivation::{
+    derive_pubkey, p2pkh_uncompressed, pubkey_payload, script_from_pubkey, AddressType, Node, Payload,
+};
+use crate::history::HistoryBackend;
+use crate::multisig::Multisig;
+use bitcoin::bip32::{ChildNumber, DerivationPath, Xpriv, Xpub};
+use bitcoin::secp256k1::PublicKey;
+use bitcoin::hashes::Hash;
+use bitcoin::{PubkeyHash, Script, ScriptBuf, ScriptHash, WPubkeyHash};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Scripts whose hash is `hash`: as a public key hash (P2PKH, P2WPKH and the
+/// BIP49 P2SH-P2WPKH wrapping) and as a script hash (P2SH). Targets built from a
+/// bare hash160 match any of BIP44/49/84 without an address to decode.
+pub fn hash160_targets(hash: [u8; 20]) -> Vec<ScriptBuf> {
+    let pubkey_hash = PubkeyHash::from_byte_array(hash);
+    let wrapped = ScriptBuf::new_p2wpkh(&WPubkeyHash::from_byte_array(hash));
+    vec![
+        ScriptBuf::new_p2pkh(&pubkey_hash),
+        wrapped.clone(),
+        ScriptBuf::new_p2sh(&wrapped.script_hash()),
+        ScriptBuf::new_p2sh(&ScriptHash::from_byte_array(hash)),
+    ]
+}
+
+/// Address type whose outputs look like `script`; `None` for scripts no
+/// single-key path derives (P2WSH, bare multisig, ...).
+pub fn script_type(script: &Script) -> Option<AddressType> {
+    if script.is_p2pkh() {
+        Some(AddressType::Bip44)
+    } else if script.is_p2sh() {
+        Some(AddressType::Bip49)
+    } else if script.is_p2wpkh() {
+        Some(AddressType::Bip84)
+    } else if script.is_p2tr() {
+        Some(AddressType::Bip86)
+    } else {
+        None
+    }
+}
+
+pub struct Matcher {
+    targets:      HashSet<ScriptBuf>,
+    // Conteudo dos alvos de chave unica, comparado no laco da busca
+    payloads:     HashSet<Payload>,
+    account:      Option<PublicKey>,
+    address_type: AddressType,
+    paths:        Vec<DerivationPath>,
+    compared:     Option<DerivationPath>,
+    uncompressed: bool,
+    // Multisig e as chaves dos cosignatarios em cada caminho de `paths`
+    multisig:     Option<(Multisig, Vec<Vec<PublicKey>>)>,
+    bloom:        Option<Arc<BloomTargets>>,
+    history:      Option<Arc<dyn HistoryBackend>>,
+    failed:       AtomicBool,
+    error:        Mutex<Option<anyhow::Error>>,
+}
+
+impl Matcher {
+    /// `paths` nao pode ser vazio; o primeiro e o endereco reportado num acerto via xpub.
+    /// A chave da xpub e comparada no nivel dela (mestra ou conta) do primeiro caminho.
+    pub fn new(
+        targets: Vec<ScriptBuf>,
+        xpub: Option<&Xpub>,
+        address_type: AddressType,
+        paths: Vec<DerivationPath>,
+    ) -> Self {
+        let compared = match xpub {
+            Some(x) => Some(paths[0][..paths[0].len().min(x.depth as usize)].into()),
+            None if paths.len() == 1 => Some(paths[0].clone()),
+            None => None,
+        };
+        Self {
+            payloads: targets.iter().filter_map(|t| Payload::from_script(t)).collect(),
+            targets:  targets.into_iter().collect(),
+            account: xpub.map(|x| x.public_key),
+            address_type,
+            paths,
+            compared,
+            uncompressed: false,
+            multisig: None,
+            bloom:   None,
+            history: None,
+            failed:  AtomicBool::new(false),
+            error:   Mutex::new(None),
+        }
+    }
+
+    /// Also accept any derived script with on-chain history according to `history`.
+    pub fn with_history(mut self, history: Arc<dyn HistoryBackend>) -> Self {
+        self.history = Some(history);
+        self
+    }
+
+    /// Also match the targets held in `bloom` (probable hits are re-checked against its file).
+    pub fn with_bloom(mut self, bloom: Arc<BloomTargets>) -> Self {
+        self.bloom = Some(bloom);
+        self
+    }
+
+    /// P2PKH from the uncompressed public key (BIP44 only), as wallets did before 2012.
+    pub fn with_uncompressed_keys(mut self) -> Self {
+        self.uncompressed = true;
+        self
+    }
+
+    /// The derived key is one of the keys of `multisig`; the cosigner keys of every path
+    /// are derived here, once.
+    pub fn with_multisig(mut self, multisig: Multisig) -> anyhow::Result<Self> {
+        let keys = self.paths.iter().map(|p| multisig.cosigner_keys(p)).collect::<anyhow::Result<_>>()?;
+        self.multisig = Some((multisig, keys));
+        Ok(self)
+    }
+
+    /// Copy comparing only the in-memory targets: no history backend and no Bloom filter
+    /// (whose probable hits read a file), for a calibration run that the search repeats.
+    pub fn local(&self) -> Matcher {
+        Matcher {
+            targets:      self.targets.clone(),
+            payloads:     self.payloads.clone(),
+            account:      self.account,
+            address_type: self.address_type,
+            paths:        self.paths.clone(),
+            compared:     self.compared.clone(),
+            uncompressed: self.uncompressed,
+            multisig:     self.multisig.clone(),
+            bloom:        None,
+            history:      None,
+            failed:       AtomicBool::new(false),
+            error:        Mutex::new(None),
+        }
+    }
+
+    /// scriptPubKey de `pubkey` no tipo de endereco do alvo (no primeiro caminho).
+    pub fn script(&self, pubkey: PublicKey) -> ScriptBuf {
+        self.script_at(pubkey, 0)
+    }
+
+    // scriptPubKey de `pubkey` derivada em `paths[i]`
+    fn script_at(&self, pubkey: PublicKey, i: usize) -> ScriptBuf {
+        match &self.multisig {
+            Some((multisig, keys)) => multisig.script(pubkey, &keys[i]),
+            None if self.uncompressed => p2pkh_uncompressed(pubkey),
+            None => script_from_pubkey(pubkey, self.address_type),
+        }
+    }
+
+    // `pubkey` derivada em `paths[i]` e um alvo? Sem multisig, sem montar o script
+    fn is_target(&self, pubkey: PublicKey, i: usize) -> bool {
+        if self.multisig.is_some() {
+            return self.targets.contains(&self.script_at(pubkey, i));
+        }
+        // Como em `script_at`: --uncompressed e sempre P2PKH
+        let address_type = if self.uncompressed { AddressType::Bip44 } else { self.address_type };
+        let payload = pubkey_payload(pubkey, address_type, self.uncompressed);
+        if self.payloads.contains(&payload) {
+            return true;
+        }
+        match self.bloom.as_ref().map(|b| b.contains(&payload)) {
+            Some(Ok(found)) => found,
+            Some(Err(e))    => {
+                self.fail(e);
+                false
+            }
+            None => false,
+        }
+    }
+
+    // Guarda o primeiro erro e marca a busca para parar
+    fn fail(&self, e: anyhow::Error) {
+        self.failed.store(true, Ordering::Relaxed);
+        self.error.lock().expect("error lock").get_or_insert(e);
+    }
+
+    /// Uma consulta de historico (ou releitura do arquivo do Bloom) falhou? (a busca deve parar)
+    pub fn failed(&self) -> bool {
+        self.failed.load(Ordering::Relaxed)
+    }
+
+    /// Primeiro erro de consulta de historico, se houve.
+    pub fn take_error(&self) -> Option<anyhow::Error> {
+        self.error.lock().expect("error lock").take()
+    }
+
+    /// Primeiro caminho checado (o endereco reportado num acerto via xpub).
+    pub fn reported_path(&self) -> &DerivationPath {
+        &self.paths[0]
+    }
+
+    pub fn address_type(&self) -> AddressType {
+        self.address_type
+    }
+
+    /// Caminho cuja chave publica basta para decidir o acerto (conta com xpub,
+    /// ou o endereco quando ha um unico). `None` com varios enderecos por seed.
+    pub fn compared_path(&self) -> Option<&DerivationPath> {
+        self.compared.as_ref()
+    }
+
+    /// scriptPubKey (e caminho) derivado da seed, se ela casa com algum alvo.
+    pub fn check_seed(&self, seed: &[u8]) -> Option<(ScriptBuf, DerivationPath)> {
+        if let Some(path) = &self.compared {
+            return self.check_pubkey(derive_pubkey(seed, path)?, seed);
+        }
+        self.check_node(&Node::master(seed)?)
+    }
+
+    /// Como `check_seed`, a partir da chave mestra: todos os caminhos, sem a
+    /// comparacao pela chave de `compared_path`.
+    pub fn check_master(&self, master: &Xpriv) -> Option<(ScriptBuf, DerivationPath)> {
+        self.check_node(&Node::from_xpriv(master))
+    }
+
+    fn check_node(&self, master: &Node) -> Option<(ScriptBuf, DerivationPath)> {
+        // Chave do pai em cache enquanto os caminhos compartilham o prefixo
+        let mut parent: Option<(&[ChildNumber], Node)> = None;
+        let mut derived = Vec::new();
+        let hit = self.paths.iter().enumerate().find_map(|(i, path)| {
+            let (last, prefix) = path.as_ref().split_last()?;
+            if parent.as_ref().is_none_or(|(p, _)| *p != prefix) {
+                parent = Some((prefix, master.derive(prefix)?));
+            }
+            let (_, node) = parent.as_ref()?;
+            let pubkey = node.child(*last, None)?.public_key();
+            if self.is_target(pubkey, i) {
+                return Some((self.script_at(pubkey, i), path.clone()));
+            }
+            if self.history.is_some() {
+                derived.push((self.script_at(pubkey, i), path.clone()));
+            }
+            None
+        });
+        hit.or_else(|| self.check_history(derived))
+    }
+
+    // Primeiro script (em ordem de caminho) com historico no servidor
+    fn check_history(&self, derived: Vec<(ScriptBuf, DerivationPath)>) -> Option<(ScriptBuf, DerivationPath)> {
+        let history = self.history.as_ref().filter(|_| !self.failed())?;
+        let scripts: Vec<ScriptBuf> = derived.iter().map(|(s, _)| s.clone()).collect();
+        match history.used(&scripts) {
+            Ok(used) => derived.into_iter().zip(used).find(|(_, u)| *u).map(|(d, _)| d),
+            Err(e) => {
+                self.fail(e);
+                None
+            }
+        }
+    }
+
+    /// Como `check_seed`, com a chave publica de `compared_path` ja derivada.
+    pub fn check_pubkey(&self, pubkey: PublicKey, seed: &[u8]) -> Option<(ScriptBuf, DerivationPath)> {
+        match self.account {
+            Some(account) if account == pubkey => {
+                let path = self.paths[0].clone();
+                Some((self.script(derive_pubkey(seed, &path)?), path))
+            }
+            Some(_) => None,
+            None => {
+                if self.is_target(pubkey, 0) {
+                    return Some((self.script(pubkey), self.paths[0].clone()));
+                }
+                self.history.as_ref()?;
+                self.check_history(vec![(self.script(pubkey), self.paths[0].clone())])
+            }
+        }
+    }
+}
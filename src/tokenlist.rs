@@ -0,0 +1,171 @@
+// ── Tokenlists do btcrecover (--tokenlist) ───────────────────────────────────
+//
+// Receitas do btcrecover/seedrecover aproveitadas como estao, uma palavra da
+// frase por linha:
+//   a b c        → tokens mutuamente exclusivos: uma dessas, posicao livre
+//   + a b        → linha obrigatoria (aqui toda linha entra na frase)
+//   ^a   a$      → ancorada no inicio / no fim
+//   ^3^a         → ancorada na posicao 3 (a partir de 1)
+//   #--typos 2   → 1a linha: opcoes; os typos viram a distancia do --fuzzy
+// Linhas com ancora viram escolhas de posicao; as sem ancora, palavras
+// permutadas com alternativas. Deixar linhas de fora (frase menor que a
+// lista), ancoras de intervalo (^2,4^a) e curingas (%d) nao tem equivalente
+// no motor e sao recusados com a linha.
+
+use crate::mnemonic::WORD_COUNTS;
+use anyhow::Result;
+
+/// Options of the first `#--` line that are typo kinds covered by an edit distance.
+pub const TYPO_KINDS: [&str; 3] = ["--typos-replace", "--typos-insert", "--typos-delete"];
+
+/// A btcrecover tokenlist as [`SearchConfig`](crate::SearchConfig) fields: one word of the
+/// phrase per line.
+///
+/// ```
+/// use brute_force_mnemonics::tokenlist::Tokenlist;
+///
+/// let list = Tokenlist::parse("#--typos 1 --typos-replace\n^abandon\n+ ability able\n\
+///     about\nabove\nabsent\nabsorb\nabstract\nabsurd\nabuse\naccess\naccident\naccount$\n")?;
+/// assert_eq!(list.words.len(), 12);
+/// assert_eq!(list.choices, [(0, vec!["abandon".to_string()]), (11, vec!["account".to_string()])]);
+/// assert_eq!(list.alternatives, [(1, vec!["ability".to_string(), "able".to_string()])]);
+/// assert_eq!(list.typos, 1);
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Tokenlist {
+    /// The first token of each line: anchored lines at their position, the others in
+    /// file order in the positions left.
+    pub words:        Vec<String>,
+    /// Anchored lines: position (0-based) and its tokens.
+    pub choices:      Vec<(usize, Vec<String>)>,
+    /// Unanchored lines with several tokens: index into `words` and its tokens.
+    pub alternatives: Vec<(usize, Vec<String>)>,
+    /// Edit distance of `#--typos N` (0 without it).
+    pub typos:        usize,
+}
+
+// Ancora de um token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Anchor {
+    None,
+    At(usize),
+    End,
+}
+
+impl Tokenlist {
+    /// Parses the text of a tokenlist file.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut typos  = 0;
+        let mut placed = Vec::new();
+        let mut loose  = Vec::new();
+        for (lineno, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if lineno == 0 && line.starts_with("#--") {
+                typos = parse_options(&line[1..])?;
+                continue;
+            }
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let at = |e: String| anyhow::anyhow!("Line {}: {}", lineno + 1, e);
+            let tokens = line.strip_prefix('+').unwrap_or(line).split_whitespace()
+                .map(|t| token(t).map_err(at))
+                .collect::<Result<Vec<_>>>()?;
+            let Some(&(anchor, _)) = tokens.first() else {
+                return Err(at("a required line (+) needs at least one token".into()));
+            };
+            if tokens.iter().any(|(a, _)| *a != anchor) {
+                return Err(at("the tokens of a line must share one anchor (one of them goes in the phrase)".into()));
+            }
+            let words: Vec<String> = tokens.into_iter().map(|(_, w)| w).collect();
+            match anchor {
+                Anchor::None => loose.push(words),
+                _            => placed.push((lineno + 1, anchor, words)),
+            }
+        }
+
+        let n = placed.len() + loose.len();
+        if !WORD_COUNTS.contains(&n) {
+            anyhow::bail!("The tokenlist has {} lines; every line is one word of the phrase, so 12, 15, 18, 21 or 24 \
+                are expected (leaving optional lines out is not supported)", n);
+        }
+        let mut words: Vec<Option<String>> = vec![None; n];
+        let mut choices = Vec::new();
+        for (lineno, anchor, tokens) in placed {
+            let pos = match anchor {
+                Anchor::At(p) if p < n => p,
+                Anchor::At(p)          => anyhow::bail!("Line {}: position {} is out of range (1..={})", lineno, p + 1, n),
+                _                      => n - 1,
+            };
+            if words[pos].is_some() {
+                anyhow::bail!("Line {}: position {} is anchored twice", lineno, pos + 1);
+            }
+            words[pos] = Some(tokens[0].clone());
+            choices.push((pos, tokens));
+        }
+        choices.sort_unstable_by_key(|(pos, _)| *pos);
+
+        // Linhas soltas nas posicoes livres, na ordem do arquivo
+        let mut alternatives = Vec::new();
+        let mut loose = loose.into_iter();
+        for (i, word) in words.iter_mut().enumerate().filter(|(_, w)| w.is_none()) {
+            let tokens = loose.next().unwrap_or_default();
+            *word = tokens.first().cloned();
+            if tokens.len() > 1 {
+                alternatives.push((i, tokens));
+            }
+        }
+        let words = words.into_iter().map(Option::unwrap_or_default).collect();
+        Ok(Self { words, choices, alternatives, typos })
+    }
+}
+
+// "^3^word", "^word", "word$" ou "word"
+fn token(t: &str) -> Result<(Anchor, String), String> {
+    if t.contains('%') {
+        return Err(format!("'{}': wildcards (%) are not supported", t));
+    }
+    let (anchor, word) = match (t.strip_prefix('^'), t.strip_suffix('$')) {
+        (Some(_), Some(_)) => return Err(format!("'{}' is anchored at both ends", t)),
+        (Some(rest), None) => match rest.split_once('^') {
+            Some((pos, _)) if pos.contains(',') => {
+                return Err(format!("'{}': middle anchors (^A,B^) are not supported, only positions (^N^)", t))
+            }
+            Some((pos, word)) => match pos.parse::<usize>() {
+                Ok(p) if p > 0 => (Anchor::At(p - 1), word),
+                _              => return Err(format!("'{}': expected ^N^word with N from 1", t)),
+            },
+            None => (Anchor::At(0), rest),
+        },
+        (None, Some(word)) => (Anchor::End, word),
+        (None, None)       => (Anchor::None, t),
+    };
+    if word.is_empty() {
+        return Err(format!("'{}' has no word", t));
+    }
+    Ok((anchor, word.to_lowercase()))
+}
+
+// "--typos 2 --typos-replace ...": so a distancia de edicao tem equivalente
+fn parse_options(line: &str) -> Result<usize> {
+    let mut typos = None;
+    let mut kinds = false;
+    let mut args  = line.split_whitespace();
+    while let Some(arg) = args.next() {
+        match arg {
+            "--typos" => {
+                let n = args.next().and_then(|n| n.parse::<usize>().ok())
+                    .ok_or_else(|| anyhow::anyhow!("Line 1: --typos expects a number"))?;
+                if !(1..=2).contains(&n) {
+                    anyhow::bail!("Line 1: --typos {} is not supported (1 or 2, as --fuzzy)", n);
+                }
+                typos = Some(n);
+            }
+            _ if TYPO_KINDS.contains(&arg) => kinds = true,
+            _ => anyhow::bail!("Line 1: '{}' has no equivalent here (supported: --typos N, {})", arg, TYPO_KINDS.join(", ")),
+        }
+    }
+    // Tipos de typo sem --typos: um por palavra, como no btcrecover
+    Ok(typos.unwrap_or(usize::from(kinds)))
+}
@@ -0,0 +1,288 @@
+// ── Painel no terminal (--tui, feature "tui") ────────────────────────────────
+//
+// Um thread desenha o painel 4x por segundo a partir dos contadores da busca:
+// progresso + ETA, resumo da configuracao, candidato atual, grafico da vazao
+// (amostra por segundo) e uso de CPU por thread (Linux: /proc/self/task). Com
+// um acerto, o painel verde fica na tela ate uma tecla. q / Esc / Ctrl-C
+// param a busca como o SIGINT (o terminal em modo raw nao gera o sinal).
+
+use anyhow::Result;
+use brute_force_mnemonics::Match;
+use std::sync::atomic::{AtomicBool, AtomicU64};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// What the dashboard shows besides the live counters.
+#[cfg_attr(not(feature = "tui"), allow(dead_code))]
+pub struct Info {
+    pub counter: Arc<AtomicU64>,
+    pub current: Arc<AtomicU64>,
+    pub stop:    Arc<AtomicBool>,
+    pub to_test: u64,
+    pub start:   Instant,
+    /// (rotulo, valor) da configuracao, como no cabecalho
+    pub summary: Vec<(String, String)>,
+}
+
+/// Running dashboard; `finish` restores the terminal.
+pub struct Dashboard {
+    #[cfg(feature = "tui")]
+    inner: imp::Handle,
+}
+
+pub fn start(info: Info) -> Result<Dashboard> {
+    #[cfg(feature = "tui")]
+    {
+        Ok(Dashboard { inner: imp::spawn(info)? })
+    }
+    #[cfg(not(feature = "tui"))]
+    {
+        let _ = info;
+        anyhow::bail!("Built without TUI support. Rebuild with: cargo build --release --features tui")
+    }
+}
+
+impl Dashboard {
+    /// Stops drawing; with a match, shows it until a key is pressed.
+    pub fn finish(self, found: Option<&Match>) -> Result<()> {
+        #[cfg(feature = "tui")]
+        {
+            self.inner.finish(found)
+        }
+        #[cfg(not(feature = "tui"))]
+        {
+            let _ = found;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "tui")]
+mod imp {
+    use super::Info;
+    use crate::format_duration;
+    use anyhow::Result;
+    use brute_force_mnemonics::Match;
+    use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+    use ratatui::layout::{Alignment, Constraint, Layout};
+    use ratatui::style::{Color, Modifier, Style};
+    use ratatui::text::Line;
+    use ratatui::widgets::{BarChart, Block, Borders, Gauge, Paragraph, Sparkline, Wrap};
+    use ratatui::{DefaultTerminal, Frame};
+    use std::collections::{HashMap, VecDeque};
+    use std::sync::atomic::Ordering;
+    use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+    use std::thread::{self, JoinHandle};
+    use std::time::{Duration, Instant};
+
+    const FRAME: Duration = Duration::from_millis(250);
+    const SAMPLE: Duration = Duration::from_secs(1);
+    // Pontos do grafico de vazao (segundos)
+    const HISTORY: usize = 300;
+    // Ticks de CPU por segundo em /proc (USER_HZ, 100 em praticamente todo Linux)
+    const CLOCK_TICKS: f64 = 100.0;
+
+    // (frase, endereco, caminho) do acerto
+    type Found = Option<(String, String, String)>;
+
+    pub struct Handle {
+        tx:     Sender<Found>,
+        thread: JoinHandle<Result<()>>,
+    }
+
+    impl Handle {
+        pub fn finish(self, found: Option<&Match>) -> Result<()> {
+            let found = found.map(|m| (m.phrase.clone(), m.address.clone(), format!("m/{}", m.path)));
+            let _ = self.tx.send(found);
+            self.thread.join().map_err(|_| anyhow::anyhow!("TUI thread panicked"))?
+        }
+    }
+
+    pub fn spawn(info: Info) -> Result<Handle> {
+        let (tx, rx) = mpsc::channel();
+        let terminal = ratatui::try_init()?;
+        let thread = thread::spawn(move || {
+            let result = run(terminal, &info, &rx);
+            ratatui::restore();
+            result
+        });
+        Ok(Handle { tx, thread })
+    }
+
+    struct State {
+        rates:     VecDeque<u64>,
+        last:      (Instant, u64),
+        cpu:       Vec<(String, u64)>,
+        cpu_ticks: HashMap<u32, u64>,
+    }
+
+    fn run(mut terminal: DefaultTerminal, info: &Info, rx: &Receiver<Found>) -> Result<()> {
+        let mut state = State {
+            rates:     VecDeque::with_capacity(HISTORY),
+            last:      (Instant::now(), info.counter.load(Ordering::Relaxed)),
+            cpu:       Vec::new(),
+            cpu_ticks: thread_ticks(),
+        };
+        let found = loop {
+            match rx.recv_timeout(FRAME) {
+                Ok(found) => break found,
+                Err(RecvTimeoutError::Disconnected) => break None,
+                Err(RecvTimeoutError::Timeout) => {}
+            }
+            if state.last.0.elapsed() >= SAMPLE {
+                sample(&mut state, info);
+            }
+            terminal.draw(|f| draw(f, info, &state))?;
+            if stop_requested()? {
+                info.stop.store(true, Ordering::Relaxed);
+            }
+        };
+
+        if let Some(found) = found {
+            terminal.draw(|f| draw_found(f, &found))?;
+            // Espera uma tecla (evento de soltar tambem conta no Windows)
+            while !matches!(event::read()?, Event::Key(k) if k.kind == KeyEventKind::Press) {}
+        }
+        Ok(())
+    }
+
+    fn stop_requested() -> Result<bool> {
+        while event::poll(Duration::ZERO)? {
+            if let Event::Key(key) = event::read()? {
+                let ctrl_c = key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+                if key.kind == KeyEventKind::Press && (ctrl_c || matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)) {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    fn sample(state: &mut State, info: &Info) {
+        let now    = Instant::now();
+        let tested = info.counter.load(Ordering::Relaxed);
+        let dt     = now.duration_since(state.last.0).as_secs_f64();
+        if state.rates.len() == HISTORY {
+            state.rates.pop_front();
+        }
+        state.rates.push_back(((tested - state.last.1) as f64 / dt) as u64);
+        state.last = (now, tested);
+
+        // Uso de CPU por thread no ultimo intervalo, das mais ocupadas para as menos
+        let ticks = thread_ticks();
+        let mut usage: Vec<u64> = ticks.iter()
+            .map(|(tid, t)| t.saturating_sub(*state.cpu_ticks.get(tid).unwrap_or(t)))
+            .map(|d| (d as f64 / CLOCK_TICKS / dt * 100.0).round().min(100.0) as u64)
+            .collect();
+        usage.sort_unstable_by(|a, b| b.cmp(a));
+        state.cpu = usage.into_iter().enumerate().map(|(i, u)| (format!("t{}", i + 1), u)).collect();
+        state.cpu_ticks = ticks;
+    }
+
+    // utime + stime (campos 14 e 15) de cada thread do processo
+    fn thread_ticks() -> HashMap<u32, u64> {
+        let Ok(dir) = std::fs::read_dir("/proc/self/task") else { return HashMap::new() };
+        dir.flatten()
+            .filter_map(|entry| {
+                let tid  = entry.file_name().to_str()?.parse().ok()?;
+                let stat = std::fs::read_to_string(entry.path().join("stat")).ok()?;
+                // O nome do thread vem entre parenteses e pode ter espacos
+                let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+                let ticks = fields.get(11)?.parse::<u64>().ok()? + fields.get(12)?.parse::<u64>().ok()?;
+                Some((tid, ticks))
+            })
+            .collect()
+    }
+
+    fn draw(f: &mut Frame, info: &Info, state: &State) {
+        let tested  = info.counter.load(Ordering::Relaxed);
+        let elapsed = info.start.elapsed().as_secs_f64();
+        let rate    = if elapsed > 0.0 { tested as f64 / elapsed } else { 0.0 };
+        let ratio   = if info.to_test > 0 { (tested as f64 / info.to_test as f64).min(1.0) } else { 0.0 };
+        let eta = match info.to_test.checked_sub(tested) {
+            Some(left) if rate > 0.0 => format_duration(left as f64 / rate),
+            _ => "-".to_string(),
+        };
+
+        let [title, progress, middle, graph, threads] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Min(8),
+            Constraint::Length(8),
+            Constraint::Length(8),
+        ]).areas(f.area());
+
+        f.render_widget(
+            Paragraph::new(" brute_force_mnemonics — q to stop")
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+            title);
+
+        f.render_widget(
+            Gauge::default()
+                .block(Block::default().borders(Borders::ALL).title(" Progress "))
+                .gauge_style(Style::default().fg(Color::Cyan))
+                .ratio(ratio)
+                .label(format!("{:.2}%  {}/{}  ETA {}", ratio * 100.0, tested, info.to_test, eta)),
+            progress);
+
+        let [summary, stats] = Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .areas(middle);
+        let lines: Vec<Line> = info.summary.iter().map(|(k, v)| Line::from(format!("{:<18}{}", k, v))).collect();
+        f.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(Block::default().borders(Borders::ALL).title(" Search ")),
+            summary);
+        let stats_lines = vec![
+            Line::from(format!("Elapsed   : {}", format_duration(elapsed))),
+            Line::from(format!("Avg speed : {:.1}/s", rate)),
+            Line::from(format!("Now       : {}/s", state.rates.back().copied().unwrap_or(0))),
+            Line::from(format!("Candidate : #{}", info.current.load(Ordering::Relaxed))),
+            Line::from(format!("ETA       : {}", eta)),
+        ];
+        f.render_widget(
+            Paragraph::new(stats_lines).block(Block::default().borders(Borders::ALL).title(" Live ")),
+            stats);
+
+        let width = graph.width.saturating_sub(2) as usize;
+        let data: Vec<u64> = state.rates.iter().rev().take(width).rev().copied().collect();
+        f.render_widget(
+            Sparkline::default()
+                .block(Block::default().borders(Borders::ALL).title(" Throughput (permutations/s) "))
+                .style(Style::default().fg(Color::Green))
+                .data(&data),
+            graph);
+
+        let bars: Vec<(&str, u64)> = state.cpu.iter().map(|(name, u)| (name.as_str(), *u)).collect();
+        let title = if bars.is_empty() { " CPU per thread (n/a) " } else { " CPU per thread (%) " };
+        f.render_widget(
+            BarChart::default()
+                .block(Block::default().borders(Borders::ALL).title(title))
+                .data(&bars)
+                .bar_width(4)
+                .max(100)
+                .bar_style(Style::default().fg(Color::Yellow)),
+            threads);
+    }
+
+    fn draw_found(f: &mut Frame, (phrase, address, path): &(String, String, String)) {
+        let green = Style::default().fg(Color::Black).bg(Color::Green);
+        let text = vec![
+            Line::from(""),
+            Line::from("✓ FOUND MATCHING MNEMONIC!").style(green.add_modifier(Modifier::BOLD)),
+            Line::from(""),
+            Line::from(phrase.as_str()),
+            Line::from(""),
+            Line::from(format!("{}  ({})", address, path)),
+            Line::from(""),
+            Line::from("press any key"),
+        ];
+        f.render_widget(
+            Paragraph::new(text)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true })
+                .style(green)
+                .block(Block::default().borders(Borders::ALL).title(" Match ")),
+            f.area());
+    }
+}
@@ -0,0 +1,245 @@
+// ── Snapshot do UTXO set (bitcoin-cli dumptxoutset) ──────────────────────────
+//
+// Formato 2 (Bitcoin Core 28+): "utxo" 0xff | versao u16 | magic da rede (4) |
+// hash do bloco base (32) | moedas u64; depois, por transacao: txid (32) |
+// CompactSize de saidas | por saida: CompactSize do indice e a Coin.
+// Formato antigo (0.21 a 27): hash do bloco base | moedas u64; por saida:
+// txid (32) | indice u32 | Coin. Sem o magic, a rede e a do --coin.
+//
+// Coin: VARINT(altura·2 + coinbase) | VARINT(valor comprimido) | script
+// comprimido: VARINT n; n = 0/1 → hash160 P2PKH/P2SH, 2-5 → chave de um P2PK
+// (32 bytes), senao n - 6 bytes de script. VARINT aqui e o do serialize.h
+// (base 128, big-endian, +1 por byte de continuacao), nao o CompactSize.
+
+use crate::coin::Coin;
+use crate::derivation::Payload;
+use anyhow::{Context, Result};
+use bitcoin::hashes::Hash;
+use bitcoin::hex::DisplayHex;
+use bitcoin::BlockHash;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+const MAGIC: [u8; 5] = *b"utxo\xff";
+// Scripts com forma comprimida: P2PKH, P2SH e os quatro P2PK
+const SPECIAL_SCRIPTS: u64 = 6;
+const MAX_SCRIPT_SIZE: u64 = 10_000;
+
+/// What the header of a snapshot says.
+#[derive(Debug, Clone, Copy)]
+pub struct Snapshot {
+    pub coin:       Coin,
+    /// Block the UTXO set was taken at
+    pub base_block: BlockHash,
+    /// Unspent outputs in the snapshot
+    pub coins:      u64,
+}
+
+/// Does the file start like a `dumptxoutset` snapshot? The current format has a magic;
+/// the older one starts with a block hash, whose high bytes are zero (a text dump never
+/// has a NUL byte).
+pub fn is_snapshot(path: &Path) -> Result<bool> {
+    let mut head = Vec::with_capacity(32);
+    File::open(path)
+        .and_then(|f| f.take(32).read_to_end(&mut head))
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(head.starts_with(&MAGIC) || (head.len() == 32 && head.contains(&0)))
+}
+
+/// Reads the snapshot `path` and calls `f` with the payload and value of every unspent
+/// output a single key can derive (P2PKH, P2SH, P2WPKH, P2TR); the others (P2PK, P2WSH,
+/// bare multisig, ...) are only counted, as the second value returned. `coin` is required by the older format, which does not name its network.
+///
+/// ```
+/// use brute_force_mnemonics::utxo::read_snapshot;
+/// use brute_force_mnemonics::derivation::Payload;
+///
+/// // Format 2, mainnet, two outputs of one transaction: 50 BTC to a P2PKH, 1 sat to a P2WPKH
+/// let mut snapshot = b"utxo\xff\x02\x00\xf9\xbe\xb4\xd9".to_vec();
+/// snapshot.extend([0; 32]);
+/// snapshot.extend(2u64.to_le_bytes());
+/// snapshot.extend([7; 32]);
+/// snapshot.extend([2, 0, 0x03, 0x32, 0x00]);
+/// snapshot.extend([0x11; 20]);
+/// snapshot.extend([1, 0x03, 0x01, 28, 0x00, 0x14]);
+/// snapshot.extend([0x22; 20]);
+///
+/// let path = std::env::temp_dir().join(format!("utxo-doc-{}.dat", std::process::id()));
+/// std::fs::write(&path, snapshot)?;
+/// let mut outputs = Vec::new();
+/// let (info, skipped) = read_snapshot(&path, None, |payload, value| {
+///     outputs.push((payload, value));
+///     Ok(())
+/// })?;
+/// assert_eq!((info.coin, info.coins, skipped), (brute_force_mnemonics::Coin::Bitcoin, 2, 0));
+/// assert_eq!(outputs, [(Payload::PubkeyHash([0x11; 20]), 5_000_000_000), (Payload::WitnessV0([0x22; 20]), 1)]);
+/// # std::fs::remove_file(&path)?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub fn read_snapshot(path: &Path, coin: Option<Coin>, mut f: impl FnMut(Payload, u64) -> Result<()>) -> Result<(Snapshot, u64)> {
+    let file = File::open(path).with_context(|| format!("Failed to read snapshot {}", path.display()))?;
+    let mut r = Reader(BufReader::with_capacity(1 << 20, file));
+    let context = || format!("Truncated or corrupt snapshot {}", path.display());
+
+    let mut head = [0u8; 5];
+    r.bytes(&mut head).with_context(context)?;
+    let grouped = head == MAGIC;
+    let (network, base_block) = if grouped {
+        let mut version = [0u8; 2];
+        r.bytes(&mut version).with_context(context)?;
+        if u16::from_le_bytes(version) != 2 {
+            anyhow::bail!("Snapshot {} has format version {}; only 2 is supported", path.display(), u16::from_le_bytes(version));
+        }
+        let mut magic = [0u8; 4];
+        r.bytes(&mut magic).with_context(context)?;
+        let mut hash = [0u8; 32];
+        r.bytes(&mut hash).with_context(context)?;
+        (Some(magic), hash)
+    } else {
+        // Formato antigo: os 5 bytes lidos ja sao do hash do bloco base
+        let mut hash = [0u8; 32];
+        hash[..5].copy_from_slice(&head);
+        r.bytes(&mut hash[5..]).with_context(context)?;
+        (None, hash)
+    };
+    let coin = match (network.map(network_coin), coin) {
+        (Some(Some(c)), Some(given)) if c != given => {
+            anyhow::bail!("Snapshot {} is of {}, not {}", path.display(), c.name(), given.name())
+        }
+        (Some(Some(c)), _) | (_, Some(c)) => c,
+        (Some(None), None) => anyhow::bail!(
+            "Snapshot {} is of an unknown network ({}); give its --coin", path.display(), network.unwrap_or_default().to_lower_hex_string()),
+        (None, None) => anyhow::bail!("Snapshot {} is in the format before Bitcoin Core 28, which does not name the network; give its --coin", path.display()),
+    };
+    let mut count = [0u8; 8];
+    r.bytes(&mut count).with_context(context)?;
+    let coins = u64::from_le_bytes(count);
+
+    let mut read    = 0;
+    let mut skipped = 0;
+    let mut skip    = [0u8; 36];
+    while read < coins {
+        // Formato 2: txid e o numero de saidas dela; antigo: txid e indice por saida
+        let outputs = if grouped {
+            r.bytes(&mut skip[..32]).with_context(context)?;
+            r.compact_size().with_context(context)?
+        } else {
+            1
+        };
+        for _ in 0..outputs {
+            if grouped {
+                r.compact_size().with_context(context)?;
+            } else {
+                r.bytes(&mut skip).with_context(context)?;
+            }
+            let _code = r.varint().with_context(context)?;
+            let value = decompress_amount(r.varint().with_context(context)?);
+            match r.script().with_context(context)? {
+                Some(payload) => f(payload, value)?,
+                None          => skipped += 1,
+            }
+            read += 1;
+        }
+    }
+    let base_block = BlockHash::from_byte_array(base_block);
+    Ok((Snapshot { coin, base_block, coins }, skipped))
+}
+
+// pchMessageStart de cada rede (signet: o padrao; outros signets tem o seu)
+fn network_coin(magic: [u8; 4]) -> Option<Coin> {
+    match magic {
+        [0xf9, 0xbe, 0xb4, 0xd9] => Some(Coin::Bitcoin),
+        [0x0b, 0x11, 0x09, 0x07] | [0x1c, 0x16, 0x3f, 0x28] | [0x0a, 0x03, 0xcf, 0x40] => Some(Coin::Testnet),
+        [0xfa, 0xbf, 0xb5, 0xda] => Some(Coin::Regtest),
+        _ => None,
+    }
+}
+
+// DecompressAmount do compressor.cpp: expoente de 10 e digitos sem os zeros finais
+fn decompress_amount(x: u64) -> u64 {
+    if x == 0 {
+        return 0;
+    }
+    let mut x = x - 1;
+    let e = x % 10;
+    x /= 10;
+    let mut n = if e < 9 {
+        let d = x % 9 + 1;
+        x /= 9;
+        x * 10 + d
+    } else {
+        x + 1
+    };
+    for _ in 0..e {
+        n = n.saturating_mul(10);
+    }
+    n
+}
+
+struct Reader<R>(R);
+
+impl<R: Read> Reader<R> {
+    fn bytes(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        self.0.read_exact(buf)
+    }
+
+    fn byte(&mut self) -> std::io::Result<u8> {
+        let mut b = [0u8; 1];
+        self.0.read_exact(&mut b)?;
+        Ok(b[0])
+    }
+
+    fn varint(&mut self) -> Result<u64> {
+        let mut n: u64 = 0;
+        loop {
+            let b = self.byte()?;
+            n = n.checked_mul(128).context("VARINT overflow")? | u64::from(b & 0x7f);
+            if b & 0x80 == 0 {
+                return Ok(n);
+            }
+            n = n.checked_add(1).context("VARINT overflow")?;
+        }
+    }
+
+    fn compact_size(&mut self) -> Result<u64> {
+        let mut read = |len: usize| -> std::io::Result<u64> {
+            let mut buf = [0u8; 8];
+            self.0.read_exact(&mut buf[..len])?;
+            Ok(u64::from_le_bytes(buf))
+        };
+        Ok(match read(1)? {
+            0xfd => read(2)?,
+            0xfe => read(4)?,
+            0xff => read(8)?,
+            n    => n,
+        })
+    }
+
+    // Payload do script comprimido; None para os que nao saem de uma chave so
+    fn script(&mut self) -> Result<Option<Payload>> {
+        let size = self.varint()?;
+        let mut hash = [0u8; 20];
+        match size {
+            0 | 1 => {
+                self.bytes(&mut hash)?;
+                Ok(Some(if size == 0 { Payload::PubkeyHash(hash) } else { Payload::ScriptHash(hash) }))
+            }
+            2..=5 => {
+                self.bytes(&mut [0u8; 32])?;
+                Ok(None)
+            }
+            _ => {
+                let len = size - SPECIAL_SCRIPTS;
+                // Como o Bitcoin Core: longo demais e so pulado
+                if len > MAX_SCRIPT_SIZE {
+                    std::io::copy(&mut (&mut self.0).take(len), &mut std::io::sink())?;
+                    return Ok(None);
+                }
+                let mut script = vec![0u8; len as usize];
+                self.bytes(&mut script)?;
+                Ok(Payload::from_script(bitcoin::Script::from_bytes(&script)))
+            }
+        }
+    }
+}
@@ -0,0 +1,172 @@
+// ── Assistente interativo (subcomando `interactive`) ─────────────────────────
+//
+// Pergunta passo a passo o que o usuario sabe (palavras, ordem, lacunas,
+// passphrase, endereco) e monta os mesmos argumentos da linha de comando: a
+// busca passa pela validacao normal de `Args`. As palavras nunca vao para o
+// historico do shell; o comando equivalente e mostrado com --words-file.
+
+use anyhow::Result;
+use bip39::{Language, Mnemonic};
+use brute_force_mnemonics::coin;
+use brute_force_mnemonics::derivation::is_extended_pubkey;
+use brute_force_mnemonics::mnemonic::{
+    find_word, fuzzy_matches, language_name, normalize, parse_language, prefix_matches, WORD_COUNTS,
+};
+use brute_force_mnemonics::permutation::WILDCARD;
+use std::io::{self, BufRead, Write};
+
+// Sugestoes mostradas para uma palavra fora da wordlist
+const MAX_SUGGESTIONS: usize = 6;
+// Trocas oferecidas quando a ordem e "quase certa"
+const DEFAULT_SWAPS: &str = "2";
+// Enderecos por caminho checados sem alvo (--discover)
+const DEFAULT_DISCOVER: &str = "5";
+
+fn prompt(question: &str, default: &str) -> Result<String> {
+    if default.is_empty() {
+        print!("{}: ", question);
+    } else {
+        print!("{} [{}]: ", question, default);
+    }
+    io::stdout().flush()?;
+    let mut line = String::new();
+    if io::stdin().lock().read_line(&mut line)? == 0 {
+        anyhow::bail!("Aborted (end of input)");
+    }
+    let answer = line.trim();
+    Ok(if answer.is_empty() { default.to_string() } else { answer.to_string() })
+}
+
+fn confirm(question: &str, default: bool) -> Result<bool> {
+    loop {
+        let answer = prompt(&format!("{} ({})", question, if default { "Y/n" } else { "y/N" }), "")?;
+        match answer.to_lowercase().as_str() {
+            ""                        => return Ok(default),
+            "y" | "yes" | "s" | "sim" => return Ok(true),
+            "n" | "no" | "nao"        => return Ok(false),
+            _ => println!("  Answer y or n."),
+        }
+    }
+}
+
+fn ask_word(position: usize, count: usize, language: Language) -> Result<String> {
+    let wordlist = language.word_list();
+    loop {
+        let word = normalize(&prompt(&format!("Word {}/{} (? if missing)", position, count), "")?.to_lowercase());
+        if word == WILDCARD {
+            return Ok(word);
+        }
+        // Sem acento tambem vale; guarda a grafia da wordlist
+        if let Some(i) = find_word(&word, language) {
+            return Ok(wordlist[i as usize].to_string());
+        }
+        if word.is_empty() {
+            continue;
+        }
+        let mut suggestions = prefix_matches(&word, wordlist, false);
+        if suggestions.is_empty() {
+            suggestions = fuzzy_matches(&word, wordlist, 2);
+        }
+        let names: Vec<&str> = suggestions.iter().take(MAX_SUGGESTIONS).map(|&i| wordlist[i as usize]).collect();
+        if names.is_empty() {
+            println!("  '{}' is not in the wordlist and nothing is close; type it again or use ?", word);
+        } else {
+            println!("  '{}' is not in the wordlist. Did you mean: {}?", word, names.join(", "));
+        }
+    }
+}
+
+/// Asks what is known about the mnemonic and returns the equivalent command line
+/// (program name first), ready for `Args::try_parse_from`.
+pub fn run() -> Result<Vec<String>> {
+    println!("Recovery wizard — press Enter to accept the [default]. Ctrl-C aborts.\n");
+
+    // ── Palavras ─────────────────────────────────────────────────────────────
+    let count: usize = loop {
+        let answer = prompt("How many words does the mnemonic have", "12")?;
+        match answer.parse() {
+            Ok(n) if WORD_COUNTS.contains(&n) => break n,
+            _ => println!("  Expected 12, 15, 18, 21 or 24."),
+        }
+    };
+    let language: Language = loop {
+        match parse_language(&prompt("Language", "english")?) {
+            Ok(l) => break l,
+            Err(e) => println!("  {}", e),
+        }
+    };
+    let words = (1..=count).map(|i| ask_word(i, count, language)).collect::<Result<Vec<_>>>()?;
+    let missing = words.iter().filter(|w| w.as_str() == WILDCARD).count();
+
+    // ── Ordem ────────────────────────────────────────────────────────────────
+    let mut args: Vec<String> = Vec::new();
+    let mut order_known = confirm("Are the words in the correct order", false)?;
+    if order_known && missing == 0 && Mnemonic::parse_in(language, words.join(" ")).is_err() {
+        println!("  This order fails the BIP-39 checksum, so it is not the right one.");
+        order_known = false;
+    }
+    if order_known {
+        // Todas as palavras conhecidas ficam no lugar; so as lacunas variam
+        for (i, w) in words.iter().enumerate().filter(|(_, w)| w.as_str() != WILDCARD) {
+            args.push("--fixed".into());
+            args.push(format!("{}={}", i + 1, w));
+        }
+    } else if confirm("Is the order roughly right (only a few words swapped)", true)? {
+        let swaps = prompt("At most how many swaps", DEFAULT_SWAPS)?;
+        args.extend(["--max-swaps".into(), swaps]);
+    }
+
+    // ── Passphrase ───────────────────────────────────────────────────────────
+    if confirm("Did the wallet use a passphrase (25th word)", false)? {
+        if order_known && missing == 0 && confirm("Try a list of candidate passphrases from a file", false)? {
+            let path = prompt("Passphrase list file (one per line)", "")?;
+            // --passphrase-list ja fixa todas as palavras
+            args = vec!["--passphrase-list".into(), path];
+        } else {
+            println!("  (the passphrase is shown as you type it)");
+            args.extend(["--passphrase".into(), prompt("Passphrase", "")?]);
+        }
+    }
+
+    // ── Alvo ─────────────────────────────────────────────────────────────────
+    let mut target = String::new();
+    while target.is_empty() {
+        let answer = prompt("A known address or account xpub of the wallet (Enter if none)", "-")?;
+        if answer == "-" || is_extended_pubkey(&answer) || coin::detect(&answer).is_some() {
+            target = answer;
+        } else if let Err(e) = coin::classify(&answer) {
+            println!("  {}; leave empty if you don't know any.", e);
+        } else {
+            println!("  Unrecognized address; leave empty if you don't know any.");
+        }
+    }
+    let mut positional = vec![target.clone()];
+    if target == "-" {
+        println!("  Without an address, derived addresses are looked up on an Electrum server.");
+        let server = prompt("Electrum server HOST:PORT (plain TCP)", "127.0.0.1:50001")?;
+        let discover = prompt("Addresses to check per path", DEFAULT_DISCOVER)?;
+        positional.clear();
+        args.extend(["--electrum".into(), server, "--discover".into(), discover]);
+    } else if !is_extended_pubkey(&target) && confirm("Check both receive and change addresses", false)? {
+        args.extend(["--chain".into(), "both".into()]);
+    }
+    args.extend(["--language".into(), language_name(language).to_string()]);
+
+    // ── Resumo ───────────────────────────────────────────────────────────────
+    let shown: Vec<&str> = args.iter().enumerate()
+        .map(|(i, a)| if i > 0 && args[i - 1] == "--passphrase" { "'...'" } else { a.as_str() })
+        .collect();
+    println!("\nEquivalent command (put the {} words in words.txt):", count);
+    println!("  brute_force_mnemonics {}--words-file words.txt {}\n",
+        positional.iter().map(|p| format!("{} ", p)).collect::<String>(),
+        shown.join(" "));
+    if !confirm("Start the search now", true)? {
+        anyhow::bail!("Search not started");
+    }
+
+    let mut argv = vec!["brute_force_mnemonics".to_string()];
+    argv.extend(positional);
+    argv.extend(words);
+    argv.extend(args);
+    Ok(argv)
+}